@@ -2,9 +2,27 @@ use crate::{rdi, MEM_BATTERY};
 
 /// Returns the remaining battery energy.
 ///
-/// Since battery is not simulated at the moment, this function doesn't come
-/// useful.
-#[doc(hidden)]
+/// Battery holds up to 4096 units of energy - each bot gets born with a fully
+/// charged battery.
+///
+/// Depending on the world's configuration, moving, turning, using the arm,
+/// scanning with the radar and sending radio messages might cost energy; if the
+/// battery gets depleted, the bot dies.
+///
+/// Standing next to a charger (`'%'`) slowly recharges the battery.
+///
+/// On worlds where actions don't cost energy, this function always returns
+/// 4096.
+///
+/// # Example
+///
+/// ```no_run
+/// # use kartoffel::*;
+/// #
+/// if battery_energy() < 512 {
+///     // time to look for a charger
+/// }
+/// ```
 #[inline(always)]
 pub fn battery_energy() -> u32 {
     rdi(MEM_BATTERY, 0)
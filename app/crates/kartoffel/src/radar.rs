@@ -170,13 +170,36 @@ pub fn radar_scan_9x9() -> RadarScan<9> {
 ///
 /// Meaning of the returned number depends on `z`:
 ///
-/// - `z=0` returns the tile located at `dx,dy` (see: [`RadarScan::at()`]),
+/// - `z=0` returns the tile located at `dx,dy` in the lowest byte (see:
+///   [`RadarScan::at()`]); for bots and flags that belong to a team, the
+///   second byte is set to 1 and the third byte contains the team (see:
+///   [`RadarScan::team_at()`]),
 ///
 /// - `z=1` returns the higher 32 bits of the id of the bot located at `dx,dy`
 ///   (see: [`RadarScan::bot_at()`]),
 ///
 /// - `z=2` returns the lower 32 bits of the id of the bot located at `dx,dy`
 ///   (see: [`RadarScan::bot_at()`]).
+///
+/// # Compatibility
+///
+/// Before teams were introduced, `z=0` always fit in a single byte - since
+/// bots and flags that belong to a team now have the upper bytes set, code
+/// that compares the returned number against a tile directly should mask it
+/// first:
+///
+/// ```no_run
+/// # use kartoffel::*;
+/// #
+/// radar_wait();
+/// radar_scan_3x3();
+///
+/// // Correct, works for bots with and without teams
+/// let is_bot = radar_read(3, 0, -1, 0) as u8 == b'@';
+///
+/// // Incorrect, returns `false` for bots that belong to a team
+/// let is_bot = radar_read(3, 0, -1, 0) == '@' as u32;
+/// ```
 pub fn radar_read(r: usize, dx: i8, dy: i8, z: u8) -> u32 {
     let x = (dx + (r as i8 / 2)) as usize;
     let y = (dy + (r as i8 / 2)) as usize;
@@ -242,7 +265,8 @@ impl<const R: usize> RadarScan<R> {
     ///
     /// - if there's a bot, returns `'@'`,
     /// - otherwise, if there's an object, returns that object (e.g. `'*'`),
-    /// - otherwise, if there's a tile, returns that tile (e.g. `'.'` or `'|'`),
+    /// - otherwise, if there's a tile, returns that tile (e.g. `'.'`, `'|'` or
+    ///   `'%'` for a charger),
     /// - otherwise returns `' '` (a space) representing void (driving into it
     ///   makes you fall out of the map and die).
     ///
@@ -270,4 +294,39 @@ impl<const R: usize> RadarScan<R> {
 
         NonZeroU64::new((d1 << 32) | d2)
     }
+
+    /// Returns team of the bot or flag at given coordinates or `None` if
+    /// there's no bot or flag there or it doesn't belong to any team.
+    ///
+    /// In capture-the-flag, each team has its own flag - comparing flag's team
+    /// against yours tells whether it's worth bringing to your base.
+    ///
+    /// Teams are assigned when bots are uploaded - comparing the result against
+    /// your own team (`team_at(0, 0)`) allows to tell friend from foe.
+    ///
+    /// # Coordinate system
+    ///
+    /// This function uses bot-centric coordinates, i.e. `team_at(0, -1)` points
+    /// at the bot right in front of you - see [`RadarScan`] for details.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use kartoffel::*;
+    /// #
+    /// radar_wait();
+    ///
+    /// let scan = radar_scan_3x3();
+    ///
+    /// // Stab whoever's in front of us, unless it's a friend
+    /// if scan.at(0, -1) == '@' && scan.team_at(0, -1) != scan.team_at(0, 0) {
+    ///     arm_wait();
+    ///     arm_stab();
+    /// }
+    /// ```
+    pub fn team_at(&self, dx: i8, dy: i8) -> Option<u8> {
+        let [_, has_team, team, _] = radar_read(R, dx, dy, 0).to_le_bytes();
+
+        (has_team == 1).then_some(team)
+    }
 }
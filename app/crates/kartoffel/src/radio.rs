@@ -0,0 +1,103 @@
+use crate::{cmd, rdi, wri, MEM_RADIO};
+use core::num::NonZeroU64;
+
+/// Returns whether the radio is ready and [`radio_send()`] can be invoked.
+///
+/// See also: [`radio_wait()`].
+#[inline(always)]
+pub fn is_radio_ready() -> bool {
+    rdi(MEM_RADIO, 0) == 1
+}
+
+/// Waits for the radio to become ready.
+///
+/// See also: [`is_radio_ready()`].
+#[inline(always)]
+pub fn radio_wait() {
+    while !is_radio_ready() {
+        //
+    }
+}
+
+/// Broadcasts a message to all bots within given range.
+///
+/// Range is measured in tiles, in a square around the bot - e.g. `r=2` reaches
+/// all bots in the `5x5` area around you. Legal values of `r` are 1 up to 16 -
+/// other values will cause the CPU to crash.
+///
+/// Each receiving bot gets the message put into its inbox, see
+/// [`radio_recv()`]. If the bot's inbox is full (16 messages), the oldest
+/// message gets overwritten.
+///
+/// # Cooldown
+///
+/// ```text
+/// 20_000 +- 15% ticks (~310 ms)
+/// ```
+///
+/// # Example
+///
+/// ```no_run
+/// # use kartoffel::*;
+/// #
+/// radio_wait();
+/// radio_send(8, &[0xcafebabe, 0, 0, 0, 0, 0, 0, 0]);
+/// ```
+#[inline(always)]
+pub fn radio_send(r: u8, data: &[u32; 8]) {
+    for (idx, val) in data.iter().enumerate() {
+        wri(MEM_RADIO, 2 + idx, *val);
+    }
+
+    wri(MEM_RADIO, 0, cmd(0x01, r, 0x00, 0x00));
+}
+
+/// Returns the number of messages waiting in the inbox.
+#[inline(always)]
+pub fn radio_pending() -> u32 {
+    rdi(MEM_RADIO, 1)
+}
+
+/// Takes the oldest message from the inbox, returning `None` if the inbox is
+/// empty.
+///
+/// # Example
+///
+/// ```no_run
+/// # use kartoffel::*;
+/// #
+/// while let Some(msg) = radio_recv() {
+///     if msg.data[0] == 0xcafebabe {
+///         // friend spotted!
+///     }
+/// }
+/// ```
+#[inline(always)]
+pub fn radio_recv() -> Option<RadioMessage> {
+    if radio_pending() == 0 {
+        return None;
+    }
+
+    let from = {
+        let d1 = rdi(MEM_RADIO, 16) as u64;
+        let d2 = rdi(MEM_RADIO, 17) as u64;
+
+        NonZeroU64::new((d1 << 32) | d2)?
+    };
+
+    let data = core::array::from_fn(|idx| rdi(MEM_RADIO, 18 + idx));
+
+    wri(MEM_RADIO, 0, cmd(0x02, 0x00, 0x00, 0x00));
+
+    Some(RadioMessage { from, data })
+}
+
+/// Message received through [`radio_recv()`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RadioMessage {
+    /// Id of the bot that sent the message.
+    pub from: NonZeroU64,
+
+    /// Message's payload.
+    pub data: [u32; 8],
+}
@@ -1,4 +1,4 @@
-use crate::{wri, MEM_SERIAL};
+use crate::{rdi, wri, MEM_SERIAL};
 use core::fmt;
 
 /// Writes a single character to the serial port.
@@ -7,6 +7,12 @@ use core::fmt;
 /// writing 257th character shifts all the previous characters, removing the
 /// first one.
 ///
+/// Serial port is displayed through a virtual terminal which understands a
+/// subset of ANSI escape sequences - colors (`\x1b[31m`), clearing the screen
+/// (`\x1b[2J`), positioning the cursor (`\x1b[3;5H`) etc. - so you can draw
+/// dashboards and maps. Combined with [`serial_buffer()`] this allows to redraw
+/// the screen without flickering.
+///
 /// Note that this is a low-level function - for convenience you'll most likely
 /// want to use [`print!()`](crate::print!()) or
 /// [`println!()`](crate::println!()).
@@ -81,6 +87,49 @@ pub fn serial_clear() {
     wri(MEM_SERIAL, 0, 0xffffff02);
 }
 
+/// Returns the number of characters sent to the bot that haven't been read
+/// yet.
+///
+/// See also: [`serial_read()`].
+#[inline(always)]
+pub fn serial_pending() -> u32 {
+    rdi(MEM_SERIAL, 1)
+}
+
+/// Reads a single character sent to the bot, returning `None` if there's
+/// nothing to read.
+///
+/// Players can send messages to their bots through the game's UI - the input
+/// buffer has capacity for 256 characters and messages that don't fit get
+/// rejected.
+///
+/// # Example
+///
+/// ```no_run
+/// # use kartoffel::*;
+/// #
+/// loop {
+///     match serial_read() {
+///         Some('w') => motor_step_fw(),
+///         Some('a') => motor_turn_left(),
+///         Some('d') => motor_turn_right(),
+///         _ => (),
+///     }
+/// }
+/// ```
+#[inline(always)]
+pub fn serial_read() -> Option<char> {
+    if serial_pending() == 0 {
+        return None;
+    }
+
+    let ch = rdi(MEM_SERIAL, 2);
+
+    wri(MEM_SERIAL, 0, 0xffffff03);
+
+    char::from_u32(ch)
+}
+
 /// Allows to `write!()` and `writeln!()` into the serial port.
 ///
 /// See also: [`print!()`](crate::print!()), [`println!()`](crate::println!()).
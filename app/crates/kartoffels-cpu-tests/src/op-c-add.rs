@@ -0,0 +1,19 @@
+#![cfg_attr(target_arch = "riscv32", no_std, no_main)]
+
+kartoffels_cpu_tests::test! {
+    r#"
+    .global _start
+    .attribute arch, "rv32ic"
+
+    _start:
+        li x5, 10
+        li x6, 20
+        c.add x5, x6
+        ebreak
+    "#
+}
+
+/*
+ * x5 = 30
+ * x6 = 20
+ */
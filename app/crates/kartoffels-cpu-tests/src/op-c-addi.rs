@@ -0,0 +1,20 @@
+#![cfg_attr(target_arch = "riscv32", no_std, no_main)]
+
+kartoffels_cpu_tests::test! {
+    r#"
+    .global _start
+    .attribute arch, "rv32ic"
+
+    _start:
+        li x5, 10
+        c.addi x5, 31
+        li x6, 10
+        c.addi x6, -32
+        ebreak
+    "#
+}
+
+/*
+ * x5 = 41
+ * x6 = -22
+ */
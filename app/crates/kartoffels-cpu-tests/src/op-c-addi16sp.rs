@@ -0,0 +1,21 @@
+#![cfg_attr(target_arch = "riscv32", no_std, no_main)]
+
+kartoffels_cpu_tests::test! {
+    r#"
+    .global _start
+    .attribute arch, "rv32ic"
+
+    _start:
+        li x2, 0x00102000
+        c.addi16sp x2, -64
+        li x3, 0x00102000
+        c.addi16sp x2, 496
+        sub x3, x2, x3
+        ebreak
+    "#
+}
+
+/*
+ * x2 = 0x001021b0
+ * x3 = 432
+ */
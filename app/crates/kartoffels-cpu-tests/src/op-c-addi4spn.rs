@@ -0,0 +1,20 @@
+#![cfg_attr(target_arch = "riscv32", no_std, no_main)]
+
+kartoffels_cpu_tests::test! {
+    r#"
+    .global _start
+    .attribute arch, "rv32ic"
+
+    _start:
+        li x2, 0x00102000
+        c.addi4spn x8, x2, 1020
+        c.addi4spn x9, x2, 4
+        ebreak
+    "#
+}
+
+/*
+ * x2 = 0x00102000
+ * x8 = 0x001023fc
+ * x9 = 0x00102004
+ */
@@ -0,0 +1,20 @@
+#![cfg_attr(target_arch = "riscv32", no_std, no_main)]
+
+kartoffels_cpu_tests::test! {
+    r#"
+    .global _start
+    .attribute arch, "rv32ic"
+
+    _start:
+        li x8, 0xff
+        c.andi x8, 0x0f
+        li x9, 0xff
+        c.andi x9, -2
+        ebreak
+    "#
+}
+
+/*
+ * x8 = 0x0f
+ * x9 = 0xfe
+ */
@@ -0,0 +1,23 @@
+#![cfg_attr(target_arch = "riscv32", no_std, no_main)]
+
+kartoffels_cpu_tests::test! {
+    r#"
+    .global _start
+    .attribute arch, "rv32ic"
+
+    _branch:
+        li x9, 60
+        ebreak
+
+    _start:
+        li x8, 0
+        li x9, 50
+        c.beqz x8, _branch
+        ebreak
+    "#
+}
+
+/*
+ * x8 = 0
+ * x9 = 60
+ */
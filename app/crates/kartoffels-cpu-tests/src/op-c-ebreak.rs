@@ -0,0 +1,17 @@
+#![cfg_attr(target_arch = "riscv32", no_std, no_main)]
+
+kartoffels_cpu_tests::test! {
+    r#"
+    .global _start
+    .attribute arch, "rv32ic"
+
+    _start:
+        li x5, 123
+        c.ebreak
+        li x5, 456
+    "#
+}
+
+/*
+ * x5 = 123
+ */
@@ -0,0 +1,26 @@
+#![cfg_attr(target_arch = "riscv32", no_std, no_main)]
+
+kartoffels_cpu_tests::test! {
+    r#"
+    .global _start
+    .attribute arch, "rv32ic"
+
+    _one:
+        li x4, 104
+        c.j _two
+
+    _start:
+        li x2, 102
+        c.j _one
+
+    _two:
+        li x3, 103
+        ebreak
+    "#
+}
+
+/*
+ * x2 = 102
+ * x3 = 103
+ * x4 = 104
+ */
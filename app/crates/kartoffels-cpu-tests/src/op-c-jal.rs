@@ -0,0 +1,24 @@
+#![cfg_attr(target_arch = "riscv32", no_std, no_main)]
+
+kartoffels_cpu_tests::test! {
+    r#"
+    .global _start
+    .attribute arch, "rv32ic"
+
+    _fun:
+        li x3, 103
+        ret
+
+    _start:
+        li x2, 102
+        c.jal _fun
+        li x4, 104
+        ebreak
+    "#
+}
+
+/*
+ * x2 = 102
+ * x3 = 103
+ * x4 = 104
+ */
@@ -0,0 +1,27 @@
+#![cfg_attr(target_arch = "riscv32", no_std, no_main)]
+
+kartoffels_cpu_tests::test! {
+    r#"
+    .global _start
+    .attribute arch, "rv32ic"
+
+    _fun:
+        add x4, x2, x3
+        ret
+
+    _start:
+        li x2, 10
+        li x3, 20
+        la x5, _fun
+        c.jalr x5
+        li x6, 123
+        ebreak
+    "#
+}
+
+/*
+ * x2 = 10
+ * x3 = 20
+ * x4 = 30
+ * x6 = 123
+ */
@@ -0,0 +1,24 @@
+#![cfg_attr(target_arch = "riscv32", no_std, no_main)]
+
+kartoffels_cpu_tests::test! {
+    r#"
+    .global _start
+    .attribute arch, "rv32ic"
+
+    _two:
+        li x3, 103
+        ebreak
+
+    _start:
+        li x2, 102
+        la x5, _two
+        c.jr x5
+        li x3, 999
+        ebreak
+    "#
+}
+
+/*
+ * x2 = 102
+ * x3 = 103
+ */
@@ -0,0 +1,18 @@
+#![cfg_attr(target_arch = "riscv32", no_std, no_main)]
+
+kartoffels_cpu_tests::test! {
+    r#"
+    .global _start
+    .attribute arch, "rv32ic"
+
+    _start:
+        c.li x5, 31
+        c.li x6, -32
+        ebreak
+    "#
+}
+
+/*
+ * x5 = 31
+ * x6 = -32
+ */
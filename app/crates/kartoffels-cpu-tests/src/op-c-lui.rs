@@ -0,0 +1,18 @@
+#![cfg_attr(target_arch = "riscv32", no_std, no_main)]
+
+kartoffels_cpu_tests::test! {
+    r#"
+    .global _start
+    .attribute arch, "rv32ic"
+
+    _start:
+        c.lui x5, 1
+        c.lui x6, 0xfffff
+        ebreak
+    "#
+}
+
+/*
+ * x5 = 0x1000
+ * x6 = 0xfffff000
+ */
@@ -0,0 +1,23 @@
+#![cfg_attr(target_arch = "riscv32", no_std, no_main)]
+
+kartoffels_cpu_tests::test! {
+    r#"
+    .global _start
+    .attribute arch, "rv32ic"
+
+    _start:
+        li x8, 0x00102000
+        li x9, 0x12345678
+        c.sw x9, 4(x8)
+        c.lw x10, 4(x8)
+        c.lw x11, 0(x8)
+        ebreak
+    "#
+}
+
+/*
+ * x8 = 0x00102000
+ * x9 = 0x12345678
+ * x10 = 0x12345678
+ * x11 = 0
+ */
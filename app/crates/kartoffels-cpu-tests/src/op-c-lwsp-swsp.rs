@@ -0,0 +1,23 @@
+#![cfg_attr(target_arch = "riscv32", no_std, no_main)]
+
+kartoffels_cpu_tests::test! {
+    r#"
+    .global _start
+    .attribute arch, "rv32ic"
+
+    _start:
+        li x2, 0x00102000
+        li x5, 0x12345678
+        c.swsp x5, 252(x2)
+        c.lwsp x6, 252(x2)
+        c.lwsp x7, 248(x2)
+        ebreak
+    "#
+}
+
+/*
+ * x2 = 0x00102000
+ * x5 = 0x12345678
+ * x6 = 0x12345678
+ * x7 = 0
+ */
@@ -0,0 +1,20 @@
+#![cfg_attr(target_arch = "riscv32", no_std, no_main)]
+
+kartoffels_cpu_tests::test! {
+    r#"
+    .global _start
+    .attribute arch, "rv32ic"
+
+    _start:
+        li x5, 123
+        c.slli x5, 4
+        li x6, 1
+        c.slli x6, 31
+        ebreak
+    "#
+}
+
+/*
+ * x5 = 1968
+ * x6 = 0x80000000
+ */
@@ -0,0 +1,20 @@
+#![cfg_attr(target_arch = "riscv32", no_std, no_main)]
+
+kartoffels_cpu_tests::test! {
+    r#"
+    .global _start
+    .attribute arch, "rv32ic"
+
+    _start:
+        li x8, -123
+        c.srai x8, 4
+        li x9, 123
+        c.srai x9, 4
+        ebreak
+    "#
+}
+
+/*
+ * x8 = -8
+ * x9 = 7
+ */
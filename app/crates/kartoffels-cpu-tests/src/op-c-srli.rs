@@ -0,0 +1,20 @@
+#![cfg_attr(target_arch = "riscv32", no_std, no_main)]
+
+kartoffels_cpu_tests::test! {
+    r#"
+    .global _start
+    .attribute arch, "rv32ic"
+
+    _start:
+        li x8, -123
+        c.srli x8, 4
+        li x9, 123
+        c.srli x9, 4
+        ebreak
+    "#
+}
+
+/*
+ * x8 = 0x0ffffff8
+ * x9 = 7
+ */
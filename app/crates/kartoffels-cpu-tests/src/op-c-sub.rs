@@ -0,0 +1,19 @@
+#![cfg_attr(target_arch = "riscv32", no_std, no_main)]
+
+kartoffels_cpu_tests::test! {
+    r#"
+    .global _start
+    .attribute arch, "rv32ic"
+
+    _start:
+        li x8, 10
+        li x9, 30
+        c.sub x8, x9
+        ebreak
+    "#
+}
+
+/*
+ * x8 = -20
+ * x9 = 30
+ */
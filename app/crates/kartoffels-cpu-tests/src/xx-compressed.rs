@@ -0,0 +1,35 @@
+//! Makes sure the CPU runs compiler-generated code that uses compressed
+//! instructions, since the default target enables `+c`.
+
+#![cfg_attr(target_arch = "riscv32", no_std, no_main)]
+
+extern crate alloc;
+extern crate kartoffel;
+
+use alloc::vec::Vec;
+use core::hint::black_box;
+
+#[cfg_attr(target_arch = "riscv32", no_mangle)]
+fn main() {
+    kartoffels_cpu_tests::exit(run());
+}
+
+fn run() -> u32 {
+    let mut items = Vec::new();
+
+    for n in 0..64 {
+        black_box(&mut items).push(n * 3 + 1);
+    }
+
+    let mut out = 0u32;
+
+    for (idx, item) in black_box(&items).iter().enumerate() {
+        out = out.wrapping_mul(31) ^ (item << (idx % 8)) ^ (item >> 2);
+    }
+
+    out
+}
+
+/*
+ * x10 = 1668313568
+ */
@@ -0,0 +1,32 @@
+#![cfg_attr(target_arch = "riscv32", no_std, no_main)]
+
+kartoffels_cpu_tests::test! {
+    r#"
+    .global _start
+    .attribute arch, "rv32i"
+
+    _fun:
+        li x2, 102
+        ret
+
+    _start:
+        call _fun
+        mv x3, x2
+
+        # Replace `li x2, 102` with `li x2, 202` - CPU should pick up the
+        # change instead of executing the previously seen instruction
+        la x5, _fun
+        li x6, 0x0ca00113
+        sw x6, 0(x5)
+
+        call _fun
+        mv x4, x2
+
+        ebreak
+    "#
+}
+
+/*
+ * x3 = 102
+ * x4 = 202
+ */
@@ -10,6 +10,35 @@ use test::Bencher;
 
 #[bench]
 fn bench(b: &mut Bencher) {
+    let fw = load_fw();
+
+    b.iter(|| {
+        let mut cpu = Cpu::new(&fw);
+
+        while cpu.try_tick(()).unwrap() {
+            //
+        }
+    });
+}
+
+/// Same as [`bench()`], but with the decoded-instruction cache disabled - the
+/// difference between both shows how much time the cache saves.
+#[bench]
+fn bench_without_icache(b: &mut Bencher) {
+    let fw = load_fw();
+
+    b.iter(|| {
+        let mut cpu = Cpu::new(&fw);
+
+        cpu.disable_icache();
+
+        while cpu.try_tick(()).unwrap() {
+            //
+        }
+    });
+}
+
+fn load_fw() -> Firmware {
     build_tests();
 
     let elf_path = Path::new("..")
@@ -20,15 +49,8 @@ fn bench(b: &mut Bencher) {
         .join("xx-ints");
 
     let elf = fs::read(&elf_path).unwrap();
-    let fw = Firmware::from_elf(&elf).unwrap();
 
-    b.iter(|| {
-        let mut cpu = Cpu::new(&fw);
-
-        while cpu.try_tick(()).unwrap() {
-            //
-        }
-    });
+    Firmware::from_elf(&elf).unwrap()
 }
 
 fn build_tests() {
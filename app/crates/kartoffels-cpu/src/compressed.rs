@@ -0,0 +1,265 @@
+use super::Cpu;
+
+impl Cpu {
+    /// Expands a 16-bit compressed instruction (RV32C) into its 32-bit
+    /// counterpart, returning `None` if the instruction is illegal or not
+    /// supported (e.g. floating-point loads and stores).
+    pub(super) fn decompress(instr: u16) -> Option<u32> {
+        let i = instr as u32;
+        let funct3 = (i >> 13) & 0x7;
+
+        // Full register indices, as used by the CR, CI and CSS formats
+        let rd = (i >> 7) & 0x1f;
+        let rs2 = (i >> 2) & 0x1f;
+
+        // Popular-register indices (x8..x15), as used by the CIW, CL, CS, CA
+        // and CB formats
+        let rd_ = ((i >> 2) & 0x7) + 8;
+        let rs1_ = ((i >> 7) & 0x7) + 8;
+
+        // Immediate used by c.addi, c.li, c.andi etc.
+        let imm6 = sext(((i >> 7) & 0x20) | ((i >> 2) & 0x1f), 6);
+
+        // Unsigned immediate used by c.lw and c.sw
+        let lw_imm = ((i >> 7) & 0x38) | ((i >> 4) & 0x4) | ((i << 1) & 0x40);
+
+        let word = match (i & 0b11, funct3) {
+            (0b00, 0b000) => {
+                let imm = ((i >> 7) & 0x30)
+                    | ((i >> 1) & 0x3c0)
+                    | ((i >> 4) & 0x4)
+                    | ((i >> 2) & 0x8);
+
+                if imm == 0 {
+                    return None;
+                }
+
+                // c.addi4spn
+                i_type(0b0010011, 0b000, rd_, 2, imm as i32)
+            }
+
+            // c.lw
+            (0b00, 0b010) => i_type(0b0000011, 0b010, rd_, rs1_, lw_imm as i32),
+
+            // c.sw
+            (0b00, 0b110) => s_type(0b0100011, 0b010, rs1_, rd_, lw_imm as i32),
+
+            // c.addi, c.nop
+            (0b01, 0b000) => i_type(0b0010011, 0b000, rd, rd, imm6),
+
+            (0b01, 0b001) => {
+                // c.jal
+                j_type(0b1101111, 1, Self::decompress_j_imm(i))
+            }
+
+            // c.li
+            (0b01, 0b010) => i_type(0b0010011, 0b000, rd, 0, imm6),
+
+            (0b01, 0b011) if rd == 2 => {
+                let imm = sext(
+                    ((i >> 3) & 0x200)
+                        | ((i >> 2) & 0x10)
+                        | ((i << 1) & 0x40)
+                        | ((i << 4) & 0x180)
+                        | ((i << 3) & 0x20),
+                    10,
+                );
+
+                if imm == 0 {
+                    return None;
+                }
+
+                // c.addi16sp
+                i_type(0b0010011, 0b000, 2, 2, imm)
+            }
+
+            (0b01, 0b011) => {
+                if rd == 0 || imm6 == 0 {
+                    return None;
+                }
+
+                // c.lui
+                (imm6 << 12) as u32 | (rd << 7) | 0b0110111
+            }
+
+            (0b01, 0b100) => {
+                let rd = rs1_;
+
+                match (i >> 10) & 0x3 {
+                    0b00 | 0b01 if i & 0x1000 != 0 => {
+                        return None;
+                    }
+
+                    // c.srli
+                    0b00 => i_type(0b0010011, 0b101, rd, rd, imm6),
+
+                    // c.srai
+                    0b01 => i_type(0b0010011, 0b101, rd, rd, imm6 | 0x400),
+
+                    // c.andi
+                    0b10 => i_type(0b0010011, 0b111, rd, rd, imm6),
+
+                    _ => {
+                        if i & 0x1000 != 0 {
+                            return None;
+                        }
+
+                        let rs2 = rd_;
+
+                        match (i >> 5) & 0x3 {
+                            // c.sub
+                            0b00 => r_type(0b0100000, 0b000, rd, rd, rs2),
+
+                            // c.xor
+                            0b01 => r_type(0b0000000, 0b100, rd, rd, rs2),
+
+                            // c.or
+                            0b10 => r_type(0b0000000, 0b110, rd, rd, rs2),
+
+                            // c.and
+                            _ => r_type(0b0000000, 0b111, rd, rd, rs2),
+                        }
+                    }
+                }
+            }
+
+            // c.j
+            (0b01, 0b101) => j_type(0b1101111, 0, Self::decompress_j_imm(i)),
+
+            (0b01, 0b110 | 0b111) => {
+                let imm = sext(
+                    ((i >> 4) & 0x100)
+                        | ((i >> 7) & 0x18)
+                        | ((i << 1) & 0xc0)
+                        | ((i >> 2) & 0x6)
+                        | ((i << 3) & 0x20),
+                    9,
+                );
+
+                // c.beqz, c.bnez
+                b_type(funct3 & 0b001, rs1_, 0, imm)
+            }
+
+            (0b10, 0b000) => {
+                if i & 0x1000 != 0 {
+                    return None;
+                }
+
+                // c.slli
+                i_type(0b0010011, 0b001, rd, rd, imm6)
+            }
+
+            (0b10, 0b010) => {
+                if rd == 0 {
+                    return None;
+                }
+
+                let imm =
+                    ((i >> 7) & 0x20) | ((i >> 2) & 0x1c) | ((i << 4) & 0xc0);
+
+                // c.lwsp
+                i_type(0b0000011, 0b010, rd, 2, imm as i32)
+            }
+
+            (0b10, 0b100) => match (i & 0x1000 != 0, rd, rs2) {
+                (false, 0, 0) => {
+                    return None;
+                }
+
+                // c.jr
+                (false, rs1, 0) => i_type(0b1100111, 0b000, 0, rs1, 0),
+
+                // c.mv
+                (false, rd, rs2) => r_type(0b0000000, 0b000, rd, 0, rs2),
+
+                // c.ebreak
+                (true, 0, 0) => 0x00100073,
+
+                // c.jalr
+                (true, rs1, 0) => i_type(0b1100111, 0b000, 1, rs1, 0),
+
+                // c.add
+                (true, rd, rs2) => r_type(0b0000000, 0b000, rd, rd, rs2),
+            },
+
+            (0b10, 0b110) => {
+                let imm = ((i >> 7) & 0x3c) | ((i >> 1) & 0xc0);
+
+                // c.swsp
+                s_type(0b0100011, 0b010, 2, rs2, imm as i32)
+            }
+
+            _ => {
+                return None;
+            }
+        };
+
+        Some(word)
+    }
+
+    fn decompress_j_imm(i: u32) -> i32 {
+        sext(
+            ((i >> 1) & 0x800)
+                | ((i >> 7) & 0x10)
+                | ((i >> 1) & 0x300)
+                | ((i << 2) & 0x400)
+                | ((i >> 1) & 0x40)
+                | ((i << 1) & 0x80)
+                | ((i >> 2) & 0xe)
+                | ((i << 3) & 0x20),
+            12,
+        )
+    }
+}
+
+fn sext(val: u32, bits: u32) -> i32 {
+    ((val << (32 - bits)) as i32) >> (32 - bits)
+}
+
+fn r_type(funct7: u32, funct3: u32, rd: u32, rs1: u32, rs2: u32) -> u32 {
+    (funct7 << 25)
+        | (rs2 << 20)
+        | (rs1 << 15)
+        | (funct3 << 12)
+        | (rd << 7)
+        | 0b0110011
+}
+
+fn i_type(op: u32, funct3: u32, rd: u32, rs1: u32, imm: i32) -> u32 {
+    ((imm as u32 & 0xfff) << 20) | (rs1 << 15) | (funct3 << 12) | (rd << 7) | op
+}
+
+fn s_type(op: u32, funct3: u32, rs1: u32, rs2: u32, imm: i32) -> u32 {
+    let imm = imm as u32;
+
+    (((imm >> 5) & 0x7f) << 25)
+        | (rs2 << 20)
+        | (rs1 << 15)
+        | (funct3 << 12)
+        | ((imm & 0x1f) << 7)
+        | op
+}
+
+fn b_type(funct3: u32, rs1: u32, rs2: u32, imm: i32) -> u32 {
+    let imm = imm as u32;
+
+    (((imm >> 12) & 0x1) << 31)
+        | (((imm >> 5) & 0x3f) << 25)
+        | (rs2 << 20)
+        | (rs1 << 15)
+        | (funct3 << 12)
+        | (((imm >> 1) & 0xf) << 8)
+        | (((imm >> 11) & 0x1) << 7)
+        | 0b1100011
+}
+
+fn j_type(op: u32, rd: u32, imm: i32) -> u32 {
+    let imm = imm as u32;
+
+    (((imm >> 20) & 0x1) << 31)
+        | (((imm >> 1) & 0x3ff) << 21)
+        | (((imm >> 11) & 0x1) << 20)
+        | (((imm >> 12) & 0xff) << 12)
+        | (rd << 7)
+        | op
+}
@@ -0,0 +1,392 @@
+use super::Cpu;
+
+/// Instruction decoded into a form that's cheap to execute - registers are
+/// extracted and immediates are sign-extended (and shifted, where applicable)
+/// upfront.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum Op {
+    Lui(U),
+    Auipc(U),
+
+    Add(R),
+    Addi(I),
+    Sub(R),
+    Mul(R),
+    Mulh(R),
+    Mulhsu(R),
+    Mulhu(R),
+    Div(R),
+    Divu(R),
+    Rem(R),
+    Remu(R),
+    And(R),
+    Andi(I),
+    Or(R),
+    Ori(I),
+    Xor(R),
+    Xori(I),
+    Sll(R),
+    Slli(I),
+    Srl(R),
+    Srli(I),
+    Sra(R),
+    Srai(I),
+    Slt(R),
+    Slti(I),
+    Sltu(R),
+    Sltiu(I),
+
+    Lb(I),
+    Lbu(I),
+    Lh(I),
+    Lhu(I),
+    Lw(I),
+    Sb(S),
+    Sh(S),
+    Sw(S),
+
+    AmoaddW(R),
+    AmoswapW(R),
+    LrW(R),
+    ScW(R),
+    AmoxorW(R),
+    AmoandW(R),
+    AmoorW(R),
+    AmominW(R),
+    AmomaxW(R),
+    AmominuW(R),
+    AmomaxuW(R),
+    Fence,
+
+    Beq(S),
+    Bne(S),
+    Blt(S),
+    Bltu(S),
+    Bge(S),
+    Bgeu(S),
+    Jal(U),
+    Jalr(I),
+    Ebreak,
+
+    /// Instruction we don't recognize - kept around (instead of failing the
+    /// decoding) so that the error is reported only once the instruction is
+    /// actually executed
+    Unknown(u32),
+
+    /// Same as [`Op::Unknown`], but for compressed instructions
+    UnknownCompressed(u16),
+}
+
+/// Operands of register-register instructions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) struct R {
+    pub rd: u8,
+    pub rs1: u8,
+    pub rs2: u8,
+}
+
+/// Operands of register-immediate instructions, loads and `jalr`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) struct I {
+    pub rd: u8,
+    pub rs1: u8,
+    pub imm: i32,
+}
+
+/// Operands of stores and branches.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) struct S {
+    pub rs1: u8,
+    pub rs2: u8,
+    pub imm: i32,
+}
+
+/// Operands of `lui`, `auipc` and `jal`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) struct U {
+    pub rd: u8,
+    pub imm: i32,
+}
+
+/// Decoded instruction together with its length in bytes, i.e. the amount by
+/// which the program counter has to be advanced to reach the next instruction.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) struct Instr {
+    pub op: Op,
+    pub len: u8,
+}
+
+impl Cpu {
+    /// Decodes a 16-bit or 32-bit instruction, given its lower half and a
+    /// function that returns its upper half (called only for 32-bit ones).
+    pub(super) fn decode<E>(
+        lo: u16,
+        hi: impl FnOnce() -> Result<u16, E>,
+    ) -> Result<Instr, E> {
+        // Instructions with the two lowest bits set are regular, 32-bit ones -
+        // everything else is a compressed, 16-bit instruction
+        if lo & 0b11 == 0b11 {
+            let word = (lo as u32) | ((hi()? as u32) << 16);
+
+            Ok(Instr {
+                op: Self::decode_word(word),
+                len: 4,
+            })
+        } else {
+            let op = match Self::decompress(lo) {
+                Some(word) => Self::decode_word(word),
+                None => Op::UnknownCompressed(lo),
+            };
+
+            Ok(Instr { op, len: 2 })
+        }
+    }
+
+    fn decode_word(word: u32) -> Op {
+        let op = word & 0x7f;
+        let funct3 = (word >> 12) & 0x7;
+        let funct7 = word >> 25;
+
+        let rd = ((word >> 7) & 0x1f) as u8;
+        let rs1 = ((word >> 15) & 0x1f) as u8;
+        let rs2 = ((word >> 20) & 0x1f) as u8;
+
+        let i_imm = (word as i32) >> 20;
+        let u_imm = (word as i32) >> 12;
+
+        let s_imm =
+            ((word & 0xfe000000) as i32 >> 20) | (((word >> 7) & 0x1f) as i32);
+
+        let b_imm = (((word & 0x80000000) as i32 >> 19) as u32
+            | ((word & 0x80) << 4)
+            | ((word >> 20) & 0x7e0)
+            | ((word >> 7) & 0x1e)) as i32;
+
+        let j_imm = (((word & 0x80000000) as i32 >> 11) as u32
+            | (word & 0xff000)
+            | ((word >> 9) & 0x800)
+            | ((word >> 20) & 0x7fe)) as i32;
+
+        match (op, funct3, funct7) {
+            (0b0110111, _, _) => Op::Lui(U {
+                rd,
+                imm: u_imm << 12,
+            }),
+
+            (0b0010111, _, _) => Op::Auipc(U {
+                rd,
+                imm: u_imm << 12,
+            }),
+
+            (0b0110011, 0b000, 0b0000000) => Op::Add(R { rd, rs1, rs2 }),
+
+            (0b0010011, 0b000, _) => Op::Addi(I {
+                rd,
+                rs1,
+                imm: i_imm,
+            }),
+
+            (0b0110011, 0b000, 0b0100000) => Op::Sub(R { rd, rs1, rs2 }),
+            (0b0110011, 0b000, 0b0000001) => Op::Mul(R { rd, rs1, rs2 }),
+            (0b0110011, 0b001, 0b0000001) => Op::Mulh(R { rd, rs1, rs2 }),
+            (0b0110011, 0b010, 0b0000001) => Op::Mulhsu(R { rd, rs1, rs2 }),
+            (0b0110011, 0b011, 0b0000001) => Op::Mulhu(R { rd, rs1, rs2 }),
+            (0b0110011, 0b100, 0b0000001) => Op::Div(R { rd, rs1, rs2 }),
+            (0b0110011, 0b101, 0b0000001) => Op::Divu(R { rd, rs1, rs2 }),
+            (0b0110011, 0b110, 0b0000001) => Op::Rem(R { rd, rs1, rs2 }),
+            (0b0110011, 0b111, 0b0000001) => Op::Remu(R { rd, rs1, rs2 }),
+            (0b0110011, 0b111, 0b0000000) => Op::And(R { rd, rs1, rs2 }),
+
+            (0b0010011, 0b111, _) => Op::Andi(I {
+                rd,
+                rs1,
+                imm: i_imm,
+            }),
+
+            (0b0110011, 0b110, 0b0000000) => Op::Or(R { rd, rs1, rs2 }),
+
+            (0b0010011, 0b110, _) => Op::Ori(I {
+                rd,
+                rs1,
+                imm: i_imm,
+            }),
+
+            (0b0110011, 0b100, 0b0000000) => Op::Xor(R { rd, rs1, rs2 }),
+
+            (0b0010011, 0b100, _) => Op::Xori(I {
+                rd,
+                rs1,
+                imm: i_imm,
+            }),
+
+            (0b0110011, 0b001, 0b0000000) => Op::Sll(R { rd, rs1, rs2 }),
+
+            (0b0010011, 0b001, _) => match i_imm >> 6 {
+                0x00 => Op::Slli(I {
+                    rd,
+                    rs1,
+                    imm: i_imm,
+                }),
+
+                _ => Op::Unknown(word),
+            },
+
+            (0b0110011, 0b101, 0b0000000) => Op::Srl(R { rd, rs1, rs2 }),
+
+            (0b0010011, 0b101, _) => match i_imm >> 6 {
+                0x00 => Op::Srli(I {
+                    rd,
+                    rs1,
+                    imm: i_imm,
+                }),
+
+                0x10 => Op::Srai(I {
+                    rd,
+                    rs1,
+                    imm: i_imm & 0x3f,
+                }),
+
+                _ => Op::Unknown(word),
+            },
+
+            (0b0110011, 0b101, 0b0100000) => Op::Sra(R { rd, rs1, rs2 }),
+            (0b0110011, 0b010, 0b0000000) => Op::Slt(R { rd, rs1, rs2 }),
+
+            (0b0010011, 0b010, _) => Op::Slti(I {
+                rd,
+                rs1,
+                imm: i_imm,
+            }),
+
+            (0b0110011, 0b011, 0b0000000) => Op::Sltu(R { rd, rs1, rs2 }),
+
+            (0b0010011, 0b011, _) => Op::Sltiu(I {
+                rd,
+                rs1,
+                imm: i_imm,
+            }),
+
+            (0b0000011, 0b000, _) => Op::Lb(I {
+                rd,
+                rs1,
+                imm: i_imm,
+            }),
+
+            (0b0000011, 0b100, _) => Op::Lbu(I {
+                rd,
+                rs1,
+                imm: i_imm,
+            }),
+
+            (0b0000011, 0b001, _) => Op::Lh(I {
+                rd,
+                rs1,
+                imm: i_imm,
+            }),
+
+            (0b0000011, 0b101, _) => Op::Lhu(I {
+                rd,
+                rs1,
+                imm: i_imm,
+            }),
+
+            (0b0000011, 0b010, _) => Op::Lw(I {
+                rd,
+                rs1,
+                imm: i_imm,
+            }),
+
+            (0b0100011, 0b000, _) => Op::Sb(S {
+                rs1,
+                rs2,
+                imm: s_imm,
+            }),
+
+            (0b0100011, 0b001, _) => Op::Sh(S {
+                rs1,
+                rs2,
+                imm: s_imm,
+            }),
+
+            (0b0100011, 0b010, _) => Op::Sw(S {
+                rs1,
+                rs2,
+                imm: s_imm,
+            }),
+
+            (0b0101111, 0b010, _) => {
+                // funct7's low bits encode the ordering semantics (acquire
+                // and/or release) which we don't care about
+                let funct5 = funct7 >> 2;
+
+                match funct5 {
+                    0b00000 => Op::AmoaddW(R { rd, rs1, rs2 }),
+                    0b00001 => Op::AmoswapW(R { rd, rs1, rs2 }),
+                    0b00010 => Op::LrW(R { rd, rs1, rs2 }),
+                    0b00011 => Op::ScW(R { rd, rs1, rs2 }),
+                    0b00100 => Op::AmoxorW(R { rd, rs1, rs2 }),
+                    0b01100 => Op::AmoandW(R { rd, rs1, rs2 }),
+                    0b01000 => Op::AmoorW(R { rd, rs1, rs2 }),
+                    0b10000 => Op::AmominW(R { rd, rs1, rs2 }),
+                    0b10100 => Op::AmomaxW(R { rd, rs1, rs2 }),
+                    0b11000 => Op::AmominuW(R { rd, rs1, rs2 }),
+                    0b11100 => Op::AmomaxuW(R { rd, rs1, rs2 }),
+                    _ => Op::Unknown(word),
+                }
+            }
+
+            (0b0001111, 0b000, _) => Op::Fence,
+
+            (0b1100011, 0b000, _) => Op::Beq(S {
+                rs1,
+                rs2,
+                imm: b_imm,
+            }),
+
+            (0b1100011, 0b001, _) => Op::Bne(S {
+                rs1,
+                rs2,
+                imm: b_imm,
+            }),
+
+            (0b1100011, 0b100, _) => Op::Blt(S {
+                rs1,
+                rs2,
+                imm: b_imm,
+            }),
+
+            (0b1100011, 0b110, _) => Op::Bltu(S {
+                rs1,
+                rs2,
+                imm: b_imm,
+            }),
+
+            (0b1100011, 0b101, _) => Op::Bge(S {
+                rs1,
+                rs2,
+                imm: b_imm,
+            }),
+
+            (0b1100011, 0b111, _) => Op::Bgeu(S {
+                rs1,
+                rs2,
+                imm: b_imm,
+            }),
+
+            (0b1101111, _, _) => Op::Jal(U { rd, imm: j_imm }),
+
+            (0b1100111, 0b000, _) => Op::Jalr(I {
+                rd,
+                rs1,
+                imm: i_imm,
+            }),
+
+            (0b1110011, 0b000, _) => match i_imm {
+                0x01 => Op::Ebreak,
+                _ => Op::Unknown(word),
+            },
+
+            _ => Op::Unknown(word),
+        }
+    }
+}
@@ -0,0 +1,203 @@
+use super::Cpu;
+
+impl Cpu {
+    /// ABI names of the registers, indexed by their numbers.
+    pub const REG_NAMES: [&'static str; 32] = [
+        "zero", "ra", "sp", "gp", "tp", "t0", "t1", "t2", "s0", "s1", "a0",
+        "a1", "a2", "a3", "a4", "a5", "a6", "a7", "s2", "s3", "s4", "s5", "s6",
+        "s7", "s8", "s9", "s10", "s11", "t3", "t4", "t5", "t6",
+    ];
+
+    /// Decodes instruction located at given address of given memory (which is
+    /// expected to be a copy of [`Cpu::ram()`]), returning its size in bytes
+    /// and its human-readable form.
+    ///
+    /// Compressed instructions are presented in their expanded forms.
+    pub fn disassemble(ram: &[u8], addr: u32) -> Option<(u32, String)> {
+        let load = |addr: u32, size: usize| -> Option<u32> {
+            let rel_addr = addr.checked_sub(Self::RAM_BASE)? as usize;
+            let bytes = ram.get(rel_addr..rel_addr + size)?;
+
+            Some(
+                bytes
+                    .iter()
+                    .rev()
+                    .fold(0, |val, byte| (val << 8) | (*byte as u32)),
+            )
+        };
+
+        let half = load(addr, 2)? as u16;
+
+        let (size, word) = if half & 0b11 == 0b11 {
+            (4, load(addr, 4)?)
+        } else {
+            (2, Self::decompress(half)?)
+        };
+
+        Some((size, Self::disassemble_word(addr, word)?))
+    }
+
+    fn disassemble_word(addr: u32, word: u32) -> Option<String> {
+        let op = word & 0x7f;
+        let funct3 = (word >> 12) & 0x7;
+        let funct7 = word >> 25;
+
+        let rd = Self::REG_NAMES[((word >> 7) & 0x1f) as usize];
+        let rs1 = Self::REG_NAMES[((word >> 15) & 0x1f) as usize];
+        let rs2 = Self::REG_NAMES[((word >> 20) & 0x1f) as usize];
+
+        let i_imm = (word as i32) >> 20;
+        let u_imm = (word as i32) >> 12;
+
+        let s_imm =
+            ((word & 0xfe000000) as i32 >> 20) | (((word >> 7) & 0x1f) as i32);
+
+        let b_imm = (((word & 0x80000000) as i32 >> 19) as u32
+            | ((word & 0x80) << 4)
+            | ((word >> 20) & 0x7e0)
+            | ((word >> 7) & 0x1e)) as i32;
+
+        let j_imm = (((word & 0x80000000) as i32 >> 11) as u32
+            | (word & 0xff000)
+            | ((word >> 9) & 0x800)
+            | ((word >> 20) & 0x7fe)) as i32;
+
+        let instr = match (op, funct3, funct7) {
+            (0b0110111, _, _) => {
+                format!("lui {rd}, 0x{:x}", u_imm & 0xfffff)
+            }
+
+            (0b0010111, _, _) => {
+                format!("auipc {rd}, 0x{:x}", u_imm & 0xfffff)
+            }
+
+            (0b0110011, _, _) => {
+                let name = match (funct3, funct7) {
+                    (0b000, 0b0000000) => "add",
+                    (0b000, 0b0100000) => "sub",
+                    (0b001, 0b0000000) => "sll",
+                    (0b010, 0b0000000) => "slt",
+                    (0b011, 0b0000000) => "sltu",
+                    (0b100, 0b0000000) => "xor",
+                    (0b101, 0b0000000) => "srl",
+                    (0b101, 0b0100000) => "sra",
+                    (0b110, 0b0000000) => "or",
+                    (0b111, 0b0000000) => "and",
+                    (0b000, 0b0000001) => "mul",
+                    (0b001, 0b0000001) => "mulh",
+                    (0b010, 0b0000001) => "mulhsu",
+                    (0b011, 0b0000001) => "mulhu",
+                    (0b100, 0b0000001) => "div",
+                    (0b101, 0b0000001) => "divu",
+                    (0b110, 0b0000001) => "rem",
+                    (0b111, 0b0000001) => "remu",
+                    _ => return None,
+                };
+
+                format!("{name} {rd}, {rs1}, {rs2}")
+            }
+
+            (0b0010011, 0b001 | 0b101, _) => {
+                let name = match (funct3, funct7) {
+                    (0b001, 0b0000000) => "slli",
+                    (0b101, 0b0000000) => "srli",
+                    (0b101, 0b0100000) => "srai",
+                    _ => return None,
+                };
+
+                format!("{name} {rd}, {rs1}, {}", i_imm & 0x1f)
+            }
+
+            (0b0010011, _, _) => {
+                let name = match funct3 {
+                    0b000 => "addi",
+                    0b010 => "slti",
+                    0b011 => "sltiu",
+                    0b100 => "xori",
+                    0b110 => "ori",
+                    _ => "andi",
+                };
+
+                format!("{name} {rd}, {rs1}, {i_imm}")
+            }
+
+            (0b0000011, _, _) => {
+                let name = match funct3 {
+                    0b000 => "lb",
+                    0b001 => "lh",
+                    0b010 => "lw",
+                    0b100 => "lbu",
+                    0b101 => "lhu",
+                    _ => return None,
+                };
+
+                format!("{name} {rd}, {i_imm}({rs1})")
+            }
+
+            (0b0100011, _, _) => {
+                let name = match funct3 {
+                    0b000 => "sb",
+                    0b001 => "sh",
+                    0b010 => "sw",
+                    _ => return None,
+                };
+
+                format!("{name} {rs2}, {s_imm}({rs1})")
+            }
+
+            (0b0101111, 0b010, _) => {
+                let name = match funct7 >> 2 {
+                    0b00000 => "amoadd.w",
+                    0b00001 => "amoswap.w",
+                    0b00010 => return Some(format!("lr.w {rd}, ({rs1})")),
+                    0b00011 => "sc.w",
+                    0b00100 => "amoxor.w",
+                    0b01100 => "amoand.w",
+                    0b01000 => "amoor.w",
+                    0b10000 => "amomin.w",
+                    0b10100 => "amomax.w",
+                    0b11000 => "amominu.w",
+                    0b11100 => "amomaxu.w",
+                    _ => return None,
+                };
+
+                format!("{name} {rd}, {rs2}, ({rs1})")
+            }
+
+            (0b1100011, _, _) => {
+                let name = match funct3 {
+                    0b000 => "beq",
+                    0b001 => "bne",
+                    0b100 => "blt",
+                    0b101 => "bge",
+                    0b110 => "bltu",
+                    0b111 => "bgeu",
+                    _ => return None,
+                };
+
+                let target = addr.wrapping_add_signed(b_imm);
+
+                format!("{name} {rs1}, {rs2}, 0x{target:08x}")
+            }
+
+            (0b1101111, _, _) => {
+                let target = addr.wrapping_add_signed(j_imm);
+
+                format!("jal {rd}, 0x{target:08x}")
+            }
+
+            (0b1100111, 0b000, _) => {
+                format!("jalr {rd}, {i_imm}({rs1})")
+            }
+
+            (0b0001111, _, _) => "fence".into(),
+            (0b1110011, 0b000, _) if i_imm == 0x01 => "ebreak".into(),
+
+            _ => {
+                return None;
+            }
+        };
+
+        Some(instr)
+    }
+}
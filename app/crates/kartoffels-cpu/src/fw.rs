@@ -1,20 +1,37 @@
 use crate::Cpu;
 use anyhow::{anyhow, Context, Result};
-use elf::abi::PT_LOAD;
+use elf::abi::{PT_LOAD, STT_FUNC};
 use elf::endian::LittleEndian;
 use elf::file::Class;
 use elf::ElfBytes;
 use serde::{Deserialize, Serialize};
 use std::fmt;
+use tracing::warn;
 
 #[derive(Clone, Default, Serialize, Deserialize)]
 pub struct Firmware {
     pub(crate) segments: Vec<Segment>,
     pub(crate) entry_pc: u32,
+
+    /// Function symbols, sorted by their addresses - `None` if the binary has
+    /// been stripped or symbols haven't been requested, see
+    /// [`Self::from_elf_with_symbols()`]
+    pub(crate) symbols: Option<Box<[FirmwareSymbol]>>,
 }
 
 impl Firmware {
     pub fn from_elf(src: &[u8]) -> Result<Self> {
+        Self::parse(src, false)
+    }
+
+    /// Parses firmware together with its function symbols, so that it can be
+    /// symbolized later - symbols take memory and space in saves, so they are
+    /// worth keeping only for bots that can be debugged.
+    pub fn from_elf_with_symbols(src: &[u8]) -> Result<Self> {
+        Self::parse(src, true)
+    }
+
+    fn parse(src: &[u8], with_symbols: bool) -> Result<Self> {
         let mut segments = Vec::new();
 
         let elf = ElfBytes::<LittleEndian>::minimal_parse(src)?;
@@ -71,7 +88,69 @@ impl Firmware {
             }
         }
 
-        Ok(Self { segments, entry_pc })
+        let symbols = if with_symbols {
+            match Self::parse_symbols(&elf) {
+                Ok(symbols) => symbols,
+
+                Err(err) => {
+                    // Symbols are only a debugging aid, so there's no point in
+                    // rejecting the entire firmware because of them
+                    warn!("couldn't parse symbols: {err:?}");
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
+        Ok(Self {
+            segments,
+            entry_pc,
+            symbols,
+        })
+    }
+
+    fn parse_symbols(
+        elf: &ElfBytes<LittleEndian>,
+    ) -> Result<Option<Box<[FirmwareSymbol]>>> {
+        let Some((symtab, strtab)) = elf.symbol_table()? else {
+            return Ok(None);
+        };
+
+        let mut symbols = Vec::new();
+
+        for sym in symtab {
+            if sym.st_symtype() != STT_FUNC || sym.st_value == 0 {
+                continue;
+            }
+
+            let name = strtab.get(sym.st_name as usize)?;
+            let name = format!("{:#}", rustc_demangle::demangle(name));
+
+            symbols.push(FirmwareSymbol {
+                addr: sym.st_value as u32,
+                size: sym.st_size as u32,
+                name: name.into(),
+            });
+        }
+
+        symbols.sort_by_key(|sym| sym.addr);
+
+        Ok(Some(symbols.into()))
+    }
+
+    /// Returns name of the function containing given address, if the firmware
+    /// has been compiled with symbols.
+    pub fn symbolize(&self, addr: u32) -> Option<&str> {
+        let symbols = self.symbols.as_ref()?;
+        let idx = symbols.partition_point(|sym| sym.addr <= addr);
+        let sym = symbols.get(idx.checked_sub(1)?)?;
+
+        if addr < sym.addr.saturating_add(sym.size.max(1)) {
+            Some(&sym.name)
+        } else {
+            None
+        }
     }
 }
 
@@ -83,9 +162,54 @@ impl fmt::Debug for Firmware {
     }
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct FirmwareSymbol {
+    pub(super) addr: u32,
+    pub(super) size: u32,
+    pub(super) name: Box<str>,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Segment {
     pub(super) addr: usize,
     #[serde(with = "serde_bytes")]
     pub(super) data: Box<[u8]>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn symbolize() {
+        let sym = |addr, size, name: &str| FirmwareSymbol {
+            addr,
+            size,
+            name: name.into(),
+        };
+
+        let target = Firmware {
+            symbols: Some(
+                vec![
+                    sym(0x1000, 0x10, "foo"),
+                    sym(0x1010, 0x08, "bar"),
+                    sym(0x1020, 0x00, "zar"),
+                    sym(0xfffffff0, 0x20, "dar"),
+                ]
+                .into(),
+            ),
+            ..Default::default()
+        };
+
+        assert_eq!(None, target.symbolize(0x0fff));
+        assert_eq!(Some("foo"), target.symbolize(0x1000));
+        assert_eq!(Some("foo"), target.symbolize(0x100f));
+        assert_eq!(Some("bar"), target.symbolize(0x1010));
+        assert_eq!(None, target.symbolize(0x1018));
+        assert_eq!(Some("zar"), target.symbolize(0x1020));
+        assert_eq!(None, target.symbolize(0x1024));
+        assert_eq!(Some("dar"), target.symbolize(0xfffffff0));
+        assert_eq!(Some("dar"), target.symbolize(0xfffffffe));
+        assert_eq!(None, Firmware::default().symbolize(0x1000));
+    }
+}
@@ -0,0 +1,118 @@
+use super::{Cpu, Instr};
+
+/// Cache of decoded instructions, so that hot code doesn't have to be fetched
+/// and decoded over and over again.
+///
+/// RAM is split into pages which are allocated lazily, on the first execution
+/// of an instruction located within given page - this way the cache grows
+/// only for code, not for data or stack. Pages are dropped whenever something
+/// writes to them, which keeps self-modifying code working.
+#[derive(Clone, Debug, Default)]
+pub(super) struct ICache {
+    pages: Vec<Option<Box<[Option<Instr>]>>>,
+    disabled: bool,
+}
+
+impl ICache {
+    const PAGE_SIZE: usize = 256;
+    const PAGES: usize = (Cpu::RAM_SIZE as usize) / Self::PAGE_SIZE;
+
+    pub fn disabled() -> Self {
+        Self {
+            pages: Default::default(),
+            disabled: true,
+        }
+    }
+
+    pub fn get(&self, addr: usize) -> Option<Instr> {
+        let page = self.pages.get(addr / Self::PAGE_SIZE)?.as_ref()?;
+
+        page[(addr % Self::PAGE_SIZE) / 2]
+    }
+
+    pub fn insert(&mut self, addr: usize, instr: Instr) {
+        if self.disabled {
+            return;
+        }
+
+        if self.pages.is_empty() {
+            self.pages.resize(Self::PAGES, None);
+        }
+
+        let page =
+            self.pages[addr / Self::PAGE_SIZE].get_or_insert_with(|| {
+                vec![None; Self::PAGE_SIZE / 2].into_boxed_slice()
+            });
+
+        page[(addr % Self::PAGE_SIZE) / 2] = Some(instr);
+    }
+
+    /// Invalidates pages affected by a write of `size` bytes at `addr`.
+    pub fn invalidate(&mut self, addr: usize, size: usize) {
+        if self.pages.is_empty() {
+            return;
+        }
+
+        // 32-bit instructions can start up to three bytes before the written
+        // address (and thus possibly in the previous page)
+        let beg = addr.saturating_sub(3) / Self::PAGE_SIZE;
+        let end = (addr + size - 1) / Self::PAGE_SIZE;
+
+        for page in beg..=end {
+            if let Some(page) = self.pages.get_mut(page) {
+                *page = None;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Op;
+
+    #[test]
+    fn smoke() {
+        let mut target = ICache::default();
+
+        let instr = Instr {
+            op: Op::Fence,
+            len: 4,
+        };
+
+        assert_eq!(None, target.get(0x1fe));
+
+        target.insert(0x1fe, instr);
+
+        assert_eq!(Some(instr), target.get(0x1fe));
+        assert_eq!(None, target.get(0x1fc));
+        assert_eq!(None, target.get(0x200));
+
+        // Write to the next page, but which overlaps our instruction
+        target.invalidate(0x201, 1);
+
+        assert_eq!(None, target.get(0x1fe));
+
+        // ---
+
+        target.insert(0x1fe, instr);
+        target.invalidate(0x204, 1);
+
+        assert_eq!(Some(instr), target.get(0x1fe));
+    }
+
+    #[test]
+    fn disabled() {
+        let mut target = ICache::disabled();
+
+        target.insert(
+            0x100,
+            Instr {
+                op: Op::Fence,
+                len: 4,
+            },
+        );
+
+        assert_eq!(None, target.get(0x100));
+    }
+}
@@ -1,12 +1,20 @@
 #![allow(clippy::result_unit_err)]
 
+mod compressed;
+mod decode;
+mod disasm;
 mod fw;
+mod icache;
 mod mem;
 mod mmio;
+mod profiler;
 mod tick;
 
+pub(crate) use self::decode::*;
 pub use self::fw::*;
+pub(crate) use self::icache::*;
 pub use self::mmio::*;
+pub use self::profiler::*;
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use std::fmt;
@@ -17,12 +25,17 @@ pub struct Cpu {
     #[serde(with = "serde_bytes")]
     ram: Box<[u8]>,
     regs: Box<[i32; 32]>,
+    #[serde(skip)]
+    icache: ICache,
+    #[serde(skip)]
+    profiler: Option<Box<CpuProfiler>>,
 }
 
 impl Cpu {
-    const RAM_BASE: u32 = 0x00100000;
-    const RAM_SIZE: u32 = 128 * 1024;
+    pub const RAM_BASE: u32 = 0x00100000;
+    pub const RAM_SIZE: u32 = 128 * 1024;
     const MMIO_BASE: u32 = 0x08000000;
+    const BACKTRACE_DEPTH: usize = 16;
 
     pub fn new(fw: &Firmware) -> Self {
         let pc = fw.entry_pc;
@@ -41,10 +54,28 @@ impl Cpu {
 
         let regs = Box::new([0; 32]);
 
-        Self { pc, ram, regs }
+        Self {
+            pc,
+            ram,
+            regs,
+            icache: Default::default(),
+            profiler: None,
+        }
+    }
+
+    /// Disables caching of decoded instructions, making the CPU fetch and
+    /// decode each instruction anew on every tick.
+    ///
+    /// This is slower, but comes handy for benchmarking.
+    pub fn disable_icache(&mut self) {
+        self.icache = ICache::disabled();
     }
 
     pub fn tick(&mut self, mmio: impl Mmio) -> Result<(), Box<str>> {
+        if let Some(profiler) = &mut self.profiler {
+            profiler.tick(self.pc);
+        }
+
         self.do_tick(mmio)
     }
 
@@ -67,6 +98,67 @@ impl Cpu {
     pub fn regs(&self) -> &[i32; 32] {
         &self.regs
     }
+
+    pub fn profiler(&self) -> Option<&CpuProfiler> {
+        self.profiler.as_deref()
+    }
+
+    /// Attaches (or detaches) a profiler - note that profiler is not
+    /// serialized, so it doesn't survive a save & load.
+    pub fn set_profiler(&mut self, profiler: Option<CpuProfiler>) {
+        self.profiler = profiler.map(Box::new);
+    }
+
+    /// Walks the stack by following frame pointers, returning the return
+    /// addresses of callers (innermost first).
+    ///
+    /// This is best-effort - it works reliably only for firmwares compiled
+    /// with frame pointers, otherwise the result might be cut short.
+    pub fn backtrace(&self) -> Vec<u32> {
+        let load = |addr: u32| -> Option<u32> {
+            if addr % 4 != 0 {
+                return None;
+            }
+
+            let addr = addr.checked_sub(Self::RAM_BASE)? as usize;
+            let bytes = self.ram.get(addr..addr + 4)?;
+
+            Some(u32::from_le_bytes(bytes.try_into().unwrap()))
+        };
+
+        let is_code = |addr: u32| {
+            addr > Self::RAM_BASE && addr < Self::RAM_BASE + Self::RAM_SIZE
+        };
+
+        let mut frames = Vec::new();
+        let mut fp = self.regs[8] as u32;
+
+        while frames.len() < Self::BACKTRACE_DEPTH {
+            let Some(ra) = load(fp.wrapping_sub(4)) else {
+                break;
+            };
+
+            let Some(next_fp) = load(fp.wrapping_sub(8)) else {
+                break;
+            };
+
+            if !is_code(ra) {
+                break;
+            }
+
+            frames.push(ra);
+
+            // Stack grows downwards, so caller's frame must be located above
+            // ours - otherwise we're probably looking at garbage
+            if next_fp <= fp {
+                break;
+            }
+
+            fp = next_fp;
+        }
+
+        frames
+    }
 }
 
 impl fmt::Debug for Cpu {
@@ -74,3 +166,31 @@ impl fmt::Debug for Cpu {
         f.debug_struct("Cpu").field("pc", &self.pc).finish()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backtrace() {
+        let mut target = Cpu::new(&Default::default());
+
+        let mut store = |addr: u32, val: u32| {
+            let addr = (addr - Cpu::RAM_BASE) as usize;
+
+            target.ram[addr..addr + 4].copy_from_slice(&val.to_le_bytes());
+        };
+
+        // main() at 0x00101100 -> foo() at 0x00101200 -> bar()
+        store(0x0011fff8, 0x00000000);
+        store(0x0011fffc, 0x00000000);
+        store(0x0011ffe8, 0x00120000);
+        store(0x0011ffec, 0x00101100);
+        store(0x0011ffd8, 0x0011fff0);
+        store(0x0011ffdc, 0x00101200);
+
+        target.regs[8] = 0x0011ffe0;
+
+        assert_eq!(vec![0x00101200, 0x00101100], target.backtrace());
+    }
+}
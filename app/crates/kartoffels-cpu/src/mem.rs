@@ -135,6 +135,8 @@ impl Cpu {
             self.ram[rel_addr + offset] = (val >> (offset * 8)) as u8;
         }
 
+        self.icache.invalidate(rel_addr, SIZE);
+
         Ok(())
     }
 
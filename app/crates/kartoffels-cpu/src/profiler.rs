@@ -0,0 +1,148 @@
+use crate::Firmware;
+use std::collections::{BTreeMap, HashMap};
+
+/// Sampling profiler - every n-th tick it records the program counter, which
+/// gives a cheap-ish overview of where the firmware spends its time.
+#[derive(Clone, Debug)]
+pub struct CpuProfiler {
+    interval: u32,
+    countdown: u32,
+    samples: BTreeMap<u32, u32>,
+}
+
+impl CpuProfiler {
+    pub fn new(interval: u32) -> Self {
+        assert!(interval > 0);
+
+        Self {
+            interval,
+            countdown: interval,
+            samples: Default::default(),
+        }
+    }
+
+    pub(super) fn tick(&mut self, pc: u32) {
+        self.countdown -= 1;
+
+        if self.countdown == 0 {
+            self.countdown = self.interval;
+
+            *self.samples.entry(pc).or_default() += 1;
+        }
+    }
+
+    /// Returns collected samples, as a histogram of program counters.
+    pub fn samples(&self) -> &BTreeMap<u32, u32> {
+        &self.samples
+    }
+
+    /// Groups collected samples by functions they belong to.
+    ///
+    /// If the firmware has been stripped (or given address doesn't belong to
+    /// any known function), the address itself is used as the function name.
+    pub fn profile(&self, fw: &Firmware) -> CpuProfile {
+        let mut functions = HashMap::<_, u32>::new();
+
+        for (&addr, &samples) in &self.samples {
+            let name = match fw.symbolize(addr) {
+                Some(name) => name.into(),
+                None => format!("0x{addr:08x}"),
+            };
+
+            *functions.entry(name).or_default() += samples;
+        }
+
+        let mut functions: Vec<_> = functions
+            .into_iter()
+            .map(|(name, samples)| CpuProfileEntry { name, samples })
+            .collect();
+
+        functions.sort_by(|lhs, rhs| {
+            rhs.samples
+                .cmp(&lhs.samples)
+                .then_with(|| lhs.name.cmp(&rhs.name))
+        });
+
+        CpuProfile {
+            samples: self.samples.values().sum(),
+            functions,
+        }
+    }
+}
+
+/// Outcome of profiling, see [`CpuProfiler::profile()`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CpuProfile {
+    pub samples: u32,
+
+    /// Functions sorted by the number of samples, hottest first
+    pub functions: Vec<CpuProfileEntry>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CpuProfileEntry {
+    pub name: String,
+    pub samples: u32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::FirmwareSymbol;
+
+    #[test]
+    fn smoke() {
+        let mut target = CpuProfiler::new(2);
+
+        for pc in [0x1000, 0x1000, 0x1004, 0x1010, 0x1008, 0x1020, 0x1030] {
+            target.tick(pc);
+        }
+
+        assert_eq!(
+            BTreeMap::from_iter([(0x1000, 1), (0x1010, 1), (0x1020, 1)]),
+            *target.samples(),
+        );
+
+        // ---
+
+        for pc in [0x1004; 6] {
+            target.tick(pc);
+        }
+
+        let fw = Firmware {
+            symbols: Some(
+                vec![
+                    FirmwareSymbol {
+                        addr: 0x1000,
+                        size: 0x10,
+                        name: "foo".into(),
+                    },
+                    FirmwareSymbol {
+                        addr: 0x1010,
+                        size: 0x10,
+                        name: "bar".into(),
+                    },
+                ]
+                .into(),
+            ),
+            ..Default::default()
+        };
+
+        let entry = |name: &str, samples| CpuProfileEntry {
+            name: name.into(),
+            samples,
+        };
+
+        assert_eq!(
+            CpuProfile {
+                samples: 6,
+                functions: vec![
+                    entry("foo", 4),
+                    entry("0x00001020", 1),
+                    entry("bar", 1),
+                ],
+            },
+            target.profile(&fw),
+        );
+    }
+}
@@ -1,598 +1,424 @@
-use super::{Cpu, Mmio};
+use super::{Cpu, Instr, Mmio, Op, I, R, S, U};
 use std::cmp;
 use std::ops::{BitAnd, BitOr, BitXor};
 
 impl Cpu {
     pub(super) fn do_tick(&mut self, mmio: impl Mmio) -> Result<(), Box<str>> {
-        let word = self.mem_load::<(), 4>(None, self.pc)? as u32;
-
-        let op = word & 0x7f;
-        let funct3 = (word >> 12) & 0x7;
-        let funct7 = word >> 25;
-
-        self.pc += 4;
-
-        macro_rules! op {
-            (fn $name:ident ( $($arg:ident),* ) $body:tt) => {{
-                $(
-                    let $arg = op!(@arg $arg);
-                )*
-
-                $body;
-            }};
-
-            (@arg rd) => {
-                ((word >> 7) & 0x1f) as usize
-            };
-
-            (@arg rs1) => {
-                ((word >> 15) & 0x1f) as usize
-            };
-
-            (@arg rs2) => {
-                ((word >> 20) & 0x1f) as usize
-            };
-
-            (@arg i_imm) => {
-                (word as i32 as i32) >> 20
-            };
-
-            (@arg u_imm) => {
-                (word as i32 as i32) >> 12
-            };
-
-            (@arg s_imm) => {
-                ((word & 0xfe000000) as i32 as i32 >> 20)
-                    | (((word >> 7) & 0x1f) as i32)
-            };
-
-            (@arg b_imm) => {
-                (((word & 0x80000000) as i32 >> 19) as u32
-                    | ((word & 0x80) << 4)
-                    | ((word >> 20) & 0x7e0)
-                    | ((word >> 7) & 0x1e)) as i32
-            };
-
-            (@arg j_imm) => {
-                (((word & 0x80000000) as i32 >> 11) as u32
-                    | (word & 0xff000)
-                    | ((word >> 9) & 0x800)
-                    | ((word >> 20) & 0x7fe)) as i32
-            };
-        }
+        let pc = self.pc;
 
-        macro_rules! unknown_instr {
-            () => {
-                format!("unknown instruction: 0x{word:08x}").into()
-            };
-        }
+        let addr = pc
+            .checked_sub(Self::RAM_BASE)
+            .map(|addr| addr as usize)
+            .filter(|addr| addr % 2 == 0 && *addr < self.ram.len());
 
-        match (op, funct3, funct7) {
-            (0b0110111, _, _) => op! {
-                fn lui(rd, u_imm) {
-                    self.reg_store(rd, u_imm << 12);
-                }
-            },
+        let instr = match addr.and_then(|addr| self.icache.get(addr)) {
+            Some(instr) => instr,
+
+            None => {
+                let instr = self.fetch(pc)?;
 
-            (0b0010111, _, _) => op! {
-                fn auipc(rd, u_imm) {
-                    self.reg_store(rd, (self.pc as i32) - 4 + (u_imm << 12));
+                if let Some(addr) = addr {
+                    self.icache.insert(addr, instr);
                 }
-            },
 
-            (0b0110011, 0b000, 0b0000000) => op! {
-                fn add(rd, rs1, rs2) {
-                    let lhs = self.regs[rs1];
-                    let rhs = self.regs[rs2];
+                instr
+            }
+        };
 
-                    self.reg_store(rd, lhs.wrapping_add(rhs));
-                }
-            },
+        self.pc += instr.len as u32;
+        self.execute(pc, instr.op, mmio)
+    }
 
-            (0b0010011, 0b000, _) => op! {
-                fn addi(rd, rs1, i_imm) {
-                    let lhs = self.regs[rs1];
-                    let rhs = i_imm;
+    fn fetch(&self, pc: u32) -> Result<Instr, Box<str>> {
+        let lo = self.mem_load::<(), 2>(None, pc)? as u16;
 
-                    self.reg_store(rd, lhs.wrapping_add(rhs));
-                }
-            },
+        Self::decode(lo, || {
+            Ok((self.mem_load::<(), 4>(None, pc)? as u32 >> 16) as u16)
+        })
+    }
 
-            (0b0110011, 0b000, 0b0100000) => op! {
-                fn sub(rd, rs1, rs2) {
-                    let lhs = self.regs[rs1];
-                    let rhs = self.regs[rs2];
+    fn execute(
+        &mut self,
+        pc: u32,
+        op: Op,
+        mmio: impl Mmio,
+    ) -> Result<(), Box<str>> {
+        match op {
+            Op::Lui(U { rd, imm }) => {
+                self.reg_store(rd, imm);
+            }
 
-                    self.reg_store(rd, lhs.wrapping_sub(rhs));
-                }
-            },
+            Op::Auipc(U { rd, imm }) => {
+                self.reg_store(rd, (pc as i32) + imm);
+            }
 
-            (0b0110011, 0b000, 0b0000001) => op! {
-                fn mul(rd, rs1, rs2) {
-                    let lhs = self.regs[rs1];
-                    let rhs = self.regs[rs2];
+            Op::Add(R { rd, rs1, rs2 }) => {
+                let lhs = self.reg_load(rs1);
+                let rhs = self.reg_load(rs2);
 
-                    self.reg_store(rd, lhs.wrapping_mul(rhs));
-                }
-            },
+                self.reg_store(rd, lhs.wrapping_add(rhs));
+            }
 
-            (0b0110011, 0b001, 0b0000001) => op! {
-                fn mulh(rd, rs1, rs2) {
-                    let lhs = self.regs[rs1] as i64;
-                    let rhs = self.regs[rs2] as i64;
+            Op::Addi(I { rd, rs1, imm }) => {
+                let lhs = self.reg_load(rs1);
+                let rhs = imm;
 
-                    self.reg_store(rd, (lhs.wrapping_mul(rhs) >> 32) as i32);
-                }
-            },
+                self.reg_store(rd, lhs.wrapping_add(rhs));
+            }
 
-            (0b0110011, 0b010, 0b0000001) => op! {
-                fn mulhsu(rd, rs1, rs2) {
-                    let lhs = self.regs[rs1] as i64 as u64;
-                    let rhs = self.regs[rs2] as u32 as u64;
+            Op::Sub(R { rd, rs1, rs2 }) => {
+                let lhs = self.reg_load(rs1);
+                let rhs = self.reg_load(rs2);
 
-                    self.reg_store(rd, (lhs.wrapping_mul(rhs) >> 32) as u32 as i32);
-                }
-            },
+                self.reg_store(rd, lhs.wrapping_sub(rhs));
+            }
 
-            (0b0110011, 0b011, 0b0000001) => op! {
-                fn mulhu(rd, rs1, rs2) {
-                    let lhs = self.regs[rs1] as u32 as u64;
-                    let rhs = self.regs[rs2] as u32 as u64;
+            Op::Mul(R { rd, rs1, rs2 }) => {
+                let lhs = self.reg_load(rs1);
+                let rhs = self.reg_load(rs2);
 
-                    self.reg_store(rd, (lhs.wrapping_mul(rhs) >> 32) as i32);
-                }
-            },
+                self.reg_store(rd, lhs.wrapping_mul(rhs));
+            }
 
-            (0b0110011, 0b100, 0b0000001) => op! {
-                fn div(rd, rs1, rs2) {
-                    let lhs = self.regs[rs1];
-                    let rhs = self.regs[rs2];
+            Op::Mulh(R { rd, rs1, rs2 }) => {
+                let lhs = self.reg_load(rs1) as i64;
+                let rhs = self.reg_load(rs2) as i64;
 
-                    self.reg_store(rd, lhs.checked_div(rhs).unwrap_or(-1));
-                }
-            },
+                self.reg_store(rd, (lhs.wrapping_mul(rhs) >> 32) as i32);
+            }
 
-            (0b0110011, 0b101, 0b0000001) => op! {
-                fn divu(rd, rs1, rs2) {
-                    let lhs = self.regs[rs1] as u32;
-                    let rhs = self.regs[rs2] as u32;
+            Op::Mulhsu(R { rd, rs1, rs2 }) => {
+                let lhs = self.reg_load(rs1) as i64 as u64;
+                let rhs = self.reg_load(rs2) as u32 as u64;
 
-                    self.reg_store(
-                        rd,
-                        lhs.checked_div(rhs).unwrap_or(-1i32 as u32) as i32,
-                    );
-                }
-            },
+                self.reg_store(rd, (lhs.wrapping_mul(rhs) >> 32) as u32 as i32);
+            }
 
-            (0b0110011, 0b110, 0b0000001) => op! {
-                fn rem(rd, rs1, rs2) {
-                    let lhs = self.regs[rs1];
-                    let rhs = self.regs[rs2];
+            Op::Mulhu(R { rd, rs1, rs2 }) => {
+                let lhs = self.reg_load(rs1) as u32 as u64;
+                let rhs = self.reg_load(rs2) as u32 as u64;
 
-                    self.reg_store(rd, lhs.checked_rem(rhs).unwrap_or(-1));
-                }
-            },
+                self.reg_store(rd, (lhs.wrapping_mul(rhs) >> 32) as i32);
+            }
 
-            (0b0110011, 0b111, 0b0000001) => op! {
-                fn remu(rd, rs1, rs2) {
-                    let lhs = self.regs[rs1] as u32;
-                    let rhs = self.regs[rs2] as u32;
+            Op::Div(R { rd, rs1, rs2 }) => {
+                let lhs = self.reg_load(rs1);
+                let rhs = self.reg_load(rs2);
 
-                    self.reg_store(
-                        rd,
-                        lhs.checked_rem(rhs).unwrap_or(-1i32 as u32) as i32,
-                    );
-                }
-            },
+                self.reg_store(rd, lhs.checked_div(rhs).unwrap_or(-1));
+            }
 
-            (0b0110011, 0b111, 0b0000000) => op! {
-                fn and(rd, rs1, rs2) {
-                    let lhs = self.regs[rs1];
-                    let rhs = self.regs[rs2];
+            Op::Divu(R { rd, rs1, rs2 }) => {
+                let lhs = self.reg_load(rs1) as u32;
+                let rhs = self.reg_load(rs2) as u32;
 
-                    self.reg_store(rd, lhs & rhs);
-                }
-            },
+                self.reg_store(
+                    rd,
+                    lhs.checked_div(rhs).unwrap_or(-1i32 as u32) as i32,
+                );
+            }
 
-            (0b0010011, 0b111, _) => op! {
-                fn andi(rd, rs1, i_imm) {
-                    let lhs = self.regs[rs1];
-                    let rhs = i_imm;
+            Op::Rem(R { rd, rs1, rs2 }) => {
+                let lhs = self.reg_load(rs1);
+                let rhs = self.reg_load(rs2);
 
-                    self.reg_store(rd, lhs & rhs);
-                }
-            },
+                self.reg_store(rd, lhs.checked_rem(rhs).unwrap_or(-1));
+            }
 
-            (0b0110011, 0b110, 0b0000000) => op! {
-                fn or(rd, rs1, rs2) {
-                    let lhs = self.regs[rs1];
-                    let rhs = self.regs[rs2];
+            Op::Remu(R { rd, rs1, rs2 }) => {
+                let lhs = self.reg_load(rs1) as u32;
+                let rhs = self.reg_load(rs2) as u32;
 
-                    self.reg_store(rd, lhs | rhs);
-                }
-            },
+                self.reg_store(
+                    rd,
+                    lhs.checked_rem(rhs).unwrap_or(-1i32 as u32) as i32,
+                );
+            }
 
-            (0b0010011, 0b110, _) => op! {
-                fn ori(rd, rs1, i_imm) {
-                    let lhs = self.regs[rs1];
-                    let rhs = i_imm;
+            Op::And(R { rd, rs1, rs2 }) => {
+                let lhs = self.reg_load(rs1);
+                let rhs = self.reg_load(rs2);
 
-                    self.reg_store(rd, lhs | rhs);
-                }
-            },
+                self.reg_store(rd, lhs & rhs);
+            }
 
-            (0b0110011, 0b100, 0b0000000) => op! {
-                fn xor(rd, rs1, rs2) {
-                    let lhs = self.regs[rs1];
-                    let rhs = self.regs[rs2];
+            Op::Andi(I { rd, rs1, imm }) => {
+                let lhs = self.reg_load(rs1);
+                let rhs = imm;
 
-                    self.reg_store(rd, lhs ^ rhs);
-                }
-            },
+                self.reg_store(rd, lhs & rhs);
+            }
 
-            (0b0010011, 0b100, _) => op! {
-                fn xori(rd, rs1, i_imm) {
-                    let lhs = self.regs[rs1];
-                    let rhs = i_imm;
+            Op::Or(R { rd, rs1, rs2 }) => {
+                let lhs = self.reg_load(rs1);
+                let rhs = self.reg_load(rs2);
 
-                    self.reg_store(rd, lhs ^ rhs);
-                }
-            },
+                self.reg_store(rd, lhs | rhs);
+            }
 
-            (0b0110011, 0b001, 0b0000000) => op! {
-                fn sll(rd, rs1, rs2) {
-                    let lhs = self.regs[rs1] as u32;
-                    let rhs = self.regs[rs2] as u32;
+            Op::Ori(I { rd, rs1, imm }) => {
+                let lhs = self.reg_load(rs1);
+                let rhs = imm;
 
-                    self.reg_store(rd, lhs.wrapping_shl(rhs) as i32);
-                }
-            },
+                self.reg_store(rd, lhs | rhs);
+            }
 
-            (0b0010011, 0b001, _) => {
-                let i_imm = op!(@arg i_imm);
+            Op::Xor(R { rd, rs1, rs2 }) => {
+                let lhs = self.reg_load(rs1);
+                let rhs = self.reg_load(rs2);
 
-                match i_imm >> 6 {
-                    0x00 => op! {
-                        fn slli(rd, rs1, i_imm) {
-                            let lhs = self.regs[rs1] as u32;
-                            let rhs = i_imm as u32;
+                self.reg_store(rd, lhs ^ rhs);
+            }
 
-                            self.reg_store(rd, lhs.wrapping_shl(rhs) as i32);
-                        }
-                    },
+            Op::Xori(I { rd, rs1, imm }) => {
+                let lhs = self.reg_load(rs1);
+                let rhs = imm;
 
-                    _ => {
-                        return Err(unknown_instr!());
-                    }
-                }
+                self.reg_store(rd, lhs ^ rhs);
             }
 
-            (0b0110011, 0b101, 0b0000000) => op! {
-                fn srl(rd, rs1, rs2) {
-                    let lhs = self.regs[rs1] as u32;
-                    let rhs = self.regs[rs2] as u32;
+            Op::Sll(R { rd, rs1, rs2 }) => {
+                let lhs = self.reg_load(rs1) as u32;
+                let rhs = self.reg_load(rs2) as u32;
 
-                    self.reg_store(rd, lhs.wrapping_shr(rhs) as i32);
-                }
-            },
+                self.reg_store(rd, lhs.wrapping_shl(rhs) as i32);
+            }
 
-            (0b0010011, 0b101, _) => {
-                let i_imm = op!(@arg i_imm);
+            Op::Slli(I { rd, rs1, imm }) => {
+                let lhs = self.reg_load(rs1) as u32;
+                let rhs = imm as u32;
 
-                match i_imm >> 6 {
-                    0x00 => op! {
-                        fn srli(rd, rs1, i_imm) {
-                            let lhs = self.regs[rs1] as u32;
-                            let rhs = i_imm as u32;
+                self.reg_store(rd, lhs.wrapping_shl(rhs) as i32);
+            }
 
-                            self.reg_store(rd, lhs.wrapping_shr(rhs) as i32);
-                        }
-                    },
+            Op::Srl(R { rd, rs1, rs2 }) => {
+                let lhs = self.reg_load(rs1) as u32;
+                let rhs = self.reg_load(rs2) as u32;
 
-                    0x10 => op! {
-                        fn srai(rd, rs1, i_imm) {
-                            let lhs = self.regs[rs1];
-                            let rhs = (i_imm as u32) & 0x3f;
+                self.reg_store(rd, lhs.wrapping_shr(rhs) as i32);
+            }
 
-                            self.reg_store(rd, lhs.wrapping_shr(rhs));
-                        }
-                    },
+            Op::Srli(I { rd, rs1, imm }) => {
+                let lhs = self.reg_load(rs1) as u32;
+                let rhs = imm as u32;
 
-                    _ => {
-                        return Err(unknown_instr!());
-                    }
-                }
+                self.reg_store(rd, lhs.wrapping_shr(rhs) as i32);
             }
 
-            (0b0110011, 0b101, 0b0100000) => op! {
-                fn sra(rd, rs1, rs2) {
-                    let lhs = self.regs[rs1];
-                    let rhs = self.regs[rs2] as u32;
+            Op::Sra(R { rd, rs1, rs2 }) => {
+                let lhs = self.reg_load(rs1);
+                let rhs = self.reg_load(rs2) as u32;
 
-                    self.reg_store(rd, lhs.wrapping_shr(rhs));
-                }
-            },
+                self.reg_store(rd, lhs.wrapping_shr(rhs));
+            }
 
-            (0b0110011, 0b010, 0b0000000) => op! {
-                fn slt(rd, rs1, rs2) {
-                    let lhs = self.regs[rs1];
-                    let rhs = self.regs[rs2];
+            Op::Srai(I { rd, rs1, imm }) => {
+                let lhs = self.reg_load(rs1);
+                let rhs = imm as u32;
 
-                    self.reg_store(rd, (lhs < rhs) as i32);
-                }
-            },
+                self.reg_store(rd, lhs.wrapping_shr(rhs));
+            }
 
-            (0b0010011, 0b010, _) => op! {
-                fn slti(rd, rs1, i_imm) {
-                    let lhs = self.regs[rs1];
-                    let rhs = i_imm;
+            Op::Slt(R { rd, rs1, rs2 }) => {
+                let lhs = self.reg_load(rs1);
+                let rhs = self.reg_load(rs2);
 
-                    self.reg_store(rd, (lhs < rhs) as i32);
-                }
-            },
+                self.reg_store(rd, (lhs < rhs) as i32);
+            }
 
-            (0b0110011, 0b011, 0b0000000) => op! {
-                fn sltu(rd, rs1, rs2) {
-                    let lhs = self.regs[rs1] as u32;
-                    let rhs = self.regs[rs2] as u32;
+            Op::Slti(I { rd, rs1, imm }) => {
+                let lhs = self.reg_load(rs1);
+                let rhs = imm;
 
-                    self.reg_store(rd, (lhs < rhs) as i32);
-                }
-            },
+                self.reg_store(rd, (lhs < rhs) as i32);
+            }
 
-            (0b0010011, 0b011, _) => op! {
-                fn sltiu(rd, rs1, i_imm) {
-                    let lhs = self.regs[rs1] as u32;
-                    let rhs = i_imm as u32;
+            Op::Sltu(R { rd, rs1, rs2 }) => {
+                let lhs = self.reg_load(rs1) as u32;
+                let rhs = self.reg_load(rs2) as u32;
 
-                    self.reg_store(rd, (lhs < rhs) as i32);
-                }
-            },
+                self.reg_store(rd, (lhs < rhs) as i32);
+            }
 
-            (0b0000011, 0b000, _) => op! {
-                fn lb(rd, rs1, i_imm) {
-                    let addr = (self.regs[rs1] + i_imm) as u32;
-                    let val = self.mem_load::<_, 1>(Some(mmio), addr)? as i8 as i32;
+            Op::Sltiu(I { rd, rs1, imm }) => {
+                let lhs = self.reg_load(rs1) as u32;
+                let rhs = imm as u32;
 
-                    self.reg_store(rd, val);
-                }
-            },
+                self.reg_store(rd, (lhs < rhs) as i32);
+            }
 
-            (0b0000011, 0b100, _) => op! {
-                fn lbu(rd, rs1, i_imm) {
-                    let addr = (self.regs[rs1] + i_imm) as u32;
-                    let val = self.mem_load::<_, 1>(Some(mmio), addr)?;
+            Op::Lb(I { rd, rs1, imm }) => {
+                let addr = (self.reg_load(rs1) + imm) as u32;
+                let val = self.mem_load::<_, 1>(Some(mmio), addr)? as i8 as i32;
 
-                    self.reg_store(rd, val);
-                }
-            },
+                self.reg_store(rd, val);
+            }
 
-            (0b0000011, 0b001, _) => op! {
-                fn lh(rd, rs1, i_imm) {
-                    let addr = (self.regs[rs1] + i_imm) as u32;
-                    let val = self.mem_load::<_, 2>(Some(mmio), addr)? as i16 as i32;
+            Op::Lbu(I { rd, rs1, imm }) => {
+                let addr = (self.reg_load(rs1) + imm) as u32;
+                let val = self.mem_load::<_, 1>(Some(mmio), addr)?;
 
-                    self.reg_store(rd, val);
-                }
-            },
+                self.reg_store(rd, val);
+            }
 
-            (0b0000011, 0b101, _) => op! {
-                fn lhu(rd, rs1, i_imm) {
-                    let addr = (self.regs[rs1] + i_imm) as u32;
-                    let val = self.mem_load::<_, 2>(Some(mmio), addr)?;
+            Op::Lh(I { rd, rs1, imm }) => {
+                let addr = (self.reg_load(rs1) + imm) as u32;
+                let val =
+                    self.mem_load::<_, 2>(Some(mmio), addr)? as i16 as i32;
 
-                    self.reg_store(rd, val);
-                }
-            },
+                self.reg_store(rd, val);
+            }
 
-            (0b0000011, 0b010, _) => op! {
-                fn lw(rd, rs1, i_imm) {
-                    let addr = (self.regs[rs1] + i_imm) as u32;
-                    let val = self.mem_load::<_, 4>(Some(mmio), addr)?;
+            Op::Lhu(I { rd, rs1, imm }) => {
+                let addr = (self.reg_load(rs1) + imm) as u32;
+                let val = self.mem_load::<_, 2>(Some(mmio), addr)?;
 
-                    self.reg_store(rd, val);
-                }
-            },
+                self.reg_store(rd, val);
+            }
 
-            (0b0100011, 0b000, _) => op! {
-                fn sb(rs1, rs2, s_imm) {
-                    let addr = self.regs[rs1].wrapping_add(s_imm) as u32;
-                    let val = self.regs[rs2];
+            Op::Lw(I { rd, rs1, imm }) => {
+                let addr = (self.reg_load(rs1) + imm) as u32;
+                let val = self.mem_load::<_, 4>(Some(mmio), addr)?;
 
-                    self.mem_store::<_, 1>(Some(mmio), addr, val)?;
-                }
-            },
+                self.reg_store(rd, val);
+            }
 
-            (0b0100011, 0b001, _) => op! {
-                fn sh(rs1, rs2, s_imm) {
-                    let addr = self.regs[rs1].wrapping_add(s_imm) as u32;
-                    let val = self.regs[rs2];
+            Op::Sb(S { rs1, rs2, imm }) => {
+                let addr = self.reg_load(rs1).wrapping_add(imm) as u32;
+                let val = self.reg_load(rs2);
 
-                    self.mem_store::<_, 2>(Some(mmio), addr, val)?;
-                }
-            },
+                self.mem_store::<_, 1>(Some(mmio), addr, val)?;
+            }
 
-            (0b0100011, 0b010, _) => op! {
-                fn sw(rs1, rs2, s_imm) {
-                    let addr = self.regs[rs1].wrapping_add(s_imm) as u32;
-                    let val = self.regs[rs2];
+            Op::Sh(S { rs1, rs2, imm }) => {
+                let addr = self.reg_load(rs1).wrapping_add(imm) as u32;
+                let val = self.reg_load(rs2);
 
-                    self.mem_store::<_, 4>(Some(mmio), addr, val)?;
-                }
-            },
-
-            (0b0101111, 0b010, _) => {
-                // funct7's low bits encode the ordering semantics (acquire
-                // and/or release) which we don't care about
-                let funct5 = funct7 >> 2;
-
-                match funct5 {
-                    0b00000 => op! {
-                        fn amoaddw(rd, rs1, rs2) {
-                            self.do_atomic::<4>(rd, rs1, rs2, i32::wrapping_add)?;
-                        }
-                    },
-
-                    0b00001 => op! {
-                        fn amoswapw(rd, rs1, rs2) {
-                            self.do_atomic::<4>(rd, rs1, rs2, |_, rhs| rhs)?;
-                        }
-                    },
-
-                    0b00010 => op! {
-                        fn lrw(rd, rs1) {
-                            let addr = self.regs[rs1] as u32;
-                            let val = self.mem_load::<(), 4>(None, addr)?;
-
-                            self.reg_store(rd, val);
-                        }
-                    },
-
-                    0b00011 => op! {
-                        fn scw(rd, rs1, rs2) {
-                            let addr = self.regs[rs1] as u32;
-                            let val = self.regs[rs2];
-
-                            self.mem_store::<(), 4>(None, addr, val)?;
-                            self.regs[rd] = 0;
-                        }
-                    },
-
-                    0b00100 => op! {
-                        fn amoxorw(rd, rs1, rs2) {
-                            self.do_atomic::<4>(rd, rs1, rs2, BitXor::bitxor)?;
-                        }
-                    },
-
-                    0b01100 => op! {
-                        fn amoandw(rd, rs1, rs2) {
-                            self.do_atomic::<4>(rd, rs1, rs2, BitAnd::bitand)?;
-                        }
-                    },
-
-                    0b01000 => op! {
-                        fn amoorw(rd, rs1, rs2) {
-                            self.do_atomic::<4>(rd, rs1, rs2, BitOr::bitor)?;
-                        }
-                    },
-
-                    0b10000 => op! {
-                        fn amominw(rd, rs1, rs2) {
-                            self.do_atomic::<4>(rd, rs1, rs2, cmp::min)?;
-                        }
-                    },
-
-                    0b10100 => op! {
-                        fn amomaxw(rd, rs1, rs2) {
-                            self.do_atomic::<4>(rd, rs1, rs2, cmp::max)?;
-                        }
-                    },
-
-                    0b11000 => op! {
-                        fn amominuw(rd, rs1, rs2) {
-                            self.do_atomic::<4>(rd, rs1, rs2, |lhs, rhs| {
-                                cmp::min(lhs as u32, rhs as u32) as u32 as i32
-                            })?;
-                        }
-                    },
-
-                    0b11100 => op! {
-                        fn amomaxuw(rd, rs1, rs2) {
-                            self.do_atomic::<4>(rd, rs1, rs2, |lhs, rhs| {
-                                cmp::max(lhs as u32, rhs as u32) as u32 as i32
-                            })?;
-                        }
-                    },
-
-                    _ => {
-                        return Err(unknown_instr!());
-                    }
-                }
+                self.mem_store::<_, 2>(Some(mmio), addr, val)?;
             }
 
-            (0b0001111, 0b000, _) => {
-                // atomic fence
+            Op::Sw(S { rs1, rs2, imm }) => {
+                let addr = self.reg_load(rs1).wrapping_add(imm) as u32;
+                let val = self.reg_load(rs2);
+
+                self.mem_store::<_, 4>(Some(mmio), addr, val)?;
             }
 
-            (0b1100011, 0b000, _) => op! {
-                fn beq(rs1, rs2, b_imm) {
-                    self.do_branch(rs1, rs2, b_imm, |lhs, rhs| lhs == rhs);
-                }
-            },
+            Op::AmoaddW(R { rd, rs1, rs2 }) => {
+                self.do_atomic::<4>(rd, rs1, rs2, i32::wrapping_add)?;
+            }
 
-            (0b1100011, 0b001, _) => op! {
-                fn bne(rs1, rs2, b_imm) {
-                    self.do_branch(rs1, rs2, b_imm, |lhs, rhs| lhs != rhs);
-                }
-            },
+            Op::AmoswapW(R { rd, rs1, rs2 }) => {
+                self.do_atomic::<4>(rd, rs1, rs2, |_, rhs| rhs)?;
+            }
 
-            (0b1100011, 0b100, _) => op! {
-                fn blt(rs1, rs2, b_imm) {
-                    self.do_branch(rs1, rs2, b_imm, |lhs, rhs| lhs < rhs);
-                }
-            },
+            Op::LrW(R { rd, rs1, .. }) => {
+                let addr = self.reg_load(rs1) as u32;
+                let val = self.mem_load::<(), 4>(None, addr)?;
 
-            (0b1100011, 0b110, _) => op! {
-                fn bltu(rs1, rs2, b_imm) {
-                    self.do_branch(rs1, rs2, b_imm, |lhs, rhs| {
-                        (lhs as u32) < (rhs as u32)
-                    });
-                }
-            },
+                self.reg_store(rd, val);
+            }
 
-            (0b1100011, 0b101, _) => op! {
-                fn bge(rs1, rs2, b_imm) {
-                    self.do_branch(rs1, rs2, b_imm, |lhs, rhs| lhs >= rhs);
-                }
-            },
+            Op::ScW(R { rd, rs1, rs2 }) => {
+                let addr = self.reg_load(rs1) as u32;
+                let val = self.reg_load(rs2);
 
-            (0b1100011, 0b111, _) => op! {
-                fn bgeu(rs1, rs2, b_imm) {
-                    self.do_branch(rs1, rs2, b_imm, |lhs, rhs| {
-                        (lhs as u32) >= (rhs as u32)
-                    });
-                }
-            },
+                self.mem_store::<(), 4>(None, addr, val)?;
+                self.regs[rd as usize] = 0;
+            }
 
-            (0b1101111, _, _) => op! {
-                fn jal(rd, j_imm) {
-                    #[cfg(test)]
-                    if j_imm == 0 {
-                        return Err("infinite loop detected".into());
-                    }
+            Op::AmoxorW(R { rd, rs1, rs2 }) => {
+                self.do_atomic::<4>(rd, rs1, rs2, BitXor::bitxor)?;
+            }
 
-                    self.reg_store(rd, self.pc as i32);
+            Op::AmoandW(R { rd, rs1, rs2 }) => {
+                self.do_atomic::<4>(rd, rs1, rs2, BitAnd::bitand)?;
+            }
 
-                    self.pc =
-                        self.pc.wrapping_add_signed(j_imm as i32).wrapping_sub(4);
-                }
-            },
+            Op::AmoorW(R { rd, rs1, rs2 }) => {
+                self.do_atomic::<4>(rd, rs1, rs2, BitOr::bitor)?;
+            }
 
-            (0b1100111, 0b000, _) => op! {
-                fn jalr(rd, rs1, i_imm) {
-                    let rs1_val = self.regs[rs1];
+            Op::AmominW(R { rd, rs1, rs2 }) => {
+                self.do_atomic::<4>(rd, rs1, rs2, cmp::min)?;
+            }
 
-                    self.reg_store(rd, self.pc as i32);
-                    self.pc = rs1_val.wrapping_add(i_imm) as u32;
-                }
-            },
+            Op::AmomaxW(R { rd, rs1, rs2 }) => {
+                self.do_atomic::<4>(rd, rs1, rs2, cmp::max)?;
+            }
+
+            Op::AmominuW(R { rd, rs1, rs2 }) => {
+                self.do_atomic::<4>(rd, rs1, rs2, |lhs, rhs| {
+                    cmp::min(lhs as u32, rhs as u32) as u32 as i32
+                })?;
+            }
+
+            Op::AmomaxuW(R { rd, rs1, rs2 }) => {
+                self.do_atomic::<4>(rd, rs1, rs2, |lhs, rhs| {
+                    cmp::max(lhs as u32, rhs as u32) as u32 as i32
+                })?;
+            }
+
+            Op::Fence => {
+                // atomic fence
+            }
+
+            Op::Beq(S { rs1, rs2, imm }) => {
+                self.do_branch(pc, rs1, rs2, imm, |lhs, rhs| lhs == rhs);
+            }
 
-            (0b1110011, 0b000, _) => {
-                let i_imm = op!(@arg i_imm);
+            Op::Bne(S { rs1, rs2, imm }) => {
+                self.do_branch(pc, rs1, rs2, imm, |lhs, rhs| lhs != rhs);
+            }
+
+            Op::Blt(S { rs1, rs2, imm }) => {
+                self.do_branch(pc, rs1, rs2, imm, |lhs, rhs| lhs < rhs);
+            }
 
-                match i_imm {
-                    0x01 => {
-                        return Err("got `ebreak`".into());
-                    }
+            Op::Bltu(S { rs1, rs2, imm }) => {
+                self.do_branch(pc, rs1, rs2, imm, |lhs, rhs| {
+                    (lhs as u32) < (rhs as u32)
+                });
+            }
 
-                    _ => {
-                        return Err(unknown_instr!());
-                    }
+            Op::Bge(S { rs1, rs2, imm }) => {
+                self.do_branch(pc, rs1, rs2, imm, |lhs, rhs| lhs >= rhs);
+            }
+
+            Op::Bgeu(S { rs1, rs2, imm }) => {
+                self.do_branch(pc, rs1, rs2, imm, |lhs, rhs| {
+                    (lhs as u32) >= (rhs as u32)
+                });
+            }
+
+            Op::Jal(U { rd, imm }) => {
+                #[cfg(test)]
+                if imm == 0 {
+                    return Err("infinite loop detected".into());
                 }
+
+                self.reg_store(rd, self.pc as i32);
+
+                self.pc = pc.wrapping_add_signed(imm);
             }
 
-            _ => {
-                return Err(unknown_instr!());
+            Op::Jalr(I { rd, rs1, imm }) => {
+                let rs1_val = self.reg_load(rs1);
+
+                self.reg_store(rd, self.pc as i32);
+                self.pc = rs1_val.wrapping_add(imm) as u32;
+            }
+
+            Op::Ebreak => {
+                return Err("got `ebreak`".into());
+            }
+
+            Op::Unknown(word) => {
+                return Err(format!("unknown instruction: 0x{word:08x}").into());
+            }
+
+            Op::UnknownCompressed(half) => {
+                return Err(format!("unknown instruction: 0x{half:04x}").into());
             }
         }
 
@@ -601,30 +427,31 @@ impl Cpu {
 
     fn do_branch(
         &mut self,
-        rs1: usize,
-        rs2: usize,
+        pc: u32,
+        rs1: u8,
+        rs2: u8,
         imm: i32,
         op: fn(i32, i32) -> bool,
     ) {
-        let lhs = self.regs[rs1];
-        let rhs = self.regs[rs2];
+        let lhs = self.reg_load(rs1);
+        let rhs = self.reg_load(rs2);
 
         if op(lhs, rhs) {
-            self.pc = self.pc.wrapping_add_signed(imm).wrapping_sub(4);
+            self.pc = pc.wrapping_add_signed(imm);
         }
     }
 
     fn do_atomic<const SIZE: usize>(
         &mut self,
-        rd: usize,
-        rs1: usize,
-        rs2: usize,
+        rd: u8,
+        rs1: u8,
+        rs2: u8,
         op: fn(i32, i32) -> i32,
     ) -> Result<(), Box<str>> {
-        let addr = self.regs[rs1] as u32;
+        let addr = self.reg_load(rs1) as u32;
 
         let old_val = self.mem_load::<(), SIZE>(None, addr)?;
-        let new_val = op(old_val, self.regs[rs2]);
+        let new_val = op(old_val, self.reg_load(rs2));
 
         self.mem_store::<(), SIZE>(None, addr, new_val)?;
         self.reg_store(rd, old_val);
@@ -632,9 +459,13 @@ impl Cpu {
         Ok(())
     }
 
-    fn reg_store(&mut self, id: usize, val: i32) {
+    fn reg_load(&self, id: u8) -> i32 {
+        self.regs[id as usize]
+    }
+
+    fn reg_store(&mut self, id: u8, val: i32) {
         if id != 0 {
-            self.regs[id] = val;
+            self.regs[id as usize] = val;
         }
     }
 }
@@ -4,7 +4,7 @@ mod utils;
 mod views;
 
 use self::utils::*;
-use anyhow::Result;
+use anyhow::{Error, Result};
 use kartoffels_store::{Session, Store};
 use kartoffels_ui::{Abort, Frame};
 
@@ -43,6 +43,18 @@ pub async fn main(
     }
 }
 
+/// Shows an error screen to someone we couldn't create a session for, e.g.
+/// because they've exceeded one of the limits.
+pub async fn reject(
+    store: &Store,
+    frame: &mut Frame,
+    err: Error,
+) -> Result<()> {
+    let bg = Background::new(store, frame);
+
+    views::error::run(frame, &bg, err).await
+}
+
 pub fn init() {
     Background::init();
 }
@@ -1,5 +1,6 @@
 use crate::Background;
 use anyhow::{Error, Result};
+use kartoffels_store::LimitExceeded;
 use kartoffels_ui::{Button, Frame, KeyCode};
 use kartoffels_utils::ErrorExt;
 use ratatui::layout::{Constraint, Layout};
@@ -9,7 +10,26 @@ use tracing::debug;
 pub async fn run(frame: &mut Frame, bg: &Background, err: Error) -> Result<()> {
     debug!(?err, "run()");
 
-    let err = Paragraph::new(err.to_fmt_string()).wrap(Default::default());
+    // Hitting a limit is not really an error on our side, so let's be a bit
+    // more helpful there
+    let (title, err) = match err.downcast_ref::<LimitExceeded>() {
+        Some(limit) => {
+            let hint = match limit {
+                LimitExceeded::Sessions => {
+                    "close some of your other sessions and try again"
+                }
+                LimitExceeded::Uploads | LimitExceeded::PrivateWorlds => {
+                    "wait a minute and try again"
+                }
+            };
+
+            (" slow down ", format!("{limit}, please {hint}"))
+        }
+
+        None => (" ouch ", err.to_fmt_string()),
+    };
+
+    let err = Paragraph::new(err).wrap(Default::default());
 
     loop {
         let go_back = frame
@@ -19,7 +39,7 @@ pub async fn run(frame: &mut Frame, bg: &Background, err: Error) -> Result<()> {
 
                 ui.add(bg);
 
-                ui.error_window(width, height, Some(" ouch "), |ui| {
+                ui.error_window(width, height, Some(title), |ui| {
                     let [text_area, _, footer_area] = Layout::vertical([
                         Constraint::Fill(1),
                         Constraint::Length(1),
@@ -22,7 +22,7 @@ use anyhow::Result;
 use futures_util::FutureExt;
 use glam::{IVec2, UVec2};
 use kartoffels_store::{Session, Store};
-use kartoffels_ui::{theme, Clear, Fade, FadeDir, Frame, Ui, UiWidget};
+use kartoffels_ui::{theme, Clear, Fade, FadeDir, Frame, Input, Ui, UiWidget};
 use kartoffels_world::prelude::{
     BotId, Handle as WorldHandle, Snapshot as WorldSnapshot, SnapshotStream,
 };
@@ -30,7 +30,7 @@ use ratatui::layout::{Constraint, Layout};
 use std::future::Future;
 use std::ops::ControlFlow;
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use tokio::select;
 use tokio::sync::oneshot;
 use tracing::debug;
@@ -65,7 +65,10 @@ async fn run_once(
 
     let mut fade = Some(Fade::new(FadeDir::In));
     let mut tick = Instant::now();
-    let mut state = State::default();
+    let mut state = State {
+        owner: sess.with(|sess| sess.owner().clone()),
+        ..Default::default()
+    };
 
     loop {
         let event = frame
@@ -83,7 +86,7 @@ async fn run_once(
 
         if let Some(event) = event {
             if let ControlFlow::Break(_) =
-                event.handle(frame, &mut state).await?
+                event.handle(store, sess, frame, &mut state).await?
             {
                 fade = Some(Fade::new(FadeDir::Out));
             }
@@ -110,7 +113,13 @@ struct State {
     map: Map,
     modal: Option<Box<Modal>>,
     mode: Mode,
+    owner: Arc<str>,
     paused: bool,
+
+    /// Bot that's currently being profiled and when its profile has been last
+    /// fetched, see [`Self::update_profile()`]
+    profile: Option<(BotId, Instant)>,
+
     restart: Option<oneshot::Sender<()>>,
     snapshot: Arc<WorldSnapshot>,
     snapshots: Option<SnapshotStream>,
@@ -118,6 +127,14 @@ struct State {
 }
 
 impl State {
+    fn is_own_bot(&self, id: BotId) -> bool {
+        self.snapshot
+            .bots
+            .get(id)
+            .and_then(|bot| bot.owner())
+            .is_some_and(|owner| *owner == *self.owner)
+    }
+
     fn tick(&mut self, dt: f32, store: &Store) {
         // If we're following a bot, adjust the camera to the bot's current
         // position - unless we're under test, in which case we don't want to
@@ -184,9 +201,15 @@ impl State {
             }
         }
 
+        // While typing a message for the bot, keystrokes belong to the input
+        // box - so let's not trigger any shortcuts in the meantime
+        let typing = self.bot.as_ref().is_some_and(|bot| bot.input.is_some());
+
         ui.enable(self.modal.is_none(), |ui| {
-            ui.clamp(bottom_area, |ui| {
-                BottomPanel::render(ui, self);
+            ui.enable(!typing, |ui| {
+                ui.clamp(bottom_area, |ui| {
+                    BottomPanel::render(ui, self);
+                });
             });
 
             if self.handle.is_some() {
@@ -195,8 +218,10 @@ impl State {
                         SidePanel::render(ui, self);
                     });
 
-                    ui.clamp(map_area, |ui| {
-                        self.map.render(ui, self);
+                    ui.enable(!typing, |ui| {
+                        ui.clamp(map_area, |ui| {
+                            self.map.render(ui, self);
+                        });
                     });
                 });
             }
@@ -224,6 +249,63 @@ impl State {
             self.update_snapshot(snapshot?);
         }
 
+        self.update_profile().await?;
+
+        Ok(())
+    }
+
+    /// Keeps bot's profiler in sync with the inspect-bot modal - this way the
+    /// profiler gets stopped no matter how the modal got closed.
+    ///
+    /// Profiler is shared with other viewers of the same bot, so each start
+    /// here must be paired with a stop - otherwise we'd keep the profiler
+    /// running forever or stop it for everybody else.
+    async fn update_profile(&mut self) -> Result<()> {
+        let Some(handle) = &self.handle else {
+            return Ok(());
+        };
+
+        let modal = match self.modal.as_deref_mut() {
+            Some(Modal::InspectBot(modal)) => Some(modal),
+            _ => None,
+        };
+
+        let id = modal.as_ref().and_then(|modal| modal.profiled_bot());
+
+        if let Some((prev_id, _)) = self.profile
+            && Some(prev_id) != id
+        {
+            handle.stop_profiling_bot(prev_id).await?;
+
+            self.profile = None;
+        }
+
+        if let Some(id) = id
+            && let Some(modal) = modal
+            && self.snapshot.bots.alive.has(id)
+        {
+            match self.profile {
+                Some((_, refreshed_at)) => {
+                    if refreshed_at.elapsed() < Duration::from_secs(1) {
+                        return Ok(());
+                    }
+                }
+
+                None => {
+                    // Bot might've died in the meantime, in which case there's
+                    // nothing to profile
+                    if handle.start_profiling_bot(id).await.is_err() {
+                        modal.set_profile(None);
+                        return Ok(());
+                    }
+                }
+            }
+
+            modal.set_profile(handle.profile_bot(id).await.ok());
+
+            self.profile = Some((id, Instant::now()));
+        }
+
         Ok(())
     }
 
@@ -284,6 +366,7 @@ impl State {
             id,
             follow,
             exists: false,
+            input: None,
         });
 
         self.map.blink = Instant::now();
@@ -308,4 +391,7 @@ struct JoinedBot {
     id: BotId,
     follow: bool,
     exists: bool,
+
+    /// Message being typed for the bot's serial port, if any
+    input: Option<Input>,
 }
@@ -15,11 +15,13 @@ pub struct Config {
     /// user themself.
     pub sync_pause: bool,
 
-    pub can_delete_bots: bool,
+    pub can_debug_bots: BotAccess,
+    pub can_delete_bots: BotAccess,
     pub can_join_bots: bool,
     pub can_overclock: bool,
     pub can_pause: bool,
-    pub can_restart_bots: bool,
+    pub can_restart_bots: BotAccess,
+    pub can_send_to_bots: BotAccess,
     pub can_spawn_bots: bool,
     pub can_upload_bots: bool,
 }
@@ -38,13 +40,37 @@ impl Default for Config {
             hero_mode: false,
             sync_pause: false,
 
-            can_delete_bots: false,
+            can_debug_bots: BotAccess::None,
+            can_delete_bots: BotAccess::None,
             can_join_bots: true,
             can_overclock: false,
             can_pause: true,
-            can_restart_bots: false,
+            can_restart_bots: BotAccess::None,
+            can_send_to_bots: BotAccess::None,
             can_spawn_bots: false,
             can_upload_bots: true,
         }
     }
 }
+
+/// Which bots given action can be performed on.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BotAccess {
+    None,
+
+    /// Only bots uploaded by the current player (or, for anonymous users, by
+    /// the current session)
+    Own,
+
+    All,
+}
+
+impl BotAccess {
+    pub fn allows(self, is_own: bool) -> bool {
+        match self {
+            BotAccess::None => false,
+            BotAccess::Own => is_own,
+            BotAccess::All => true,
+        }
+    }
+}
@@ -1,5 +1,5 @@
 use super::{Modal, State};
-use crate::views::game::{Config, HelpMsgRef};
+use crate::views::game::{BotAccess, Config, HelpMsgRef};
 use anyhow::{anyhow, Result};
 use kartoffels_ui::{theme, Frame, Msg, Ui};
 use kartoffels_world::prelude::Handle as WorldHandle;
@@ -172,6 +172,14 @@ impl GameCtrlEvent {
     ) -> Result<()> {
         match self {
             GameCtrlEvent::Join(handle) => {
+                // Profiler belongs to the previous world, so let's stop it
+                // before we lose track of it
+                if let Some(prev) = &state.handle
+                    && let Some((id, _)) = state.profile.take()
+                {
+                    prev.stop_profiling_bot(id).await?;
+                }
+
                 let mut snapshots = handle.snapshots();
 
                 state.snapshot = snapshots.next().await?;
@@ -215,8 +223,7 @@ impl GameCtrlEvent {
 
             GameCtrlEvent::WaitForRestart(tx) => {
                 state.config.can_join_bots = false;
-                state.config.can_restart_bots = false;
-                state.config.can_restart_bots = false;
+                state.config.can_restart_bots = BotAccess::None;
                 state.config.can_spawn_bots = false;
                 state.config.can_upload_bots = false;
                 state.restart = Some(tx);
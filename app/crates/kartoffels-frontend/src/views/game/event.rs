@@ -5,9 +5,11 @@ use super::{
 };
 use anyhow::{anyhow, Error, Result};
 use glam::IVec2;
-use kartoffels_ui::Frame;
+use kartoffels_store::{Session, Store};
+use kartoffels_ui::{Frame, Input};
 use kartoffels_world::prelude::{BotId, Clock, CreateBotRequest};
 use std::ops::ControlFlow;
+use tracing::warn;
 
 pub enum Event {
     Copy {
@@ -47,12 +49,31 @@ pub enum Event {
         follow: bool,
     },
     LeaveBot,
+    ToggleBotInput,
+    SendToBot,
     RestartBot,
     DeleteBot,
     FollowBot,
     InspectBot {
         id: BotId,
     },
+    PauseBot {
+        id: BotId,
+    },
+    ResumeBot {
+        id: BotId,
+    },
+    StepBot {
+        id: BotId,
+    },
+    AddBreakpoint {
+        id: BotId,
+        addr: u32,
+    },
+    RemoveBreakpoint {
+        id: BotId,
+        addr: u32,
+    },
     Overclock {
         clock: Clock,
     },
@@ -61,6 +82,8 @@ pub enum Event {
 impl Event {
     pub async fn handle(
         self,
+        store: &Store,
+        sess: &Session,
         frame: &mut Frame,
         state: &mut State,
     ) -> Result<ControlFlow<(), ()>> {
@@ -165,24 +188,61 @@ impl Event {
                     let request = request.with_source(source.source());
 
                     state.modal = None;
-                    state.upload_bot(request).await?;
+                    state.upload_bot(store, sess, request).await?;
                 }
             },
 
             Event::UploadBot { request } => {
                 state.modal = None;
-                state.upload_bot(request).await?;
+                state.upload_bot(store, sess, request).await?;
             }
 
             Event::CreateBot { src, pos, follow } => {
                 state.modal = None;
-                state.create_bot(src, pos, follow).await?;
+                state.create_bot(store, sess, src, pos, follow).await?;
             }
 
             Event::LeaveBot => {
                 state.bot = None;
             }
 
+            Event::ToggleBotInput => {
+                if let Some(bot) = &mut state.bot {
+                    bot.input = match bot.input {
+                        Some(_) => None,
+                        None => Some(Input::default()),
+                    };
+                }
+            }
+
+            Event::SendToBot => {
+                let Some(bot) = &mut state.bot else {
+                    return Ok(ControlFlow::Continue(()));
+                };
+
+                let Some(input) = &mut bot.input else {
+                    return Ok(ControlFlow::Continue(()));
+                };
+
+                let msg = input.take_value();
+
+                if !msg.is_empty() {
+                    let result = state
+                        .handle
+                        .as_ref()
+                        .unwrap()
+                        .send_to_bot(bot.id, format!("{msg}\n"))
+                        .await;
+
+                    if let Err(err) = result {
+                        state.modal =
+                            Some(Box::new(Modal::Error(ErrorModal::new(
+                                err.context("couldn't send message"),
+                            ))));
+                    }
+                }
+            }
+
             Event::RestartBot => {
                 let id = state.bot.as_ref().unwrap().id;
 
@@ -207,9 +267,45 @@ impl Event {
             }
 
             Event::InspectBot { id } => {
-                state.modal = Some(Box::new(Modal::InspectBot(
-                    InspectBotModal::new(id, state.modal.take()),
-                )));
+                state.modal =
+                    Some(Box::new(Modal::InspectBot(InspectBotModal::new(
+                        id,
+                        state
+                            .config
+                            .can_debug_bots
+                            .allows(state.is_own_bot(id)),
+                        state.modal.take(),
+                    ))));
+            }
+
+            Event::PauseBot { id } => {
+                state.handle.as_ref().unwrap().pause_bot(id).await?;
+            }
+
+            Event::ResumeBot { id } => {
+                state.handle.as_ref().unwrap().resume_bot(id).await?;
+            }
+
+            Event::StepBot { id } => {
+                state.handle.as_ref().unwrap().step_bot(id).await?;
+            }
+
+            Event::AddBreakpoint { id, addr } => {
+                state
+                    .handle
+                    .as_ref()
+                    .unwrap()
+                    .add_breakpoint(id, addr)
+                    .await?;
+            }
+
+            Event::RemoveBreakpoint { id, addr } => {
+                state
+                    .handle
+                    .as_ref()
+                    .unwrap()
+                    .remove_breakpoint(id, addr)
+                    .await?;
             }
 
             Event::Overclock { clock } => {
@@ -224,6 +320,8 @@ impl Event {
 impl State {
     async fn upload_bot(
         &mut self,
+        store: &Store,
+        sess: &Session,
         request: UploadBotRequest<Vec<u8>>,
     ) -> Result<()> {
         match request.position {
@@ -238,7 +336,14 @@ impl State {
 
             BotPosition::Random => {
                 for _ in 0..request.count.get() {
-                    self.create_bot(request.source.clone(), None, true).await?;
+                    self.create_bot(
+                        store,
+                        sess,
+                        request.source.clone(),
+                        None,
+                        true,
+                    )
+                    .await?;
                 }
             }
         }
@@ -248,16 +353,32 @@ impl State {
 
     async fn create_bot(
         &mut self,
+        store: &Store,
+        sess: &Session,
         src: Vec<u8>,
         pos: Option<IVec2>,
         follow: bool,
     ) -> Result<()> {
-        let id = self
-            .handle
-            .as_ref()
-            .unwrap()
-            .create_bot(CreateBotRequest::new(src).at(pos))
-            .await;
+        if let Err(err) = store.record_upload(sess) {
+            self.modal = Some(Box::new(Modal::Error(ErrorModal::new(
+                Error::new(err).context("couldn't upload bot"),
+            ))));
+
+            return Ok(());
+        }
+
+        let world = self.handle.as_ref().unwrap();
+        let world_id = world.id();
+        let req = CreateBotRequest::new(src).at(pos).owner(self.owner.clone());
+
+        // Symbols are only useful if the player can debug this bot later
+        let req = if self.config.can_debug_bots.allows(true) {
+            req.with_symbols()
+        } else {
+            req
+        };
+
+        let id = world.create_bot(req).await;
 
         let id = match id {
             Ok(id) => id,
@@ -271,6 +392,16 @@ impl State {
             }
         };
 
+        if let Some(player) = sess.with(|sess| sess.player().cloned()) {
+            let result = store.update_player(player, |player| {
+                player.add_bot(world_id, id);
+            });
+
+            if let Err(err) = result {
+                warn!("couldn't record player's bot: {err:?}");
+            }
+        }
+
         self.join_bot(id, follow);
 
         Ok(())
@@ -114,6 +114,12 @@ impl Map {
                 bg = theme::BG;
             }
 
+            TileKind::CHARGER => {
+                ch = '%';
+                fg = theme::GREEN;
+                bg = theme::BG;
+            }
+
             TileKind::DOOR => {
                 ch = '+';
                 fg = theme::GRAY;
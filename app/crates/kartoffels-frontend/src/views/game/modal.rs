@@ -45,7 +45,7 @@ impl Modal {
 
         match self {
             Modal::Bots(this) => {
-                this.render(ui, world);
+                this.render(ui, sess, world);
             }
             Modal::Error(this) => {
                 this.render(ui);
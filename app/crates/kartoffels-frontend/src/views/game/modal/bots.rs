@@ -1,6 +1,7 @@
 use crate::views::game::Event as ParentEvent;
 use crate::BotIdExt;
 use itertools::Itertools;
+use kartoffels_store::Session;
 use kartoffels_ui::{theme, Button, KeyCode, Ui, UiWidget, VRow};
 use kartoffels_world::prelude::{AliveBotSnapshot, BotId, Snapshot};
 use ratatui::layout::{Constraint, Layout};
@@ -16,6 +17,7 @@ pub struct BotsModal {
     length: Option<usize>,
     height: usize,
     selected: Selected,
+    only_own: bool,
 }
 
 impl BotsModal {
@@ -24,10 +26,16 @@ impl BotsModal {
         BotId::LENGTH as u16 + 1, // id
         7,                        // age
         6,                        // score
-        18,                       // actions
+        22,                       // actions
     ];
 
-    pub fn render(&mut self, ui: &mut Ui<ParentEvent>, world: &Snapshot) {
+    pub fn render(
+        &mut self,
+        ui: &mut Ui<ParentEvent>,
+        sess: &Session,
+        world: &Snapshot,
+    ) {
+        let owner = sess.with(|sess| sess.owner().clone());
         let width = Self::WIDTHS.iter().copied().sum();
         let height = ui.area.height - 2;
 
@@ -41,7 +49,7 @@ impl BotsModal {
                 .areas(ui.area);
 
                 ui.clamp(body_area, |ui| {
-                    self.render_body(ui, world);
+                    self.render_body(ui, world, &owner);
                 });
 
                 ui.clamp(footer_area, |ui| {
@@ -51,13 +59,18 @@ impl BotsModal {
         });
 
         if let Some(event) = event
-            && let Some(event) = self.handle(event, world)
+            && let Some(event) = self.handle(event, world, &owner)
         {
             ui.throw(event);
         }
     }
 
-    fn render_body(&mut self, ui: &mut Ui<Event>, world: &Snapshot) {
+    fn render_body(
+        &mut self,
+        ui: &mut Ui<Event>,
+        world: &Snapshot,
+        owner: &str,
+    ) {
         let [table_area, scrollbar_area] =
             Layout::horizontal([Constraint::Fill(1), Constraint::Length(2)])
                 .areas(ui.area);
@@ -75,11 +88,8 @@ impl BotsModal {
                 // If we're tracking a bot with specific id, recalculate the
                 // offset so that this particular bot is always centered
 
-                let nth = world
-                    .bots
-                    .alive
-                    .iter_sorted_by_scores()
-                    .enumerate()
+                let nth = self
+                    .bots(world, owner)
                     .find_position(|(_, bot)| bot.id == id)
                     .map(|(nth, _)| nth);
 
@@ -103,7 +113,7 @@ impl BotsModal {
             }
         }
 
-        self.length = world.bots.alive.len().checked_sub(self.height);
+        self.length = self.bots(world, owner).count().checked_sub(self.height);
         self.offset = self.offset.min(self.length.unwrap_or(0));
 
         // ---
@@ -117,15 +127,14 @@ impl BotsModal {
         });
 
         ui.clamp(tbody_area, |ui| {
-            let rows = world
-                .bots
-                .alive
-                .iter_sorted_by_scores()
+            let rows = self
+                .bots(world, owner)
                 .enumerate()
                 .skip(self.offset)
                 .take(self.height)
-                .map(|(nth, bot)| Row {
+                .map(|(nth, (place, bot))| Row {
                     nth,
+                    place,
                     bot,
                     selected: self.selected,
                 });
@@ -186,6 +195,18 @@ impl BotsModal {
                 .enabled(self.selected.is_nth())
                 .render(ui);
 
+            ui.space(2);
+
+            if self.only_own {
+                Button::new("show-all", KeyCode::Char('m'))
+                    .throwing(Event::ShowAll)
+                    .render(ui);
+            } else {
+                Button::new("show-mine", KeyCode::Char('m'))
+                    .throwing(Event::ShowOwn)
+                    .render(ui);
+            }
+
             Button::new("close", KeyCode::Escape)
                 .throwing(Event::Parent(ParentEvent::CloseModal))
                 .right_aligned()
@@ -197,6 +218,7 @@ impl BotsModal {
         &mut self,
         event: Event,
         world: &Snapshot,
+        owner: &str,
     ) -> Option<ParentEvent> {
         match event {
             Event::ScrollUp => {
@@ -222,8 +244,10 @@ impl BotsModal {
             }
 
             Event::SelectDown => {
+                let len = self.bots(world, owner).count();
+
                 if let Selected::Nth(nth) = &mut self.selected {
-                    *nth = nth.saturating_add(1).min(world.bots.alive.len());
+                    *nth = nth.saturating_add(1).min(len);
 
                     if *nth >= self.max_visible_nth() {
                         self.offset += 1;
@@ -235,8 +259,7 @@ impl BotsModal {
 
             Event::TrackId => {
                 if let Selected::Nth(nth) = self.selected
-                    && let Some(bot) =
-                        world.bots.alive.iter_sorted_by_scores().nth(nth)
+                    && let Some((_, bot)) = self.bots(world, owner).nth(nth)
                 {
                     self.selected = Selected::Id(bot.id);
                 }
@@ -246,11 +269,8 @@ impl BotsModal {
 
             Event::TrackNth => {
                 if let Selected::Id(id) = self.selected {
-                    let nth = world
-                        .bots
-                        .alive
-                        .iter_sorted_by_scores()
-                        .enumerate()
+                    let nth = self
+                        .bots(world, owner)
                         .find_position(|(_, bot)| bot.id == id)
                         .map(|(nth, _)| nth)
                         .unwrap_or(0);
@@ -261,10 +281,36 @@ impl BotsModal {
                 None
             }
 
+            Event::ShowAll => {
+                self.only_own = false;
+                self.offset = 0;
+                None
+            }
+
+            Event::ShowOwn => {
+                self.only_own = true;
+                self.offset = 0;
+                None
+            }
+
             Event::Parent(event) => Some(event),
         }
     }
 
+    /// Returns bots sorted by their scores, together with their places on the
+    /// leaderboard, optionally narrowed down to bots uploaded by `owner`.
+    fn bots<'a>(
+        &self,
+        world: &'a Snapshot,
+        owner: &'a str,
+    ) -> impl Iterator<Item = (usize, &'a AliveBotSnapshot)> + 'a {
+        let only_own = self.only_own;
+
+        world.bots.alive.iter_sorted_by_scores().enumerate().filter(
+            move |(_, bot)| !only_own || bot.owner.as_deref() == Some(owner),
+        )
+    }
+
     fn min_visible_nth(&self) -> usize {
         self.offset
     }
@@ -281,6 +327,8 @@ enum Event {
     SelectDown,
     TrackId,
     TrackNth,
+    ShowAll,
+    ShowOwn,
     Parent(ParentEvent),
 }
 
@@ -312,6 +360,7 @@ impl Default for Selected {
 #[derive(Clone, Debug)]
 struct Row<'a> {
     nth: usize,
+    place: usize,
     bot: &'a AliveBotSnapshot,
     selected: Selected,
 }
@@ -320,7 +369,7 @@ impl UiWidget<Event> for Row<'_> {
     fn render(self, ui: &mut Ui<Event>) {
         let is_selected = self.selected.matches(self.nth, self.bot);
 
-        let nth = Span::raw(format!("#{}", self.nth + 1));
+        let nth = Span::raw(format!("#{}", self.place + 1));
         let id = Span::raw(self.bot.id.to_string()).fg(self.bot.id.color());
         let age = Span::raw(self.bot.age.time().to_string());
         let score = Span::raw(self.bot.score.to_string());
@@ -1,30 +1,53 @@
 use super::Modal;
 use crate::views::game::Event as ParentEvent;
+use itertools::Itertools;
+use kartoffels_cpu::{Cpu, CpuProfile};
 use kartoffels_ui::{theme, Button, KeyCode, Ui, UiWidget};
 use kartoffels_world::cfg;
-use kartoffels_world::prelude::{BotId, BotSnapshot, Snapshot};
+use kartoffels_world::prelude::{
+    BotDebuggerSnapshot, BotId, BotSnapshot, Snapshot,
+};
 use ordinal::Ordinal;
 use ratatui::layout::{Alignment, Constraint, Layout};
 use ratatui::style::{Style, Stylize};
 use ratatui::symbols;
+use ratatui::text::{Line, Span};
 use ratatui::widgets::{Axis, Cell, Chart, Dataset, GraphType, Row, Table};
 use std::fmt;
 
 pub struct InspectBotModal {
     id: BotId,
     tab: Tab,
+    can_debug: bool,
+    cursor: Option<u32>,
+    memory: Option<u32>,
     parent: Option<Box<Modal>>,
+    profile: Option<CpuProfile>,
 }
 
 impl InspectBotModal {
-    pub fn new(id: BotId, parent: Option<Box<Modal>>) -> Self {
+    pub fn new(id: BotId, can_debug: bool, parent: Option<Box<Modal>>) -> Self {
         Self {
             id,
             tab: Default::default(),
+            can_debug,
+            cursor: None,
+            memory: None,
             parent,
+            profile: None,
         }
     }
 
+    /// Returns id of the bot that should be profiled - profiling is active
+    /// only while the debug tab is open.
+    pub fn profiled_bot(&self) -> Option<BotId> {
+        (self.tab == Tab::Debug).then_some(self.id)
+    }
+
+    pub fn set_profile(&mut self, profile: Option<CpuProfile>) {
+        self.profile = profile;
+    }
+
     pub fn render(&mut self, ui: &mut Ui<ParentEvent>, world: &Snapshot) {
         let event = ui.catch(|ui| {
             let width = ui.area.width - 8;
@@ -67,6 +90,9 @@ impl InspectBotModal {
             Tab::Lives => {
                 self.render_body_lives(ui, world);
             }
+            Tab::Debug => {
+                self.render_body_debug(ui, world);
+            }
         }
     }
 
@@ -261,9 +287,288 @@ impl InspectBotModal {
         Table::new(rows, widths).header(header).render(ui);
     }
 
+    fn render_body_debug(&self, ui: &mut Ui<Event>, world: &Snapshot) {
+        let Some(bot) = world.bots.alive.get(self.id) else {
+            ui.line("bot is not alive, there's nothing to debug");
+            return;
+        };
+
+        // Registers, code and memory are only sent for paused bots
+        let debugger =
+            bot.debugger.as_deref().filter(|debugger| debugger.paused);
+        let paused = debugger.is_some();
+        let cursor =
+            debugger.map(|debugger| self.cursor.unwrap_or(debugger.pc));
+
+        ui.row(|ui| {
+            if paused {
+                Button::new("resume", KeyCode::Char('p'))
+                    .throwing(Event::ResumeBot)
+                    .render(ui);
+            } else {
+                Button::new("pause", KeyCode::Char('p'))
+                    .throwing(Event::PauseBot)
+                    .render(ui);
+            }
+
+            ui.space(2);
+
+            Button::new("step", KeyCode::Char('n'))
+                .throwing(Event::StepBot)
+                .enabled(paused)
+                .render(ui);
+
+            ui.space(2);
+
+            if let Some(debugger) = debugger
+                && let Some(cursor) = cursor
+            {
+                let (up, down) = Self::neighbours(debugger, cursor);
+                let enabled = !debugger.breakpoints.contains(&cursor);
+
+                Button::new("breakpoint", KeyCode::Char('b'))
+                    .throwing(Event::ToggleBreakpoint {
+                        addr: cursor,
+                        enabled,
+                    })
+                    .render(ui);
+
+                ui.space(2);
+
+                Button::multi("cursor")
+                    .throwing_on(KeyCode::UpArrow, Event::MoveCursor(up))
+                    .throwing_on(KeyCode::DownArrow, Event::MoveCursor(down))
+                    .render(ui);
+
+                ui.space(2);
+
+                let memory = self.memory(debugger);
+
+                Button::multi("memory")
+                    .throwing_on(
+                        KeyCode::Char('<'),
+                        Event::MoveMemory(memory.wrapping_sub(64)),
+                    )
+                    .throwing_on(
+                        KeyCode::Char('>'),
+                        Event::MoveMemory(memory.wrapping_add(64)),
+                    )
+                    .render(ui);
+            }
+        });
+
+        ui.space(1);
+
+        let (Some(debugger), Some(cursor)) = (debugger, cursor) else {
+            ui.line(
+                "pause the bot to inspect its registers, code and memory"
+                    .fg(theme::GRAY),
+            );
+
+            ui.space(1);
+
+            self.render_debug_profile(ui);
+
+            return;
+        };
+
+        let [regs_area, _, right_area] = Layout::horizontal([
+            Constraint::Length(28),
+            Constraint::Length(2),
+            Constraint::Fill(1),
+        ])
+        .areas(ui.area);
+
+        let [code_area, _, memory_area] = Layout::vertical([
+            Constraint::Fill(1),
+            Constraint::Length(1),
+            Constraint::Fill(1),
+        ])
+        .areas(right_area);
+
+        ui.clamp(regs_area, |ui| {
+            Self::render_debug_regs(ui, debugger);
+        });
+
+        ui.clamp(code_area, |ui| {
+            Self::render_debug_code(ui, debugger, cursor);
+        });
+
+        ui.clamp(memory_area, |ui| {
+            Self::render_debug_memory(ui, debugger, self.memory(debugger));
+        });
+    }
+
+    fn render_debug_profile(&self, ui: &mut Ui<Event>) {
+        ui.line("hot functions".underlined());
+
+        let Some(profile) = &self.profile else {
+            ui.line("collecting samples...".fg(theme::GRAY));
+            return;
+        };
+
+        let rows = profile.functions.iter().map(|fun| {
+            let share = 100.0 * (fun.samples as f32) / (profile.samples as f32);
+
+            Row::new(vec![
+                Cell::new(fun.samples.to_string()),
+                Cell::new(format!("{share:.1}%")),
+                Cell::new(fun.name.as_str()),
+            ])
+        });
+
+        let widths = vec![
+            Constraint::Length(7),
+            Constraint::Length(6),
+            Constraint::Fill(1),
+        ];
+
+        let header = Row::new(vec![
+            Cell::new("samples"),
+            Cell::new("share"),
+            Cell::new("function"),
+        ])
+        .underlined();
+
+        Table::new(rows, widths).header(header).render(ui);
+    }
+
+    fn render_debug_regs(ui: &mut Ui<Event>, debugger: &BotDebuggerSnapshot) {
+        ui.line("registers".underlined());
+        ui.line(format!("pc   {:08x}", debugger.pc));
+
+        for row in 0..16 {
+            let [lhs, rhs] = [row, row + 16].map(|idx| {
+                format!(
+                    "{:<4} {:08x}",
+                    Cpu::REG_NAMES[idx],
+                    debugger.regs[idx] as u32
+                )
+            });
+
+            ui.line(Line::from(vec![
+                Span::raw(lhs),
+                Span::raw("  "),
+                Span::raw(rhs),
+            ]));
+        }
+    }
+
+    fn render_debug_code(
+        ui: &mut Ui<Event>,
+        debugger: &BotDebuggerSnapshot,
+        cursor: u32,
+    ) {
+        ui.line("code".underlined());
+
+        // Start a couple of instructions before the cursor, so that it's
+        // visible what led to it
+        let mut addr = cursor;
+
+        for _ in 0..(ui.area.height / 3) {
+            addr = Self::neighbours(debugger, addr).0;
+        }
+
+        while ui.area.height > 0 {
+            let (size, instr) =
+                debugger.disassemble(addr).unwrap_or((2, "???".into()));
+
+            let marker = if addr == debugger.pc { ">" } else { " " };
+
+            let breakpoint = if debugger.breakpoints.contains(&addr) {
+                "●".fg(theme::RED)
+            } else {
+                " ".into()
+            };
+
+            let mut line = Line::from(vec![
+                Span::raw(marker),
+                breakpoint,
+                Span::raw(format!(" {addr:08x}  ")),
+                Span::raw(instr),
+            ]);
+
+            if addr == cursor {
+                line = line.fg(theme::BG).bg(theme::YELLOW);
+            } else if addr == debugger.pc {
+                line = line.fg(theme::YELLOW);
+            }
+
+            ui.line(line);
+
+            addr = addr.wrapping_add(size);
+        }
+    }
+
+    fn render_debug_memory(
+        ui: &mut Ui<Event>,
+        debugger: &BotDebuggerSnapshot,
+        memory: u32,
+    ) {
+        ui.line("memory".underlined());
+
+        let mut addr = memory;
+
+        while ui.area.height > 0 {
+            let bytes: Vec<_> =
+                (0..8).map(|offset| debugger.load(addr + offset)).collect();
+
+            let hex = bytes
+                .iter()
+                .map(|byte| match byte {
+                    Some(byte) => format!("{byte:02x}"),
+                    None => "--".into(),
+                })
+                .join(" ");
+
+            let ascii: String = bytes
+                .iter()
+                .map(|byte| match byte {
+                    Some(byte) if byte.is_ascii_graphic() => *byte as char,
+                    _ => '.',
+                })
+                .collect();
+
+            ui.line(Line::from(vec![
+                Span::raw(format!("{addr:08x}  {hex}  ")),
+                Span::raw(ascii).fg(theme::GRAY),
+            ]));
+
+            addr = addr.wrapping_add(8);
+        }
+    }
+
+    /// Returns address of the memory view - unless chosen by the user, it
+    /// follows the stack pointer.
+    fn memory(&self, debugger: &BotDebuggerSnapshot) -> u32 {
+        self.memory.unwrap_or((debugger.regs[2] as u32) & !7)
+    }
+
+    /// Returns addresses of the instructions surrounding given one.
+    ///
+    /// Since instructions can be either two or four bytes long, the previous
+    /// instruction's address is just a guess - we assume it's a four-byte one
+    /// if the bytes there look like one.
+    fn neighbours(debugger: &BotDebuggerSnapshot, addr: u32) -> (u32, u32) {
+        let prev = match debugger.disassemble(addr.wrapping_sub(4)) {
+            Some((4, _)) => addr.wrapping_sub(4),
+            _ => addr.wrapping_sub(2),
+        };
+
+        let next = match debugger.disassemble(addr) {
+            Some((size, _)) => addr.wrapping_add(size),
+            None => addr.wrapping_add(2),
+        };
+
+        (prev, next)
+    }
+
     fn render_footer(&self, ui: &mut Ui<Event>) {
         ui.row(|ui| {
-            for (idx, tab) in Tab::all().enumerate() {
+            let tabs =
+                Tab::all().filter(|tab| *tab != Tab::Debug || self.can_debug);
+
+            for (idx, tab) in tabs.enumerate() {
                 if idx > 0 {
                     ui.span(" • ");
                 }
@@ -298,11 +603,44 @@ impl InspectBotModal {
         match event {
             Event::ChangeTab(tab) => {
                 self.tab = tab;
+                self.profile = None;
                 None
             }
 
             Event::JoinBot => Some(ParentEvent::JoinBot { id: self.id }),
 
+            Event::PauseBot => Some(ParentEvent::PauseBot { id: self.id }),
+
+            Event::ResumeBot => {
+                self.cursor = None;
+
+                Some(ParentEvent::ResumeBot { id: self.id })
+            }
+
+            Event::StepBot => {
+                self.cursor = None;
+
+                Some(ParentEvent::StepBot { id: self.id })
+            }
+
+            Event::ToggleBreakpoint { addr, enabled } => {
+                if enabled {
+                    Some(ParentEvent::AddBreakpoint { id: self.id, addr })
+                } else {
+                    Some(ParentEvent::RemoveBreakpoint { id: self.id, addr })
+                }
+            }
+
+            Event::MoveCursor(addr) => {
+                self.cursor = Some(addr);
+                None
+            }
+
+            Event::MoveMemory(addr) => {
+                self.memory = Some(addr);
+                None
+            }
+
             Event::GoBack => {
                 if let Some(modal) = self.parent.take() {
                     Some(ParentEvent::OpenModal { modal })
@@ -319,6 +657,12 @@ enum Event {
     ChangeTab(Tab),
     JoinBot,
     GoBack,
+    PauseBot,
+    ResumeBot,
+    StepBot,
+    ToggleBreakpoint { addr: u32, enabled: bool },
+    MoveCursor(u32),
+    MoveMemory(u32),
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
@@ -327,11 +671,12 @@ enum Tab {
     Stats,
     Events,
     Lives,
+    Debug,
 }
 
 impl Tab {
     fn all() -> impl Iterator<Item = Self> {
-        [Self::Stats, Self::Events, Self::Lives].into_iter()
+        [Self::Stats, Self::Events, Self::Lives, Self::Debug].into_iter()
     }
 
     fn btn(&self) -> Button<Event> {
@@ -339,6 +684,7 @@ impl Tab {
             Tab::Stats => Button::new("stats", KeyCode::Char('s')),
             Tab::Events => Button::new("events", KeyCode::Char('e')),
             Tab::Lives => Button::new("lives", KeyCode::Char('l')),
+            Tab::Debug => Button::new("debug", KeyCode::Char('d')),
         };
 
         btn.throwing(Event::ChangeTab(*self))
@@ -351,6 +697,7 @@ impl fmt::Display for Tab {
             Self::Stats => write!(f, "stats"),
             Self::Events => write!(f, "events"),
             Self::Lives => write!(f, "lives"),
+            Self::Debug => write!(f, "debug"),
         }
     }
 }
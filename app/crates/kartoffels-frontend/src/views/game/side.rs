@@ -12,13 +12,16 @@ pub struct SidePanel;
 impl SidePanel {
     pub const WIDTH: u16 = 26;
 
-    pub fn render(ui: &mut Ui<Event>, state: &State) {
+    pub fn render(ui: &mut Ui<Event>, state: &mut State) {
         ui.area.x += 1;
         ui.area.width -= 1;
 
         ui.enable(state.handle.is_some(), |ui| {
-            if let Some(bot) = &state.bot {
-                JoinedSidePanel::render(ui, state, bot);
+            // Joined bot's input box needs mutable access, so we temporarily
+            // move the bot out of the state
+            if let Some(mut bot) = state.bot.take() {
+                JoinedSidePanel::render(ui, state, &mut bot);
+                state.bot = Some(bot);
             } else {
                 IdleSidePanel::render(ui, state);
             }
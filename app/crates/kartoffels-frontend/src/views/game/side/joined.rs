@@ -1,11 +1,11 @@
 use crate::views::game::{Event, JoinedBot, State};
 use crate::BotIdExt;
-use kartoffels_ui::{theme, Button, KeyCode, Ui, UiWidget};
+use kartoffels_ui::{theme, Button, Input, KeyCode, Ui, UiWidget, VirtualTerm};
 use kartoffels_world::prelude::{
-    AliveBotSnapshot, BotSnapshot, DeadBotSnapshot, QueuedBotSnapshot,
+    AliveBotSnapshot, BotId, BotSnapshot, DeadBotSnapshot, QueuedBotSnapshot,
 };
 use ordinal::Ordinal;
-use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::layout::{Constraint, Layout};
 use ratatui::style::Stylize;
 use std::collections::VecDeque;
 
@@ -13,8 +13,28 @@ use std::collections::VecDeque;
 pub struct JoinedSidePanel;
 
 impl JoinedSidePanel {
-    pub fn render(ui: &mut Ui<Event>, state: &State, jbot: &JoinedBot) {
+    pub fn render(ui: &mut Ui<Event>, state: &State, jbot: &mut JoinedBot) {
         let bot = state.snapshot.bots.get(jbot.id);
+
+        if let Some(input) = &mut jbot.input {
+            let [bot_area, _, input_area] = Layout::vertical([
+                Constraint::Fill(1),
+                Constraint::Length(1),
+                Constraint::Length(3),
+            ])
+            .areas(ui.area);
+
+            ui.clamp(bot_area, |ui| {
+                Self::render_bot(ui, jbot.id, bot);
+            });
+
+            ui.clamp(input_area, |ui| {
+                Self::render_input(ui, input);
+            });
+
+            return;
+        }
+
         let btns = Self::btns(state, jbot);
 
         let [bot_area, _, btns_area] = Layout::vertical([
@@ -25,7 +45,7 @@ impl JoinedSidePanel {
         .areas(ui.area);
 
         ui.clamp(bot_area, |ui| {
-            Self::render_bot(ui, jbot, bot);
+            Self::render_bot(ui, jbot.id, bot);
         });
 
         ui.clamp(btns_area, |ui| {
@@ -35,13 +55,25 @@ impl JoinedSidePanel {
         });
     }
 
-    fn render_bot(
-        ui: &mut Ui<Event>,
-        jbot: &JoinedBot,
-        bot: Option<BotSnapshot>,
-    ) {
+    fn render_input(ui: &mut Ui<Event>, input: &mut Input) {
+        ui.line("message".underlined());
+        ui.add(input);
+
+        ui.row(|ui| {
+            Button::new("cancel", KeyCode::Escape)
+                .throwing(Event::ToggleBotInput)
+                .render(ui);
+
+            Button::new("send", KeyCode::Enter)
+                .throwing(Event::SendToBot)
+                .right_aligned()
+                .render(ui);
+        });
+    }
+
+    fn render_bot(ui: &mut Ui<Event>, id: BotId, bot: Option<BotSnapshot>) {
         ui.line("id".underlined());
-        ui.line(jbot.id.to_string().fg(jbot.id.color()));
+        ui.line(id.to_string().fg(id.color()));
         ui.space(1);
 
         match bot {
@@ -96,12 +128,14 @@ impl JoinedSidePanel {
     fn render_bot_serial(ui: &mut Ui<Event>, serial: &VecDeque<u32>) {
         ui.line("serial port".underlined());
 
-        let serial = render_serial(serial);
-        let serial = reflow_serial(&serial, ui.area);
+        // TODO this should be done by BotSerial and memoized
+        let serial: String =
+            serial.iter().copied().filter_map(char::from_u32).collect();
 
-        for line in serial {
-            ui.line(line);
-        }
+        let mut term = VirtualTerm::new(ui.area.width, ui.area.height);
+
+        term.write(&serial);
+        ui.add(&term);
     }
 
     fn btns(state: &State, bot: &JoinedBot) -> Vec<Button<'static, Event>> {
@@ -122,7 +156,23 @@ impl JoinedSidePanel {
             Button::new(label, KeyCode::Char('f')).throwing(Event::FollowBot)
         });
 
-        if state.config.can_restart_bots {
+        if state
+            .config
+            .can_send_to_bots
+            .allows(state.is_own_bot(bot.id))
+            && state.snapshot.bots.alive.get(bot.id).is_some()
+        {
+            btns.push(
+                Button::new("message-bot", KeyCode::Char('m'))
+                    .throwing(Event::ToggleBotInput),
+            );
+        }
+
+        if state
+            .config
+            .can_restart_bots
+            .allows(state.is_own_bot(bot.id))
+        {
             btns.push(
                 Button::new("restart-bot", KeyCode::Char('R'))
                     .throwing(Event::RestartBot)
@@ -130,7 +180,11 @@ impl JoinedSidePanel {
             );
         }
 
-        if state.config.can_delete_bots {
+        if state
+            .config
+            .can_delete_bots
+            .allows(state.is_own_bot(bot.id))
+        {
             btns.push(
                 Button::new("delete-bot", KeyCode::Char('D'))
                     .throwing(Event::DeleteBot)
@@ -148,47 +202,3 @@ impl JoinedSidePanel {
         btns
     }
 }
-
-// TODO this should be done by BotSerial and memoized
-fn render_serial(serial: &VecDeque<u32>) -> String {
-    serial.iter().copied().filter_map(char::from_u32).collect()
-}
-
-// TODO this should be done by BotSerial and memoized
-fn reflow_serial(serial: &str, area: Rect) -> VecDeque<&str> {
-    let mut lines = VecDeque::with_capacity(area.height as usize);
-
-    let mut line_start = 0;
-    let mut line_chars = 0;
-
-    // TODO we should iterate through graphemes here
-    for (ch_idx, ch) in serial.char_indices() {
-        if ch == '\n' || line_chars == area.width {
-            if lines.len() == lines.capacity() {
-                lines.pop_front();
-            }
-
-            lines.push_back(&serial[line_start..ch_idx]);
-
-            line_start = ch_idx;
-            line_chars = 0;
-
-            if ch == '\n' {
-                line_start += 1;
-                continue;
-            }
-        }
-
-        line_chars += 1;
-    }
-
-    if line_chars > 0 {
-        if lines.len() == lines.capacity() {
-            lines.pop_front();
-        }
-
-        lines.push_back(&serial[line_start..]);
-    }
-
-    lines
-}
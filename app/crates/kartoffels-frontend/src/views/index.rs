@@ -1,5 +1,6 @@
 mod challenges;
 mod console;
+mod leaderboard;
 mod play;
 mod sandbox;
 mod tutorial;
@@ -22,7 +23,7 @@ pub async fn run(
     let mut fade_in = true;
 
     loop {
-        match run_once(store, frame, bg, fade_in).await? {
+        match run_once(store, sess, frame, bg, fade_in).await? {
             Event::Admin => {
                 console::run(store, sess, frame, bg).await?;
                 fade_in = false;
@@ -48,6 +49,11 @@ pub async fn run(
                 fade_in = false;
             }
 
+            Event::Leaderboard => {
+                leaderboard::run(store, frame, bg).await?;
+                fade_in = false;
+            }
+
             Event::Quit => {
                 return Ok(());
             }
@@ -57,6 +63,7 @@ pub async fn run(
 
 async fn run_once(
     store: &Store,
+    sess: &Session,
     frame: &mut Frame,
     bg: &Background,
     fade_in: bool,
@@ -71,6 +78,8 @@ async fn run_once(
         let event = frame
             .update(|ui| {
                 fade.render(ui, |ui| {
+                    let can_play = !play::worlds(store, sess).is_empty();
+
                     let [_, area, _] = Layout::horizontal([
                         Constraint::Fill(1),
                         Constraint::Length(Header::width()),
@@ -82,7 +91,7 @@ async fn run_once(
                         Constraint::Fill(1),
                         Constraint::Length(Header::height()),
                         Constraint::Fill(1),
-                        Constraint::Length(Menu::height(store, ui)),
+                        Constraint::Length(Menu::height(can_play, ui)),
                         Constraint::Fill(1),
                     ])
                     .areas(area);
@@ -101,7 +110,7 @@ async fn run_once(
                     });
 
                     ui.clamp(menu_area, |ui| {
-                        Menu::render(store, ui);
+                        Menu::render(can_play, ui);
                     });
 
                     Footer::render(store, ui);
@@ -126,6 +135,7 @@ enum Event {
     Sandbox,
     Tutorial,
     Challenges,
+    Leaderboard,
     Quit,
 }
 
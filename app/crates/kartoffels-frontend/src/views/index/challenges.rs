@@ -22,6 +22,8 @@ pub async fn run(
     loop {
         match run_once(store, frame, bg, fade_in).await? {
             Event::Play(challenge) => {
+                store.record_private_world(sess)?;
+
                 game::run(store, sess, frame, |game| {
                     (challenge.run)(store, game)
                 })
@@ -2,7 +2,7 @@ mod acyclic_maze;
 mod diamond_heist;
 mod personal_roomba;
 
-use crate::views::game::{Config, GameCtrl};
+use crate::views::game::{BotAccess, Config, GameCtrl};
 use anyhow::Result;
 use futures_util::future::BoxFuture;
 use kartoffels_store::Store;
@@ -13,11 +13,13 @@ const CONFIG: Config = Config {
     hero_mode: true,
     sync_pause: true,
 
-    can_delete_bots: true,
+    can_debug_bots: BotAccess::None,
+    can_delete_bots: BotAccess::All,
     can_join_bots: false,
     can_overclock: true,
     can_pause: true,
-    can_restart_bots: false,
+    can_restart_bots: BotAccess::None,
+    can_send_to_bots: BotAccess::None,
     can_spawn_bots: false,
     can_upload_bots: true,
 };
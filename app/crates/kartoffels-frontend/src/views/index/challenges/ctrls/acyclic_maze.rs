@@ -104,14 +104,17 @@ async fn init(store: &Store, game: &GameCtrl) -> Result<(Handle, BotId)> {
     game.set_config(CONFIG.disabled()).await?;
     game.set_status(Some("building".into())).await?;
 
-    let world = store.create_private_world(Config {
-        policy: Policy {
-            auto_respawn: false,
-            max_alive_bots: 2,
-            max_queued_bots: 1,
-        },
-        ..store.world_config("challenge:acyclic-maze")
-    })?;
+    let world = store
+        .create_private_world(Config {
+            policy: Policy {
+                auto_respawn: false,
+                max_alive_bots: 2,
+                max_queued_bots: 1,
+                ..Default::default()
+            },
+            ..store.world_config("challenge:acyclic-maze")
+        })
+        .await?;
 
     world
         .set_map({
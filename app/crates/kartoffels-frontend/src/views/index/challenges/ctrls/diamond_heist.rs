@@ -127,14 +127,17 @@ async fn init(store: &Store, game: &GameCtrl) -> Result<(Handle, IVec2)> {
     game.set_config(CONFIG).await?;
     game.set_status(Some("building".into())).await?;
 
-    let world = store.create_private_world(Config {
-        policy: Policy {
-            auto_respawn: false,
-            max_alive_bots: 16,
-            max_queued_bots: 16,
-        },
-        ..store.world_config("challenge:diamond-heist")
-    })?;
+    let world = store
+        .create_private_world(Config {
+            policy: Policy {
+                auto_respawn: false,
+                max_alive_bots: 16,
+                max_queued_bots: 16,
+                ..Default::default()
+            },
+            ..store.world_config("challenge:diamond-heist")
+        })
+        .await?;
 
     game.join(world.clone()).await?;
 
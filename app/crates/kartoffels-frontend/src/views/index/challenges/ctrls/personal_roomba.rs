@@ -106,14 +106,17 @@ async fn init(
     game.set_config(CONFIG.disabled()).await?;
     game.set_status(Some("building".into())).await?;
 
-    let world = store.create_private_world(Config {
-        policy: Policy {
-            auto_respawn: false,
-            max_alive_bots: 1,
-            max_queued_bots: 1,
-        },
-        ..store.world_config("challenge:personal-roomba")
-    })?;
+    let world = store
+        .create_private_world(Config {
+            policy: Policy {
+                auto_respawn: false,
+                max_alive_bots: 1,
+                max_queued_bots: 1,
+                ..Default::default()
+            },
+            ..store.world_config("challenge:personal-roomba")
+        })
+        .await?;
 
     game.join(world.clone()).await?;
 
@@ -1,12 +1,18 @@
 mod create_world;
 mod delete_world;
+mod grant_admin;
+mod list_players;
 mod list_worlds;
 mod rename_world;
+mod revoke_admin;
 
 pub use self::create_world::*;
 pub use self::delete_world::*;
+pub use self::grant_admin::*;
+pub use self::list_players::*;
 pub use self::list_worlds::*;
 pub use self::rename_world::*;
+pub use self::revoke_admin::*;
 use anyhow::{anyhow, Result};
 use clap::Parser;
 use kartoffels_store::{Session, Store};
@@ -20,6 +26,10 @@ pub enum Cmd {
     ListWorlds(ListWorldsCmd),
     RenameWorld(RenameWorldCmd),
 
+    GrantAdmin(GrantAdminCmd),
+    ListPlayers(ListPlayersCmd),
+    RevokeAdmin(RevokeAdminCmd),
+
     Exit,
 }
 
@@ -35,11 +45,15 @@ impl Cmd {
         }
 
         match self {
-            Cmd::CreateWorld(cmd) => cmd.run(store, term)?,
+            Cmd::CreateWorld(cmd) => cmd.run(store, term).await?,
             Cmd::DeleteWorld(cmd) => cmd.run(store).await?,
             Cmd::ListWorlds(cmd) => cmd.run(store, term)?,
             Cmd::RenameWorld(cmd) => cmd.run(store).await?,
 
+            Cmd::GrantAdmin(cmd) => cmd.run(store)?,
+            Cmd::ListPlayers(cmd) => cmd.run(store, term)?,
+            Cmd::RevokeAdmin(cmd) => cmd.run(store)?,
+
             Cmd::Exit => {
                 return Ok(ControlFlow::Break(()));
             }
@@ -1,6 +1,6 @@
-use anyhow::{Context, Result};
+use anyhow::{anyhow, Context, Result};
 use clap::Parser;
-use kartoffels_store::Store;
+use kartoffels_store::{PlayerId, Store};
 use kartoffels_ui::Term;
 use kartoffels_world::prelude::{Config, Policy, Theme};
 use std::fmt::Write;
@@ -13,12 +13,27 @@ pub struct CreateWorldCmd {
     #[clap(long, default_value = "")]
     policy: String,
 
+    /// World's theme, e.g. `arena:radius=20`, `cave:width=64,height=32` or
+    /// `file:path=maps/duel.txt`
     #[clap(long)]
     theme: String,
+
+    /// Creates a private world, i.e. one that's not listed on the index page
+    #[clap(long)]
+    private: bool,
+
+    /// Makes given player the owner of this (private) world, i.e. it will be
+    /// listed for them on the play page
+    #[clap(long, requires = "private")]
+    owner: Option<PlayerId>,
 }
 
 impl CreateWorldCmd {
-    pub(super) fn run(self, store: &Store, term: &mut Term) -> Result<()> {
+    pub(super) async fn run(
+        self,
+        store: &Store,
+        term: &mut Term,
+    ) -> Result<()> {
         let policy = Policy::from_str(&self.policy).with_context(|| {
             format!("couldn't parse policy: {}", self.policy)
         })?;
@@ -26,12 +41,32 @@ impl CreateWorldCmd {
         let theme = Theme::from_str(&self.theme)
             .with_context(|| format!("couldn't parse theme: {}", self.theme))?;
 
-        let world = store.create_public_world(Config {
+        let config = Config {
             name: self.name,
             policy,
             theme: Some(theme),
             ..Default::default()
-        })?;
+        };
+
+        if let Some(owner) = &self.owner
+            && store.player(owner).is_none()
+        {
+            return Err(anyhow!("couldn't find player `{owner}`"));
+        }
+
+        let world = if self.private {
+            store
+                .create_persistent_private_world(self.owner.clone(), config)
+                .await?
+        } else {
+            store.create_public_world(config).await?
+        };
+
+        if let Some(owner) = self.owner {
+            store.update_player(owner, |player| {
+                player.add_world(world.id());
+            })?;
+        }
 
         writeln!(term, "id: {}", world.id())?;
 
@@ -0,0 +1,18 @@
+use anyhow::Result;
+use clap::Parser;
+use kartoffels_store::{PlayerId, Store};
+
+#[derive(Debug, Parser)]
+pub struct GrantAdminCmd {
+    player: PlayerId,
+}
+
+impl GrantAdminCmd {
+    pub(super) fn run(self, store: &Store) -> Result<()> {
+        store.update_player(self.player, |player| {
+            player.admin = true;
+        })?;
+
+        Ok(())
+    }
+}
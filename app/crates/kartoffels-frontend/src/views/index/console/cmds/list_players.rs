@@ -0,0 +1,27 @@
+use anyhow::Result;
+use clap::Parser;
+use kartoffels_store::Store;
+use kartoffels_ui::Term;
+use std::fmt::Write;
+
+#[derive(Debug, Parser)]
+pub struct ListPlayersCmd;
+
+impl ListPlayersCmd {
+    pub(super) fn run(self, store: &Store, term: &mut Term) -> Result<()> {
+        for player in store.players() {
+            let role = if player.admin { "admin" } else { "user" };
+
+            writeln!(
+                term,
+                "{} | {role} | {} sessions | {} bots | {} worlds",
+                player.id,
+                player.stats.sessions,
+                player.stats.bots_uploaded,
+                player.worlds.len(),
+            )?;
+        }
+
+        Ok(())
+    }
+}
@@ -13,7 +13,13 @@ pub struct ListWorldsCmd {
 impl ListWorldsCmd {
     pub(super) fn run(self, store: &Store, term: &mut Term) -> Result<()> {
         for (ty, handle) in store.worlds(self.ty) {
-            writeln!(term, "{} | {} | {ty}", handle.id(), handle.name())?;
+            write!(term, "{} | {} | {ty}", handle.id(), handle.name())?;
+
+            if let Some(owner) = store.world_owner(handle.id()) {
+                write!(term, " | {owner}")?;
+            }
+
+            writeln!(term)?;
         }
 
         Ok(())
@@ -0,0 +1,18 @@
+use anyhow::Result;
+use clap::Parser;
+use kartoffels_store::{PlayerId, Store};
+
+#[derive(Debug, Parser)]
+pub struct RevokeAdminCmd {
+    player: PlayerId,
+}
+
+impl RevokeAdminCmd {
+    pub(super) fn run(self, store: &Store) -> Result<()> {
+        store.update_player(self.player, |player| {
+            player.admin = false;
+        })?;
+
+        Ok(())
+    }
+}
@@ -45,7 +45,11 @@ pub async fn run(
         if let Some(event) = event {
             match event {
                 InnerEvent::AttemptLogin(secret) => {
-                    return if Some(secret.as_str()) == store.secret() {
+                    let matches = store
+                        .secret()
+                        .is_some_and(|ours| ours.matches(secret.as_str()));
+
+                    return if matches {
                         info!("console login attempt succeeded");
 
                         Ok(Event::LoggedIn)
@@ -0,0 +1,122 @@
+use crate::Background;
+use anyhow::Result;
+use kartoffels_store::{Leaderboard, Store};
+use kartoffels_ui::{
+    theme, Button, FadeCtrl, FadeCtrlEvent, Frame, KeyCode, UiWidget,
+};
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use tracing::debug;
+
+pub async fn run(
+    store: &Store,
+    frame: &mut Frame,
+    bg: &Background,
+) -> Result<()> {
+    debug!("run()");
+
+    let lines = lines(&store.leaderboard().await);
+
+    let mut go_back_btn =
+        Button::new("go-back", KeyCode::Escape).throwing(Event::GoBack);
+
+    let width = lines
+        .iter()
+        .map(|line| line.width() as u16)
+        .chain([go_back_btn.width()])
+        .max()
+        .unwrap();
+
+    let height = lines.len() as u16 + 2;
+
+    let mut fade = FadeCtrl::default().animate(!store.testing());
+
+    loop {
+        let event = frame
+            .update(|ui| {
+                fade.render(ui, |ui| {
+                    bg.render(ui);
+
+                    ui.info_window(
+                        width,
+                        height,
+                        Some(" leaderboard "),
+                        |ui| {
+                            for line in &lines {
+                                ui.line(line.clone());
+                            }
+
+                            ui.space(1);
+                            ui.add(&mut go_back_btn);
+                        },
+                    );
+                });
+            })
+            .await?;
+
+        if let Some(Event::GoBack) = event {
+            return Ok(());
+        }
+    }
+}
+
+fn lines(leaderboard: &Leaderboard) -> Vec<Line<'static>> {
+    if leaderboard.recent.is_empty() {
+        return vec![Line::raw("no rounds have been played yet")];
+    }
+
+    let mut lines = Vec::new();
+
+    lines.push(Line::raw("recent rounds").fg(theme::GREEN).bold());
+
+    for round in &leaderboard.recent {
+        let winner = match round.winner {
+            Some(winner) => {
+                let score = round
+                    .bots
+                    .iter()
+                    .find(|bot| bot.id == winner)
+                    .map_or(0, |bot| bot.score);
+
+                format!("{winner} won with {score} pts")
+            }
+            None => "no winner".into(),
+        };
+
+        lines.push(Line::raw(format!(
+            "{} ({}) #{}: {}",
+            round.world,
+            round.ended_at.format("%Y-%m-%d %H:%M"),
+            round.idx + 1,
+            winner,
+        )));
+    }
+
+    lines.push(Line::default());
+    lines.push(Line::raw("all-time rankings").fg(theme::GREEN).bold());
+
+    for (idx, ranking) in leaderboard.rankings.iter().take(10).enumerate() {
+        lines.push(Line::raw(format!(
+            "{:>2}. {}: {} wins in {} rounds, {} pts, {} kills",
+            idx + 1,
+            ranking.bot,
+            ranking.wins,
+            ranking.rounds,
+            ranking.score,
+            ranking.kills,
+        )));
+    }
+
+    lines
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Event {
+    GoBack,
+}
+
+impl FadeCtrlEvent for Event {
+    fn needs_fade_out(&self) -> bool {
+        false
+    }
+}
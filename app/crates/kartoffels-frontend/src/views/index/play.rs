@@ -20,7 +20,7 @@ pub async fn run(
     let mut fade_in = false;
 
     loop {
-        match run_once(store, frame, bg, fade_in).await? {
+        match run_once(store, sess, frame, bg, fade_in).await? {
             Event::Play(world) => {
                 game::run(store, sess, frame, |game| {
                     ctrl::run(sess, world, game)
@@ -37,15 +37,28 @@ pub async fn run(
     }
 }
 
+/// Returns worlds available to given session - that's all of the public worlds
+/// plus, for players, their own private worlds.
+pub fn worlds(store: &Store, sess: &Session) -> Vec<WorldHandle> {
+    let mut worlds = store.public_worlds().to_vec();
+
+    if let Some(player) = sess.with(|sess| sess.player().cloned()) {
+        worlds.extend(store.player_worlds(&player));
+    }
+
+    worlds
+}
+
 async fn run_once(
     store: &Store,
+    sess: &Session,
     frame: &mut Frame,
     bg: &Background,
     fade_in: bool,
 ) -> Result<Event> {
     debug!("run()");
 
-    let worlds = store.public_worlds();
+    let worlds = worlds(store, sess);
 
     if worlds.is_empty() {
         return Ok(Event::GoBack);
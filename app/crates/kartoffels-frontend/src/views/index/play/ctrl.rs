@@ -1,4 +1,4 @@
-use crate::views::game::{Config, GameCtrl, HelpMsg, HelpMsgEvent};
+use crate::views::game::{BotAccess, Config, GameCtrl, HelpMsg, HelpMsgEvent};
 use anyhow::Result;
 use kartoffels_store::Session;
 use kartoffels_ui::{KeyCode, Msg, MsgButton, MsgLine};
@@ -69,15 +69,26 @@ pub async fn run(sess: &Session, world: Handle, game: GameCtrl) -> Result<()> {
             hero_mode: false,
             sync_pause: true,
 
-            can_delete_bots: true,
+            can_debug_bots: BotAccess::All,
+            can_delete_bots: BotAccess::All,
             can_join_bots: true,
             can_overclock: false,
             can_pause: true,
-            can_restart_bots: true,
+            can_restart_bots: BotAccess::All,
+            can_send_to_bots: BotAccess::All,
             can_spawn_bots: true,
             can_upload_bots: true,
         })
         .await?;
+    } else {
+        game.set_config(Config {
+            can_debug_bots: BotAccess::Own,
+            can_delete_bots: BotAccess::Own,
+            can_restart_bots: BotAccess::Own,
+            can_send_to_bots: BotAccess::Own,
+            ..Default::default()
+        })
+        .await?;
     }
 
     future::pending().await
@@ -12,7 +12,9 @@ use kartoffels_store::{Session, Store};
 use kartoffels_ui::{
     Button, FadeCtrl, FadeCtrlEvent, Frame, KeyCode, Ui, UiWidget,
 };
-use kartoffels_world::prelude::{ArenaTheme, CaveTheme, Theme};
+use kartoffels_world::prelude::{
+    ArenaTheme, CaveTheme, DungeonTheme, FileTheme, MazeTheme, Theme,
+};
 use tracing::debug;
 
 pub async fn run(
@@ -27,11 +29,15 @@ pub async fn run(
     // when user goes back to the form, for convenience
     let mut size = SandboxSize::Medium;
     let mut theme = SandboxTheme::Cave;
+    let maps = store.maps();
 
     loop {
         if let Some(theme) =
-            run_once(store, frame, bg, fade_in, &mut size, &mut theme).await?
+            run_once(store, frame, bg, fade_in, maps, &mut size, &mut theme)
+                .await?
         {
+            store.record_private_world(sess)?;
+
             game::run(store, sess, frame, |game| ctrl::run(store, theme, game))
                 .await?;
 
@@ -47,6 +53,7 @@ async fn run_once(
     frame: &mut Frame,
     bg: &Background,
     fade_in: bool,
+    maps: &[FileTheme],
     size: &mut SandboxSize,
     theme: &mut SandboxTheme,
 ) -> Result<Option<Theme>> {
@@ -58,6 +65,7 @@ async fn run_once(
 
     let mut form = Form {
         focus: None,
+        maps,
         size,
         theme,
     };
@@ -107,6 +115,7 @@ async fn run_once(
 #[derive(Debug)]
 struct Form<'a> {
     focus: Option<Focus>,
+    maps: &'a [FileTheme],
     size: &'a mut SandboxSize,
     theme: &'a mut SandboxTheme,
 }
@@ -134,7 +143,7 @@ impl Form<'_> {
     fn height(&self) -> u16 {
         match &self.focus {
             Some(Focus::SandboxSize) => SandboxSize::height() + 2,
-            Some(Focus::SandboxTheme) => SandboxTheme::height() + 2,
+            Some(Focus::SandboxTheme) => SandboxTheme::height(self.maps) + 2,
             None => 4,
         }
     }
@@ -145,7 +154,7 @@ impl Form<'_> {
                 SandboxSize::render_choice(ui);
             }
             Some(Focus::SandboxTheme) => {
-                SandboxTheme::render_choice(ui);
+                SandboxTheme::render_choice(ui, self.maps);
             }
             None => {
                 SandboxSize::render_focus(ui, self.size);
@@ -194,6 +203,32 @@ impl Form<'_> {
 
                 Theme::Cave(CaveTheme::new(size))
             }
+
+            SandboxTheme::Dungeon => {
+                let size = match self.size {
+                    SandboxSize::Tiny => uvec2(16, 8),
+                    SandboxSize::Small => uvec2(32, 16),
+                    SandboxSize::Medium => uvec2(64, 32),
+                    SandboxSize::Large => uvec2(128, 64),
+                };
+
+                Theme::Dungeon(DungeonTheme::new(size))
+            }
+
+            SandboxTheme::Maze => {
+                // Maze's size is expressed in cells, which are twice as large
+                // as tiles
+                let size = match self.size {
+                    SandboxSize::Tiny => uvec2(8, 4),
+                    SandboxSize::Small => uvec2(12, 8),
+                    SandboxSize::Medium => uvec2(32, 16),
+                    SandboxSize::Large => uvec2(64, 32),
+                };
+
+                Theme::Maze(MazeTheme::new(size))
+            }
+
+            SandboxTheme::File(map) => Theme::File(map.clone()),
         }
     }
 }
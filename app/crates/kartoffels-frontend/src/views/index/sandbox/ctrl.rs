@@ -1,5 +1,5 @@
 use crate::utils;
-use crate::views::game::{Config, GameCtrl, HelpMsg, HelpMsgEvent};
+use crate::views::game::{BotAccess, Config, GameCtrl, HelpMsg, HelpMsgEvent};
 use anyhow::Result;
 use kartoffels_store::Store;
 use kartoffels_ui::{Msg, MsgLine};
@@ -45,11 +45,13 @@ const CONFIG: Config = Config {
     hero_mode: false,
     sync_pause: true,
 
-    can_delete_bots: true,
+    can_debug_bots: BotAccess::All,
+    can_delete_bots: BotAccess::All,
     can_join_bots: true,
     can_overclock: false,
     can_pause: true,
-    can_restart_bots: true,
+    can_restart_bots: BotAccess::All,
+    can_send_to_bots: BotAccess::All,
     can_spawn_bots: true,
     can_upload_bots: true,
 };
@@ -68,15 +70,38 @@ async fn init(store: &Store, theme: Theme, game: &GameCtrl) -> Result<()> {
     game.set_config(CONFIG.disabled()).await?;
     game.set_status(Some("building".into())).await?;
 
-    let world = store.create_private_world(WorldConfig {
-        name: "sandbox".into(),
-        policy: Policy {
-            auto_respawn: true,
-            max_alive_bots: MAX_BOTS,
-            max_queued_bots: MAX_BOTS,
-        },
+    let policy = Policy {
+        auto_respawn: true,
+        max_alive_bots: MAX_BOTS,
+        max_queued_bots: MAX_BOTS,
         ..Default::default()
-    })?;
+    };
+
+    // Custom maps carry policy overrides, spawn points and objects, which get
+    // applied only when the theme is provided upfront - there's nothing to
+    // generate either, so we can skip the building animation
+    if let Theme::File(_) = &theme {
+        let world = store
+            .create_private_world(WorldConfig {
+                name: "sandbox".into(),
+                policy,
+                theme: Some(theme),
+                ..Default::default()
+            })
+            .await?;
+
+        game.join(world).await?;
+
+        return Ok(());
+    }
+
+    let world = store
+        .create_private_world(WorldConfig {
+            name: "sandbox".into(),
+            policy,
+            ..Default::default()
+        })
+        .await?;
 
     game.join(world.clone()).await?;
 
@@ -1,11 +1,17 @@
 use super::{Event, Focus};
 use kartoffels_ui::{Button, KeyCode, Ui, UiWidget};
+use kartoffels_world::prelude::FileTheme;
 use std::fmt;
 
 #[derive(Clone, Debug)]
 pub enum SandboxTheme {
     Arena,
     Cave,
+    Dungeon,
+    Maze,
+
+    /// Custom map, loaded from the store's `maps` directory
+    File(FileTheme),
 }
 
 impl SandboxTheme {
@@ -15,39 +21,44 @@ impl SandboxTheme {
             .render(ui);
     }
 
-    pub fn render_choice(ui: &mut Ui<Event>) {
-        for val in SandboxTheme::all() {
-            Button::new(val.to_string(), val.key())
+    pub fn render_choice(ui: &mut Ui<Event>, maps: &[FileTheme]) {
+        for (idx, val) in SandboxTheme::all(maps).enumerate() {
+            Button::new(val.to_string(), val.key(idx))
                 .throwing(Event::SetTheme(val))
                 .render(ui);
         }
     }
 
-    pub fn height() -> u16 {
-        Self::all().count() as u16
+    pub fn height(maps: &[FileTheme]) -> u16 {
+        Self::all(maps).count() as u16
     }
 
-    fn all() -> impl Iterator<Item = Self> {
-        [Self::Arena, Self::Cave].into_iter()
+    fn all(maps: &[FileTheme]) -> impl Iterator<Item = Self> + '_ {
+        // Custom maps are bound to digits, so we can't show more than nine
+        [Self::Arena, Self::Cave, Self::Dungeon, Self::Maze]
+            .into_iter()
+            .chain(maps.iter().take(9).cloned().map(Self::File))
     }
 
-    fn key(&self) -> KeyCode {
+    fn key(&self, idx: usize) -> KeyCode {
         KeyCode::Char(match self {
             Self::Arena => 'a',
             Self::Cave => 'c',
+            Self::Dungeon => 'd',
+            Self::Maze => 'm',
+            Self::File(_) => (b'1' + (idx - 4) as u8) as char,
         })
     }
 }
 
 impl fmt::Display for SandboxTheme {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(
-            f,
-            "{}",
-            match self {
-                Self::Arena => "arena",
-                Self::Cave => "cave",
-            }
-        )
+        match self {
+            Self::Arena => write!(f, "arena"),
+            Self::Cave => write!(f, "cave"),
+            Self::Dungeon => write!(f, "dungeon"),
+            Self::Maze => write!(f, "maze"),
+            Self::File(map) => write!(f, "{}", map.name()),
+        }
     }
 }
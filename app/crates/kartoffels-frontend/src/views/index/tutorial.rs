@@ -13,6 +13,8 @@ pub async fn run(
 ) -> Result<()> {
     let mut completed = false;
 
+    store.record_private_world(sess)?;
+
     game::run(store, sess, frame, |ctrl| {
         ctrl::run(store, ctrl, &mut completed)
     })
@@ -1,4 +1,4 @@
-use crate::views::game::{Config, GameCtrl};
+use crate::views::game::{BotAccess, Config, GameCtrl};
 use anyhow::Result;
 use glam::ivec2;
 use kartoffels_store::Store;
@@ -21,26 +21,31 @@ impl TutorialCtxt {
             hero_mode: true,
             sync_pause: true,
 
-            can_delete_bots: true,
+            can_debug_bots: BotAccess::None,
+            can_delete_bots: BotAccess::All,
             can_join_bots: false,
             can_overclock: false,
             can_pause: false,
-            can_restart_bots: false,
+            can_restart_bots: BotAccess::None,
+            can_send_to_bots: BotAccess::None,
             can_spawn_bots: false,
             can_upload_bots: true,
         })
         .await?;
 
-        let world = store.create_private_world(WorldConfig {
-            clock: Clock::Normal,
-            policy: Policy {
-                auto_respawn: false,
-                max_alive_bots: 16,
-                max_queued_bots: 16,
-            },
-            theme: Some(Theme::Arena(ArenaTheme::new(12))),
-            ..store.world_config("tutorial")
-        })?;
+        let world = store
+            .create_private_world(WorldConfig {
+                clock: Clock::Normal,
+                policy: Policy {
+                    auto_respawn: false,
+                    max_alive_bots: 16,
+                    max_queued_bots: 16,
+                    ..Default::default()
+                },
+                theme: Some(Theme::Arena(ArenaTheme::new(12))),
+                ..store.world_config("tutorial")
+            })
+            .await?;
 
         world.set_spawn(ivec2(12, 12), None).await?;
         game.join(world.clone()).await?;
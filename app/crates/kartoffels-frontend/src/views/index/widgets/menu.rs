@@ -1,5 +1,4 @@
 use super::super::Event;
-use kartoffels_store::Store;
 use kartoffels_ui::{theme, Button, KeyCode, Ui, UiWidget};
 use ratatui::style::Style;
 use ratatui::widgets::{Block, Padding};
@@ -12,23 +11,23 @@ impl Menu {
         20
     }
 
-    pub fn height<T>(store: &Store, ui: &Ui<T>) -> u16 {
-        let height = if ui.ty.is_ssh() { 7 } else { 5 };
+    pub fn height<T>(can_play: bool, ui: &Ui<T>) -> u16 {
+        let height = if ui.ty.is_ssh() { 8 } else { 6 };
 
-        if store.public_worlds().is_empty() {
-            height
-        } else {
+        if can_play {
             height + 1
+        } else {
+            height
         }
     }
 
-    pub fn render(store: &Store, ui: &mut Ui<Event>) {
+    pub fn render(can_play: bool, ui: &mut Ui<Event>) {
         let block = Block::bordered()
             .border_style(Style::new().fg(theme::GREEN).bg(theme::BG))
             .padding(Padding::horizontal(1));
 
         ui.block(block, |ui| {
-            if !store.public_worlds().is_empty() {
+            if can_play {
                 Button::new("play", KeyCode::Char('p'))
                     .throwing(Event::Play)
                     .centered()
@@ -50,6 +49,11 @@ impl Menu {
                 .centered()
                 .render(ui);
 
+            Button::new("leaderboard", KeyCode::Char('l'))
+                .throwing(Event::Leaderboard)
+                .centered()
+                .render(ui);
+
             if ui.ty.is_ssh() {
                 ui.space(1);
 
@@ -76,15 +76,14 @@ fn main() {
     // Cost of moving between nodes, used by the pathfinding algorithm.
     //
     // Same case as `nav_queue`, we keep it here for performance reasons.
-    let mut nav_costs: Vec<_> = iter::repeat(0).take(map.tiles.len()).collect();
+    let mut nav_costs: Vec<_> = iter::repeat_n(0, map.tiles.len()).collect();
 
     // Parent nodes, used by the pathfinding algorithm to reconstruct the path
     // after we find a `Tile::Unknown` or `Tile::Flag`.
     //
     // Same case as `nav_queue`, we keep it here for performance reasons.
-    let mut nav_prevs: Vec<_> = iter::repeat(I8Vec2::default())
-        .take(map.tiles.len())
-        .collect();
+    let mut nav_prevs: Vec<_> =
+        iter::repeat_n(I8Vec2::default(), map.tiles.len()).collect();
 
     loop {
         // 1. Scan the environment - if something has changed, reset our cached
@@ -414,8 +413,7 @@ impl Map {
     fn new() -> Self {
         let tiles = (Self::MAX - Self::MIN).as_uvec2();
 
-        let tiles = iter::repeat(Tile::Unknown)
-            .take((tiles.x * tiles.y) as usize)
+        let tiles = iter::repeat_n(Tile::Unknown, (tiles.x * tiles.y) as usize)
             .collect();
 
         Self { tiles }
@@ -19,6 +19,7 @@ bots!([
     static CHL_DIAMOND_HEIST_GUARD = "chl-diamond-heist-guard";
     static CHL_PERSONAL_ROOMBA = "chl-personal-roomba";
     static DUMMY = "dummy";
+    static RADIO = "radio";
     static ROBERTO = "roberto";
     static TUT_01 = "tut-01";
     static TUT_02 = "tut-02";
@@ -0,0 +1,20 @@
+//! Bot used for radio's tests - it broadcasts a single message and then prints
+//! ids of bots it hears from.
+
+#![cfg_attr(target_arch = "riscv32", no_std, no_main)]
+
+use kartoffel::*;
+
+#[cfg_attr(target_arch = "riscv32", no_mangle)]
+fn main() {
+    radio_wait();
+    radio_send(4, &[0xcafebabe, 0, 0, 0, 0, 0, 0, 0]);
+
+    loop {
+        if let Some(msg) = radio_recv() {
+            if msg.data[0] == 0xcafebabe {
+                println!("{:016x}", msg.from.get());
+            }
+        }
+    }
+}
@@ -1,6 +1,7 @@
 use futures_util::FutureExt;
-use kartoffels_store::Store;
+use kartoffels_store::{PlayerId, Store};
 use kartoffels_ui::Frame;
+use std::net::IpAddr;
 use std::panic::AssertUnwindSafe;
 use std::pin::pin;
 use std::sync::Arc;
@@ -12,13 +13,26 @@ pub async fn start_session(
     store: Arc<Store>,
     mut frame: Frame,
     shutdown: CancellationToken,
+    player: Option<PlayerId>,
+    addr: Option<IpAddr>,
 ) {
     _ = frame.create().await;
 
-    let sess = store.create_session();
-
     let result = {
-        let sess = kartoffels_frontend::main(&store, &sess, &mut frame);
+        let sess = async {
+            match store.create_session(player, addr) {
+                Ok(sess) => {
+                    kartoffels_frontend::main(&store, &sess, &mut frame).await
+                }
+
+                Err(err) => {
+                    info!("session rejected: {}", err);
+
+                    kartoffels_frontend::reject(&store, &mut frame, err).await
+                }
+            }
+        };
+
         let sess = AssertUnwindSafe(sess).catch_unwind();
         let sess = pin!(sess);
 
@@ -1,16 +1,21 @@
 mod endpoints;
 
 use anyhow::Result;
+use axum::body::Body;
 use axum::extract::DefaultBodyLimit;
-use axum::routing::{get, post};
+use axum::http::header::AUTHORIZATION;
+use axum::http::{Request, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{delete, get, post};
 use axum::Router;
-use kartoffels_store::Store;
+use kartoffels_store::{Secret, Store};
 use std::net::SocketAddr;
 use std::sync::Arc;
 use tokio::net::TcpListener;
 use tokio_util::sync::CancellationToken;
 use tower_http::cors::{self, CorsLayer};
 use tower_http::trace::{DefaultMakeSpan, TraceLayer};
+use tower_http::validate_request::ValidateRequestHeaderLayer;
 use tracing::info;
 
 pub async fn start(
@@ -31,12 +36,25 @@ pub async fn start(
 
         let limit = DefaultBodyLimit::max(512 * 1024);
 
-        Router::new()
+        let mut router = Router::new()
             .route("/", get(endpoints::connect::handle))
             .route(
                 "/sessions/:id/bots",
                 post(endpoints::create_session_bot::handle),
             )
+            .route("/worlds", get(endpoints::list_worlds::handle))
+            .route(
+                "/worlds/:id/spectate",
+                get(endpoints::spectate_world::handle),
+            );
+
+        // Admin endpoints are available only if the secret's been configured,
+        // since that's what we use as the bearer token
+        if let Some(secret) = store.secret() {
+            router = router.nest("/admin", admin(secret));
+        }
+
+        router
             .with_state((store, shutdown.clone()))
             .layer(cors)
             .layer(trace)
@@ -53,3 +71,45 @@ pub async fn start(
 
     Ok(())
 }
+
+fn admin(secret: &Secret) -> Router<(Arc<Store>, CancellationToken)> {
+    use self::endpoints::admin::*;
+
+    Router::new()
+        .route(
+            "/worlds",
+            get(list_worlds::handle).post(create_world::handle),
+        )
+        .route("/worlds/:id", delete(delete_world::handle))
+        .route("/worlds/:id/rename", post(rename_world::handle))
+        .route("/worlds/:id/pause", post(pause_world::handle))
+        .route("/worlds/:id/resume", post(resume_world::handle))
+        .route("/worlds/:id/overclock", post(overclock_world::handle))
+        .route("/worlds/:id/bots/:bot/kill", post(kill_bot::handle))
+        .route("/worlds/:id/bots/:bot", delete(delete_bot::handle))
+        .layer(ValidateRequestHeaderLayer::custom(authorize(
+            secret.clone(),
+        )))
+}
+
+/// Checks the `Authorization: Bearer <secret>` header.
+///
+/// Note that the secret is compared in constant time, so that it can't be
+/// guessed by measuring how long it takes us to reject a request.
+fn authorize(
+    secret: Secret,
+) -> impl FnMut(&mut Request<Body>) -> Result<(), Response> + Clone {
+    move |req| {
+        let token = req
+            .headers()
+            .get(AUTHORIZATION)
+            .and_then(|header| header.to_str().ok())
+            .and_then(|header| header.strip_prefix("Bearer "));
+
+        if token.is_some_and(|token| secret.matches(token)) {
+            Ok(())
+        } else {
+            Err(StatusCode::UNAUTHORIZED.into_response())
+        }
+    }
+}
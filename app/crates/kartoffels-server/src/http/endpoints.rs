@@ -1,2 +1,5 @@
+pub mod admin;
 pub mod connect;
 pub mod create_session_bot;
+pub mod list_worlds;
+pub mod spectate_world;
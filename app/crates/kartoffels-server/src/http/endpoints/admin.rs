@@ -0,0 +1,52 @@
+//! Endpoints for administering the server, mirroring the console commands.
+//!
+//! All of them require the `Authorization: Bearer <secret>` header - see
+//! [`crate::http::start()`].
+
+pub mod create_world;
+pub mod delete_bot;
+pub mod delete_world;
+pub mod kill_bot;
+pub mod list_worlds;
+pub mod overclock_world;
+pub mod pause_world;
+pub mod rename_world;
+pub mod resume_world;
+
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use kartoffels_store::Store;
+use kartoffels_utils::Id;
+use kartoffels_world::prelude::Handle as WorldHandle;
+use serde_json::json;
+
+#[derive(Debug)]
+pub enum Error {
+    WorldNotFound,
+    Other(anyhow::Error),
+}
+
+impl From<anyhow::Error> for Error {
+    fn from(err: anyhow::Error) -> Self {
+        Self::Other(err)
+    }
+}
+
+impl IntoResponse for Error {
+    fn into_response(self) -> Response {
+        let (status, err) = match self {
+            Error::WorldNotFound => {
+                (StatusCode::NOT_FOUND, "couldn't find world".to_owned())
+            }
+
+            Error::Other(err) => (StatusCode::BAD_REQUEST, format!("{err:#}")),
+        };
+
+        (status, Json(json!({ "error": err }))).into_response()
+    }
+}
+
+fn find_world(store: &Store, id: Id) -> Result<WorldHandle, Error> {
+    store.world(id).ok_or(Error::WorldNotFound)
+}
@@ -0,0 +1,51 @@
+use super::Error;
+use anyhow::Context;
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::Json;
+use kartoffels_store::Store;
+use kartoffels_world::prelude::{Config, Policy, Theme};
+use serde::Deserialize;
+use serde_json::json;
+use std::str::FromStr;
+use std::sync::Arc;
+use tokio_util::sync::CancellationToken;
+
+pub async fn handle(
+    State((store, _)): State<(Arc<Store>, CancellationToken)>,
+    Json(req): Json<Request>,
+) -> Result<impl IntoResponse, Error> {
+    let policy = req.policy.unwrap_or_default();
+
+    let policy = Policy::from_str(&policy)
+        .with_context(|| format!("couldn't parse policy: {policy}"))?;
+
+    let theme = Theme::from_str(&req.theme)
+        .with_context(|| format!("couldn't parse theme: {}", req.theme))?;
+
+    let config = Config {
+        name: req.name,
+        policy,
+        theme: Some(theme),
+        ..Default::default()
+    };
+
+    let world = if req.private.unwrap_or_default() {
+        store.create_persistent_private_world(None, config).await?
+    } else {
+        store.create_public_world(config).await?
+    };
+
+    Ok((StatusCode::CREATED, Json(json!({ "id": world.id() }))))
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Request {
+    name: String,
+    policy: Option<String>,
+    theme: String,
+
+    /// Creates a private world, i.e. one that's not listed on the index page
+    private: Option<bool>,
+}
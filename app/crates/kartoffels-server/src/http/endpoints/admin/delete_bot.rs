@@ -0,0 +1,17 @@
+use super::{find_world, Error};
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use kartoffels_store::Store;
+use kartoffels_utils::Id;
+use kartoffels_world::prelude::BotId;
+use std::sync::Arc;
+use tokio_util::sync::CancellationToken;
+
+pub async fn handle(
+    State((store, _)): State<(Arc<Store>, CancellationToken)>,
+    Path((world_id, bot_id)): Path<(Id, BotId)>,
+) -> Result<StatusCode, Error> {
+    find_world(&store, world_id)?.delete_bot(bot_id).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,31 @@
+use super::{find_world, Error};
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::Json;
+use kartoffels_store::Store;
+use kartoffels_utils::Id;
+use kartoffels_world::prelude::BotId;
+use serde::Deserialize;
+use std::sync::Arc;
+use tokio_util::sync::CancellationToken;
+
+pub async fn handle(
+    State((store, _)): State<(Arc<Store>, CancellationToken)>,
+    Path((world_id, bot_id)): Path<(Id, BotId)>,
+    req: Option<Json<Request>>,
+) -> Result<StatusCode, Error> {
+    let reason = req
+        .and_then(|Json(req)| req.reason)
+        .unwrap_or_else(|| "killed by admin".into());
+
+    find_world(&store, world_id)?
+        .kill_bot(bot_id, reason)
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Request {
+    reason: Option<String>,
+}
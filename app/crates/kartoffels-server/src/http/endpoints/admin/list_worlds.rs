@@ -0,0 +1,32 @@
+use axum::extract::State;
+use axum::response::IntoResponse;
+use axum::Json;
+use kartoffels_store::{Store, WorldType};
+use kartoffels_utils::Id;
+use serde::Serialize;
+use std::sync::Arc;
+use tokio_util::sync::CancellationToken;
+
+pub async fn handle(
+    State((store, _)): State<(Arc<Store>, CancellationToken)>,
+) -> impl IntoResponse {
+    let worlds: Vec<_> = store
+        .worlds(None)
+        .into_iter()
+        .map(|(ty, world)| WorldEntry {
+            id: world.id(),
+            name: world.name().to_string(),
+            ty,
+        })
+        .collect();
+
+    Json(worlds)
+}
+
+#[derive(Clone, Debug, Serialize)]
+struct WorldEntry {
+    id: Id,
+    name: String,
+    #[serde(rename = "type")]
+    ty: WorldType,
+}
@@ -0,0 +1,37 @@
+use super::{find_world, Error};
+use anyhow::anyhow;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::Json;
+use kartoffels_store::Store;
+use kartoffels_utils::Id;
+use kartoffels_world::prelude::Clock;
+use serde::Deserialize;
+use std::sync::Arc;
+use tokio_util::sync::CancellationToken;
+
+pub async fn handle(
+    State((store, _)): State<(Arc<Store>, CancellationToken)>,
+    Path(id): Path<Id>,
+    Json(req): Json<Request>,
+) -> Result<StatusCode, Error> {
+    let world = find_world(&store, id)?;
+
+    // Other clocks are meant for testing and benchmarking - letting them
+    // loose on a live world could starve the server
+    if !matches!(req.clock, Clock::Normal | Clock::Fast | Clock::Faster) {
+        return Err(anyhow!(
+            "unsupported clock - use `Normal`, `Fast` or `Faster`"
+        )
+        .into());
+    }
+
+    world.overclock(req.clock).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Request {
+    clock: Clock,
+}
@@ -0,0 +1,16 @@
+use super::{find_world, Error};
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use kartoffels_store::Store;
+use kartoffels_utils::Id;
+use std::sync::Arc;
+use tokio_util::sync::CancellationToken;
+
+pub async fn handle(
+    State((store, _)): State<(Arc<Store>, CancellationToken)>,
+    Path(id): Path<Id>,
+) -> Result<StatusCode, Error> {
+    find_world(&store, id)?.pause().await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,26 @@
+use super::{find_world, Error};
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::Json;
+use kartoffels_store::Store;
+use kartoffels_utils::Id;
+use serde::Deserialize;
+use std::sync::Arc;
+use tokio_util::sync::CancellationToken;
+
+pub async fn handle(
+    State((store, _)): State<(Arc<Store>, CancellationToken)>,
+    Path(id): Path<Id>,
+    Json(req): Json<Request>,
+) -> Result<StatusCode, Error> {
+    find_world(&store, id)?;
+
+    store.rename_world(id, req.name).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Request {
+    name: String,
+}
@@ -38,7 +38,7 @@ pub async fn handle(
         async move {
             info!("connection opened");
 
-            match main(store, shutdown, socket).await {
+            match main(store, shutdown, socket, addr).await {
                 Ok(()) => {
                     info!("connection closed");
                 }
@@ -56,6 +56,7 @@ async fn main(
     store: Arc<Store>,
     shutdown: CancellationToken,
     mut socket: WebSocket,
+    addr: SocketAddr,
 ) -> Result<()> {
     let hello = recv_hello_msg(&mut socket)
         .await
@@ -63,7 +64,7 @@ async fn main(
 
     let frame = create_frame(socket, hello).context("couldn't create frame")?;
 
-    common::start_session(store, frame, shutdown).await;
+    common::start_session(store, frame, shutdown, None, Some(addr.ip())).await;
 
     Ok(())
 }
@@ -0,0 +1,29 @@
+use axum::extract::State;
+use axum::response::IntoResponse;
+use axum::Json;
+use kartoffels_store::Store;
+use kartoffels_utils::Id;
+use serde::Serialize;
+use std::sync::Arc;
+use tokio_util::sync::CancellationToken;
+
+pub async fn handle(
+    State((store, _)): State<(Arc<Store>, CancellationToken)>,
+) -> impl IntoResponse {
+    let worlds: Vec<_> = store
+        .public_worlds()
+        .iter()
+        .map(|world| WorldEntry {
+            id: world.id(),
+            name: world.name().to_string(),
+        })
+        .collect();
+
+    Json(worlds)
+}
+
+#[derive(Clone, Debug, Serialize)]
+struct WorldEntry {
+    id: Id,
+    name: String,
+}
@@ -0,0 +1,114 @@
+use anyhow::Result;
+use axum::extract::ws::{Message, WebSocket};
+use axum::extract::{ConnectInfo, Path, State, WebSocketUpgrade};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use kartoffels_store::Store;
+use kartoffels_utils::Id;
+use kartoffels_world::prelude::{
+    EventLetter, EventStream, Handle as WorldHandle, Snapshot,
+};
+use serde::Serialize;
+use std::future;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tokio::select;
+use tokio_util::sync::CancellationToken;
+use tracing::{info, info_span, Instrument};
+
+/// Streams snapshots and events of given public world as JSON messages.
+///
+/// This is a read-only endpoint - anything sent by the client, apart from the
+/// close frame, is ignored.
+pub async fn handle(
+    socket: WebSocketUpgrade,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    State((store, shutdown)): State<(Arc<Store>, CancellationToken)>,
+    Path(id): Path<Id>,
+) -> Result<impl IntoResponse, StatusCode> {
+    let world = store
+        .public_worlds()
+        .iter()
+        .find(|world| world.id() == id)
+        .cloned()
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    let span = info_span!("http", %addr, world = %id);
+
+    Ok(socket.on_upgrade(move |socket| {
+        async move {
+            info!("spectator connected");
+
+            match main(world, shutdown, socket).await {
+                Ok(()) => {
+                    info!("spectator disconnected");
+                }
+
+                Err(err) => {
+                    info!("spectator disconnected: {:?}", err);
+                }
+            }
+        }
+        .instrument(span)
+    }))
+}
+
+async fn main(
+    world: WorldHandle,
+    shutdown: CancellationToken,
+    mut socket: WebSocket,
+) -> Result<()> {
+    let mut snapshots = world.snapshots();
+
+    // Events are optional - if the world doesn't emit them, we just stream the
+    // snapshots
+    let mut events = world.events().ok();
+
+    loop {
+        let msg = select! {
+            snapshot = snapshots.next() => {
+                Message::Text(serde_json::to_string(&SpectatorMsg::Snapshot(
+                    &*snapshot?,
+                ))?)
+            }
+
+            event = next_event(&mut events) => {
+                Message::Text(serde_json::to_string(&SpectatorMsg::Event(
+                    event?,
+                ))?)
+            }
+
+            msg = socket.recv() => {
+                match msg {
+                    Some(Ok(Message::Close(_))) | Some(Err(_)) | None => {
+                        return Ok(());
+                    }
+
+                    Some(Ok(_)) => {
+                        continue;
+                    }
+                }
+            }
+
+            _ = shutdown.cancelled() => {
+                return Ok(());
+            }
+        };
+
+        socket.send(msg).await?;
+    }
+}
+
+async fn next_event(events: &mut Option<EventStream>) -> Result<EventLetter> {
+    match events {
+        Some(events) => events.next().await,
+        None => future::pending().await,
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum SpectatorMsg<'a> {
+    Snapshot(&'a Snapshot),
+    Event(EventLetter),
+}
@@ -7,7 +7,7 @@ pub mod ssh;
 use anyhow::{Context, Result};
 use clap::Parser;
 use indoc::indoc;
-use kartoffels_store::{Secret, Store};
+use kartoffels_store::{Limits, Secret, Store};
 use kartoffels_world::prelude::Clock;
 use std::env;
 use std::net::SocketAddr;
@@ -41,6 +41,19 @@ pub struct Cmd {
     #[clap(long)]
     secret: Option<Secret>,
 
+    /// How many sessions can be open at once from a single IP address
+    #[clap(long, default_value_t = Limits::default().sessions_per_ip)]
+    max_sessions_per_ip: usize,
+
+    /// How many bots a single session can upload within a minute
+    #[clap(long, default_value_t = Limits::default().uploads_per_minute)]
+    max_uploads_per_minute: usize,
+
+    /// How many private worlds can be created from a single IP address within
+    /// a minute
+    #[clap(long, default_value_t = Limits::default().private_worlds_per_minute)]
+    max_private_worlds_per_minute: usize,
+
     #[clap(long)]
     debug: bool,
 
@@ -95,7 +108,13 @@ impl Cmd {
     async fn start(self) -> Result<()> {
         info!("starting");
 
-        let store = Store::new(Some(&self.store), self.secret)
+        let limits = Limits {
+            sessions_per_ip: self.max_sessions_per_ip,
+            uploads_per_minute: self.max_uploads_per_minute,
+            private_worlds_per_minute: self.max_private_worlds_per_minute,
+        };
+
+        let store = Store::new(Some(&self.store), self.secret, limits)
             .await
             .with_context(|| {
                 format!("couldn't open store at `{}`", self.store.display())
@@ -13,7 +13,7 @@ use rand::rngs::OsRng;
 use russh::keys::ssh_key::private::{Ed25519PrivateKey, KeypairData};
 use russh::keys::PrivateKey;
 use russh::server::{Config, Server as _};
-use russh::{compression, Preferred};
+use russh::{compression, MethodKind, MethodSet, Preferred};
 use std::borrow::Cow;
 use std::pin::pin;
 use std::sync::Arc;
@@ -44,6 +44,13 @@ pub async fn start(
             ..Default::default()
         },
         keys: vec![key],
+        methods: MethodSet::from(
+            &[
+                MethodKind::PublicKey,
+                MethodKind::KeyboardInteractive,
+                MethodKind::Password,
+            ][..],
+        ),
         nodelay: true,
         ..Default::default()
     });
@@ -1,10 +1,11 @@
 use crate::common;
 use anyhow::{anyhow, Result};
 use glam::uvec2;
-use kartoffels_store::Store;
+use kartoffels_store::{PlayerId, Store};
 use kartoffels_ui::{Frame, FrameType};
 use russh::server::{Handle as SessionHandle, Session};
 use russh::ChannelId;
+use std::net::IpAddr;
 use std::sync::Arc;
 use tokio::sync::mpsc;
 use tokio::task;
@@ -22,6 +23,8 @@ enum AppChannelState {
     AwaitingPty {
         store: Arc<Store>,
         shutdown: CancellationToken,
+        player: Option<PlayerId>,
+        addr: Option<IpAddr>,
     },
 
     Ready {
@@ -34,9 +37,16 @@ impl AppChannel {
         id: ChannelId,
         store: Arc<Store>,
         shutdown: CancellationToken,
+        player: Option<PlayerId>,
+        addr: Option<IpAddr>,
         span: &Span,
     ) -> Self {
-        let state = AppChannelState::AwaitingPty { store, shutdown };
+        let state = AppChannelState::AwaitingPty {
+            store,
+            shutdown,
+            player,
+            addr,
+        };
         let span = info_span!(parent: span, "chan", %id);
 
         info!(parent: &span, "channel opened");
@@ -64,13 +74,20 @@ impl AppChannel {
         height: u32,
         session: &mut Session,
     ) -> Result<()> {
-        let AppChannelState::AwaitingPty { store, shutdown } = &mut self.state
+        let AppChannelState::AwaitingPty {
+            store,
+            shutdown,
+            player,
+            addr,
+        } = &mut self.state
         else {
             return Err(anyhow!("pty has been already allocated"));
         };
 
         let store = store.clone();
         let shutdown = shutdown.clone();
+        let player = player.clone();
+        let addr = *addr;
         let handle = session.handle();
 
         let (term, stdin) = Self::create_term(
@@ -82,7 +99,7 @@ impl AppChannel {
         )?;
 
         task::spawn(
-            common::start_session(store, term, shutdown)
+            common::start_session(store, term, shutdown, player, addr)
                 .instrument(self.span.clone()),
         );
 
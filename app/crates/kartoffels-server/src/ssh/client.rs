@@ -1,10 +1,11 @@
-use super::AppChannel;
+use super::{AppChannel, AppConnection};
 use ahash::AHashMap;
 use anyhow::{anyhow, Context, Error, Result};
-use kartoffels_store::Store;
-use russh::keys::PublicKey;
+use kartoffels_store::{LimitExceeded, PlayerId, Store};
+use russh::keys::{HashAlg, PublicKey};
 use russh::server::{self, Auth, Msg, Session};
 use russh::{Channel, ChannelId, Pty};
+use std::net::{IpAddr, SocketAddr};
 use std::sync::Arc;
 use tokio_util::sync::CancellationToken;
 use tracing::{info, info_span, Span};
@@ -14,16 +15,29 @@ pub struct AppClient {
     store: Arc<Store>,
     shutdown: CancellationToken,
     channels: AHashMap<ChannelId, AppChannel>,
+    player: Option<PlayerId>,
+    addr: Option<IpAddr>,
     span: Span,
+
+    /// Connection registered for our address, or an error if there were too
+    /// many connections open from it already
+    conn: Result<Option<AppConnection>, LimitExceeded>,
 }
 
 impl AppClient {
     pub fn new(
-        addr: String,
+        addr: Option<SocketAddr>,
+        conn: Result<Option<AppConnection>, LimitExceeded>,
         store: Arc<Store>,
         shutdown: CancellationToken,
     ) -> Self {
-        let span = info_span!("ssh", %addr);
+        let span = {
+            let addr = addr
+                .map(|addr| addr.to_string())
+                .unwrap_or_else(|| "-".into());
+
+            info_span!("ssh", %addr)
+        };
 
         info!(parent: &span, "connection opened");
 
@@ -32,6 +46,9 @@ impl AppClient {
             store,
             shutdown,
             channels: Default::default(),
+            player: None,
+            addr: addr.map(|addr| addr.ip()),
+            conn,
         }
     }
 
@@ -45,15 +62,44 @@ impl AppClient {
 impl server::Handler for AppClient {
     type Error = Error;
 
+    // We reject the `none` method so that clients get a chance to present
+    // their public keys - those who don't have any can still get in through
+    // `keyboard-interactive` or `password`, both of which we accept as-is,
+    // just without a persistent identity.
+    //
+    // Note that this doesn't slow down the "happy path", since OpenSSH always
+    // starts with `none` and we don't delay the initial rejection.
     async fn auth_none(&mut self, _: &str) -> Result<Auth> {
-        Ok(Auth::Accept)
+        Ok(Auth::Reject {
+            proceed_with_methods: None,
+        })
     }
 
     async fn auth_password(&mut self, _: &str, _: &str) -> Result<Auth> {
         Ok(Auth::Accept)
     }
 
-    async fn auth_publickey(&mut self, _: &str, _: &PublicKey) -> Result<Auth> {
+    async fn auth_keyboard_interactive<'a>(
+        &'a mut self,
+        _: &str,
+        _: &str,
+        _: Option<server::Response<'a>>,
+    ) -> Result<Auth> {
+        Ok(Auth::Accept)
+    }
+
+    // Called after the client proves they own the key
+    async fn auth_publickey(
+        &mut self,
+        _: &str,
+        key: &PublicKey,
+    ) -> Result<Auth> {
+        let player = key.fingerprint(HashAlg::Sha256).to_string().parse()?;
+
+        info!(parent: &self.span, %player, "player authenticated");
+
+        self.player = Some(player);
+
         Ok(Auth::Accept)
     }
 
@@ -62,10 +108,18 @@ impl server::Handler for AppClient {
         channel: Channel<Msg>,
         _: &mut Session,
     ) -> Result<bool> {
+        if let Err(err) = self.conn {
+            info!(parent: &self.span, "connection rejected: {err}");
+
+            return Err(err.into());
+        }
+
         let app_channel = AppChannel::new(
             channel.id(),
             self.store.clone(),
             self.shutdown.clone(),
+            self.player.clone(),
+            self.addr,
             &self.span,
         );
 
@@ -1,31 +1,94 @@
 use super::AppClient;
-use kartoffels_store::Store;
+use ahash::AHashMap;
+use kartoffels_store::{LimitExceeded, Store};
 use russh::server;
-use std::net::SocketAddr;
-use std::sync::Arc;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::{Arc, Mutex};
 use tokio_util::sync::CancellationToken;
 
 #[derive(Debug)]
 pub struct AppServer {
     store: Arc<Store>,
     shutdown: CancellationToken,
+    connections: AppConnections,
 }
 
 impl AppServer {
     pub fn new(store: Arc<Store>, shutdown: CancellationToken) -> Self {
-        Self { store, shutdown }
+        Self {
+            store,
+            shutdown,
+            connections: Default::default(),
+        }
     }
 }
 
-// TODO consider some ddos / rate limiting mechanism
 impl server::Server for AppServer {
     type Handler = AppClient;
 
     fn new_client(&mut self, addr: Option<SocketAddr>) -> AppClient {
-        let addr = addr
-            .map(|addr| addr.to_string())
-            .unwrap_or_else(|| "-".into());
+        let conn = match addr {
+            Some(addr) => self
+                .connections
+                .open(addr.ip(), self.store.limits().sessions_per_ip)
+                .map(Some)
+                .ok_or(LimitExceeded::Sessions),
 
-        AppClient::new(addr, self.store.clone(), self.shutdown.clone())
+            None => Ok(None),
+        };
+
+        AppClient::new(addr, conn, self.store.clone(), self.shutdown.clone())
+    }
+}
+
+/// Connections open from each address.
+///
+/// Since a single connection can open many channels, we enforce
+/// [`kartoffels_store::Limits::sessions_per_ip`] on the connection level as
+/// well - otherwise one could keep spawning connections that never get to the
+/// point of creating a session.
+#[derive(Clone, Debug, Default)]
+pub struct AppConnections {
+    entries: Arc<Mutex<AHashMap<IpAddr, usize>>>,
+}
+
+impl AppConnections {
+    /// Registers a new connection, returning `None` if there are already
+    /// `limit` connections open from given address.
+    pub fn open(&self, addr: IpAddr, limit: usize) -> Option<AppConnection> {
+        let mut entries = self.entries.lock().unwrap();
+        let count = entries.entry(addr).or_default();
+
+        if *count >= limit {
+            return None;
+        }
+
+        *count += 1;
+
+        Some(AppConnection {
+            addr,
+            entries: self.entries.clone(),
+        })
+    }
+}
+
+/// Connection registered in [`AppConnections`], unregistered when dropped.
+#[derive(Debug)]
+pub struct AppConnection {
+    addr: IpAddr,
+    entries: Arc<Mutex<AHashMap<IpAddr, usize>>>,
+}
+
+impl Drop for AppConnection {
+    fn drop(&mut self) {
+        let mut entries = self.entries.lock().unwrap();
+
+        if let Some(count) = entries.get_mut(&self.addr) {
+            *count -= 1;
+
+            if *count == 0 {
+                entries.remove(&self.addr);
+            }
+        }
     }
 }
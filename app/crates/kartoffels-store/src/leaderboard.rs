@@ -0,0 +1,302 @@
+use ahash::AHashMap;
+use kartoffels_utils::Id;
+use kartoffels_world::prelude::{BotId, RoundResult};
+use std::cmp::Reverse;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::Path;
+use std::time::SystemTime;
+use tracing::warn;
+
+/// Summary of rounds archived by worlds, see [`RoundResult`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Leaderboard {
+    /// Most recently finished rounds, latest first
+    pub recent: Vec<RoundResult>,
+
+    /// All-time rankings, best first
+    pub rankings: Vec<Ranking>,
+}
+
+impl Leaderboard {
+    const RECENT: usize = 10;
+
+    pub fn new(mut rounds: Vec<RoundResult>) -> Self {
+        let mut rankings = AHashMap::<BotId, Ranking>::new();
+
+        for round in &rounds {
+            for bot in &round.bots {
+                let ranking = rankings.entry(bot.id).or_insert(Ranking {
+                    bot: bot.id,
+                    wins: 0,
+                    rounds: 0,
+                    score: 0,
+                    kills: 0,
+                });
+
+                ranking.rounds += 1;
+                ranking.score += bot.score as u64;
+                ranking.kills += bot.kills as u64;
+
+                if round.winner == Some(bot.id) {
+                    ranking.wins += 1;
+                }
+            }
+        }
+
+        let mut rankings: Vec<_> = rankings.into_values().collect();
+
+        rankings.sort_by_key(|ranking| {
+            (Reverse(ranking.wins), Reverse(ranking.score), ranking.bot)
+        });
+
+        rounds.sort_by_key(|round| Reverse(round.ended_at));
+        rounds.truncate(Self::RECENT);
+
+        Self {
+            recent: rounds,
+            rankings,
+        }
+    }
+
+    /// Loads rounds archived by given worlds, i.e. `<dir>/<id>.rounds`.
+    ///
+    /// Malformed entries are skipped (with a warning), so that a single broken
+    /// line doesn't make the rest unavailable.
+    ///
+    /// Files are cached by their size and modification time, so opening the
+    /// leaderboard re-reads only the files that have changed since the last
+    /// time.
+    pub(crate) fn load(
+        dir: &Path,
+        worlds: &[Id],
+        cache: &mut RoundsCache,
+    ) -> Self {
+        cache.files.retain(|id, _| worlds.contains(id));
+
+        let mut rounds = Vec::new();
+
+        for &id in worlds {
+            let path = dir.join(id.to_string()).with_extension("rounds");
+
+            let (len, modified) = match fs::metadata(&path) {
+                Ok(meta) => (meta.len(), meta.modified().ok()),
+
+                Err(err) => {
+                    if err.kind() != ErrorKind::NotFound {
+                        warn!(?path, "couldn't read rounds: {err:?}");
+                    }
+
+                    cache.files.remove(&id);
+                    continue;
+                }
+            };
+
+            if let Some(file) = cache.files.get(&id)
+                && file.len == len
+                && file.modified == modified
+                && modified.is_some()
+            {
+                rounds.extend(file.rounds.iter().cloned());
+                continue;
+            }
+
+            let src = match fs::read_to_string(&path) {
+                Ok(src) => src,
+                Err(err) => {
+                    warn!(?path, "couldn't read rounds: {err:?}");
+                    continue;
+                }
+            };
+
+            let mut file = RoundsFile {
+                len,
+                modified,
+                rounds: Vec::new(),
+            };
+
+            for line in src.lines() {
+                match serde_json::from_str(line) {
+                    Ok(round) => {
+                        file.rounds.push(round);
+                    }
+                    Err(err) => {
+                        warn!(?path, "couldn't parse round: {err:?}");
+                    }
+                }
+            }
+
+            rounds.extend(file.rounds.iter().cloned());
+            cache.files.insert(id, file);
+        }
+
+        Self::new(rounds)
+    }
+}
+
+/// Rounds loaded by [`Leaderboard::load()`], keyed by world.
+#[derive(Debug, Default)]
+pub(crate) struct RoundsCache {
+    files: AHashMap<Id, RoundsFile>,
+}
+
+#[derive(Debug)]
+struct RoundsFile {
+    len: u64,
+    modified: Option<SystemTime>,
+    rounds: Vec<RoundResult>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Ranking {
+    pub bot: BotId,
+    pub wins: u32,
+    pub rounds: u32,
+    pub score: u64,
+    pub kills: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kartoffels_world::prelude::{RoundResultBot, Ticks};
+    use std::path::PathBuf;
+    use std::time::Duration;
+    use tempfile::TempDir;
+
+    fn round(
+        idx: u32,
+        winner: Option<u64>,
+        bots: &[(u64, u32, u32)],
+    ) -> RoundResult {
+        RoundResult {
+            world: "arena".into(),
+            idx,
+            ended_at: Default::default(),
+            age: Ticks::new(64_000),
+            winner: winner.map(BotId::new),
+            bots: bots
+                .iter()
+                .map(|&(id, score, kills)| RoundResultBot {
+                    id: BotId::new(id),
+                    score,
+                    kills,
+                    age: Ticks::new(64_000),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn smoke() {
+        let mut rounds: Vec<_> = (0..12)
+            .map(|idx| round(idx, Some(1), &[(1, 3, 2), (2, 1, 0)]))
+            .collect();
+
+        rounds.push(round(12, Some(2), &[(2, 5, 1), (3, 5, 4)]));
+
+        for (idx, round) in rounds.iter_mut().enumerate() {
+            round.ended_at += Duration::from_secs(idx as u64);
+        }
+
+        let dir = TempDir::new().unwrap();
+        let public = Id::new(1);
+        let private = Id::new(2);
+
+        let src: String = rounds
+            .iter()
+            .map(|round| serde_json::to_string(round).unwrap() + "\n")
+            .chain(["this is not a round\n".into()])
+            .collect();
+
+        fs::write(path(&dir, public), &src).unwrap();
+        fs::write(path(&dir, private), &src).unwrap();
+
+        let target =
+            Leaderboard::load(dir.path(), &[public], &mut Default::default());
+
+        assert_eq!(
+            (3..=12).rev().collect::<Vec<_>>(),
+            target.recent.iter().map(|r| r.idx).collect::<Vec<_>>(),
+        );
+
+        assert_eq!(
+            vec![
+                Ranking {
+                    bot: BotId::new(1),
+                    wins: 12,
+                    rounds: 12,
+                    score: 36,
+                    kills: 24,
+                },
+                Ranking {
+                    bot: BotId::new(2),
+                    wins: 1,
+                    rounds: 13,
+                    score: 17,
+                    kills: 1,
+                },
+                Ranking {
+                    bot: BotId::new(3),
+                    wins: 0,
+                    rounds: 1,
+                    score: 5,
+                    kills: 4,
+                },
+            ],
+            target.rankings,
+        );
+    }
+
+    #[test]
+    fn cache() {
+        let dir = TempDir::new().unwrap();
+        let id = Id::new(1);
+        let mut cache = RoundsCache::default();
+
+        let load = |cache: &mut RoundsCache| {
+            Leaderboard::load(dir.path(), &[id], cache)
+                .recent
+                .iter()
+                .map(|round| round.idx)
+                .collect::<Vec<_>>()
+        };
+
+        let line = |idx| {
+            serde_json::to_string(&round(idx, None, &[(1, 0, 0)])).unwrap()
+                + "\n"
+        };
+
+        fs::write(path(&dir, id), line(0)).unwrap();
+
+        assert_eq!(vec![0], load(&mut cache));
+        assert_eq!(vec![0], load(&mut cache));
+
+        fs::write(path(&dir, id), line(0) + &line(1)).unwrap();
+
+        assert_eq!(vec![0, 1], load(&mut cache));
+
+        fs::remove_file(path(&dir, id)).unwrap();
+
+        assert!(load(&mut cache).is_empty());
+        assert!(cache.files.is_empty());
+    }
+
+    #[test]
+    fn empty() {
+        let dir = TempDir::new().unwrap();
+
+        assert_eq!(
+            Leaderboard::default(),
+            Leaderboard::load(
+                dir.path(),
+                &[Id::new(1)],
+                &mut Default::default()
+            )
+        );
+    }
+
+    fn path(dir: &TempDir, id: Id) -> PathBuf {
+        dir.path().join(id.to_string()).with_extension("rounds")
+    }
+}
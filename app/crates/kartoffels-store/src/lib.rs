@@ -1,12 +1,20 @@
 #![feature(let_chains)]
 #![feature(try_blocks)]
 
+mod leaderboard;
+mod limits;
+mod player;
+mod players;
 mod secret;
 mod session;
 mod sessions;
 mod world;
 mod worlds;
 
+pub use self::leaderboard::*;
+pub use self::limits::*;
+pub use self::player::*;
+use self::players::*;
 pub use self::secret::*;
 pub use self::session::*;
 use self::sessions::*;
@@ -15,18 +23,27 @@ use self::worlds::*;
 use anyhow::Result;
 use kartoffels_utils::Id;
 use kartoffels_world::prelude::{
-    Clock, Config as WorldConfig, Handle as WorldHandle,
+    Clock, Config as WorldConfig, FileTheme, Handle as WorldHandle,
 };
+use std::fs;
+use std::net::IpAddr;
 use std::path::{Path, PathBuf};
-use std::sync::Arc;
-use tracing::info;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use tokio::task;
+use tracing::{info, warn};
 
 #[derive(Debug)]
 pub struct Store {
     dir: Option<PathBuf>,
     secret: Option<Secret>,
+    limits: Limits,
     worlds: Worlds,
     sessions: Sessions,
+    players: Players,
+    maps: Vec<FileTheme>,
+    rounds: Arc<Mutex<RoundsCache>>,
+    private_worlds: PrivateWorldsLimiter,
     testing: bool,
 }
 
@@ -34,21 +51,37 @@ impl Store {
     pub async fn new(
         dir: Option<&Path>,
         secret: Option<Secret>,
+        limits: Limits,
     ) -> Result<Self> {
         info!("opening");
 
         Ok(Self {
             secret,
+            limits,
             worlds: Worlds::new(dir).await?,
             dir: dir.map(|dir| dir.to_owned()),
             sessions: Default::default(),
+            players: Players::new(dir).await?,
+            maps: match dir {
+                Some(dir) => {
+                    let dir = dir.to_owned();
+
+                    task::spawn_blocking(move || Self::load_maps(&dir)).await?
+                }
+
+                None => Default::default(),
+            },
+            rounds: Default::default(),
+            private_worlds: Default::default(),
             testing: false,
         })
     }
 
     pub async fn test(worlds: impl IntoIterator<Item = WorldHandle>) -> Self {
         let secret = "foobar".parse().unwrap();
-        let mut this = Self::new(None, Some(secret)).await.unwrap();
+        let mut this = Self::new(None, Some(secret), Default::default())
+            .await
+            .unwrap();
 
         this.worlds.set(worlds);
         this.testing = true;
@@ -59,34 +92,67 @@ impl Store {
         self.dir.as_deref().unwrap()
     }
 
-    pub fn secret(&self) -> Option<&str> {
-        self.secret.as_ref().map(|secret| secret.as_str())
+    pub fn secret(&self) -> Option<&Secret> {
+        self.secret.as_ref()
+    }
+
+    pub fn limits(&self) -> &Limits {
+        &self.limits
     }
 
     // ---
 
-    pub fn create_public_world(
+    pub async fn create_public_world(
         &self,
         config: WorldConfig,
     ) -> Result<WorldHandle> {
-        self.worlds.create(
-            self.testing,
-            self.dir.as_deref(),
-            WorldType::Public,
-            config,
-        )
+        self.worlds
+            .create(
+                self.testing,
+                self.dir.as_deref(),
+                WorldType::Public,
+                None,
+                true,
+                config,
+            )
+            .await
     }
 
-    pub fn create_private_world(
+    /// Creates a private world that gets deleted once its last handle is
+    /// dropped.
+    pub async fn create_private_world(
         &self,
         config: WorldConfig,
     ) -> Result<WorldHandle> {
-        self.worlds.create(
-            self.testing,
-            self.dir.as_deref(),
-            WorldType::Private,
-            config,
-        )
+        self.worlds
+            .create(
+                self.testing,
+                self.dir.as_deref(),
+                WorldType::Private,
+                None,
+                false,
+                config,
+            )
+            .await
+    }
+
+    /// Creates a private world that's saved on disk and survives restarts,
+    /// just like public worlds do.
+    pub async fn create_persistent_private_world(
+        &self,
+        owner: Option<PlayerId>,
+        config: WorldConfig,
+    ) -> Result<WorldHandle> {
+        self.worlds
+            .create(
+                self.testing,
+                self.dir.as_deref(),
+                WorldType::Private,
+                owner,
+                true,
+                config,
+            )
+            .await
     }
 
     pub async fn rename_world(&self, id: Id, name: String) -> Result<()> {
@@ -104,6 +170,14 @@ impl Store {
         self.worlds.list(ty)
     }
 
+    pub fn world(&self, id: Id) -> Option<WorldHandle> {
+        self.worlds.get(id)
+    }
+
+    pub fn world_owner(&self, id: Id) -> Option<PlayerId> {
+        self.worlds.owner(id)
+    }
+
     pub fn public_worlds(&self) -> Arc<Vec<WorldHandle>> {
         self.worlds.public()
     }
@@ -132,10 +206,85 @@ impl Store {
         }
     }
 
+    /// Returns maps available at `<store>/maps/*.txt`, sorted by name - note
+    /// that they are loaded once, when the store is opened.
+    pub fn maps(&self) -> &[FileTheme] {
+        &self.maps
+    }
+
+    /// Returns leaderboard built out of rounds archived by public worlds.
+    ///
+    /// Archives are cached and re-read only when they change on disk, so the
+    /// leaderboard always reflects the latest results without parsing all of
+    /// them on each call.
+    pub async fn leaderboard(&self) -> Leaderboard {
+        let Some(dir) = self.dir.clone() else {
+            return Default::default();
+        };
+
+        let worlds: Vec<_> = self
+            .worlds
+            .public()
+            .iter()
+            .map(|world| world.id())
+            .collect();
+
+        let rounds = self.rounds.clone();
+
+        task::spawn_blocking(move || {
+            Leaderboard::load(&dir, &worlds, &mut rounds.lock().unwrap())
+        })
+        .await
+        .unwrap_or_default()
+    }
+
     // ---
 
-    pub fn create_session(&self) -> Session {
-        self.sessions.create(&mut rand::thread_rng())
+    /// Creates a new session, optionally authenticated as given player and
+    /// originating from given address.
+    ///
+    /// Note that failing to persist player's data is not considered fatal
+    /// here - worst case, some of their statistics will get lost.
+    pub fn create_session(
+        &self,
+        player: Option<PlayerId>,
+        addr: Option<IpAddr>,
+    ) -> Result<Session> {
+        let player = player.map(|id| self.players.login(id));
+
+        let mut rng = rand::thread_rng();
+        let entry = SessionEntry::new(&mut rng, player.as_ref(), addr);
+
+        Ok(self.sessions.create(
+            &mut rng,
+            entry,
+            self.limits.sessions_per_ip,
+        )?)
+    }
+
+    /// Records a bot upload, checking [`Limits::uploads_per_minute`].
+    pub fn record_upload(&self, sess: &Session) -> Result<(), LimitExceeded> {
+        let limit = self.limits.uploads_per_minute;
+
+        if sess.with(|sess| sess.uploads_mut().hit(Instant::now(), limit)) {
+            Ok(())
+        } else {
+            Err(LimitExceeded::Uploads)
+        }
+    }
+
+    /// Records creation of a private world (a sandbox, a challenge etc.),
+    /// checking [`Limits::private_worlds_per_minute`].
+    pub fn record_private_world(
+        &self,
+        sess: &Session,
+    ) -> Result<(), LimitExceeded> {
+        if let Some(addr) = sess.with(|sess| sess.addr()) {
+            self.private_worlds
+                .hit(addr, self.limits.private_worlds_per_minute)?;
+        }
+
+        Ok(())
     }
 
     pub fn first_session_id(&self) -> SessionId {
@@ -150,12 +299,68 @@ impl Store {
         self.sessions.with(id, f)
     }
 
+    // ---
+
+    pub fn player(&self, id: &PlayerId) -> Option<Player> {
+        self.players.get(id)
+    }
+
+    pub fn players(&self) -> Vec<Player> {
+        self.players.list()
+    }
+
+    pub fn update_player<T>(
+        &self,
+        id: PlayerId,
+        f: impl FnOnce(&mut Player) -> T,
+    ) -> Result<T> {
+        self.players.update(id, f)
+    }
+
+    /// Returns private worlds owned by given player that are still alive.
+    pub fn player_worlds(&self, id: &PlayerId) -> Vec<WorldHandle> {
+        self.players
+            .get(id)
+            .into_iter()
+            .flat_map(|player| player.worlds)
+            .filter_map(|id| self.worlds.get(id))
+            .collect()
+    }
+
     pub fn testing(&self) -> bool {
         self.testing
     }
 
+    /// Loads maps from `<dir>/maps/*.txt`.
+    ///
+    /// Maps that fail to load are skipped (with a warning), so that a single
+    /// malformed file doesn't make the rest unavailable.
+    fn load_maps(dir: &Path) -> Vec<FileTheme> {
+        let Ok(entries) = fs::read_dir(dir.join("maps")) else {
+            return Default::default();
+        };
+
+        let mut maps: Vec<_> = entries
+            .filter_map(|entry| {
+                let path = entry.ok()?.path();
+
+                if path.extension()? != "txt" {
+                    return None;
+                }
+
+                FileTheme::load(&path)
+                    .inspect_err(|err| warn!("couldn't load map: {err:?}"))
+                    .ok()
+            })
+            .collect();
+
+        maps.sort_by_key(|map| map.name());
+        maps
+    }
+
     pub async fn close(&self) -> Result<()> {
         self.worlds.shutdown().await?;
+        self.players.flush().await;
 
         Ok(())
     }
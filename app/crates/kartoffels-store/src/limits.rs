@@ -0,0 +1,158 @@
+use ahash::AHashMap;
+use std::collections::VecDeque;
+use std::error::Error as StdError;
+use std::fmt;
+use std::net::IpAddr;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Caps on resources a single visitor can consume.
+///
+/// Limits tied to an IP address are not enforced for sessions without one -
+/// e.g. those created through tests.
+#[derive(Clone, Debug)]
+pub struct Limits {
+    /// How many sessions can be open at once from a single IP address
+    pub sessions_per_ip: usize,
+
+    /// How many bots a single session can upload within a minute
+    pub uploads_per_minute: usize,
+
+    /// How many private worlds (sandboxes, challenges etc.) can be created
+    /// from a single IP address within a minute
+    pub private_worlds_per_minute: usize,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Self {
+            sessions_per_ip: 16,
+            uploads_per_minute: 20,
+            private_worlds_per_minute: 10,
+        }
+    }
+}
+
+/// Error returned when one of [`Limits`] gets exceeded.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LimitExceeded {
+    Sessions,
+    Uploads,
+    PrivateWorlds,
+}
+
+impl fmt::Display for LimitExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LimitExceeded::Sessions => {
+                write!(f, "there are too many sessions open from your address")
+            }
+            LimitExceeded::Uploads => {
+                write!(f, "you're uploading bots too fast")
+            }
+            LimitExceeded::PrivateWorlds => {
+                write!(f, "you're creating worlds too fast")
+            }
+        }
+    }
+}
+
+impl StdError for LimitExceeded {
+    //
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct PrivateWorldsLimiter {
+    entries: Mutex<AHashMap<IpAddr, RateWindow>>,
+}
+
+impl PrivateWorldsLimiter {
+    pub fn hit(&self, addr: IpAddr, limit: usize) -> Result<(), LimitExceeded> {
+        let now = Instant::now();
+        let mut entries = self.entries.lock().unwrap();
+
+        // Forget addresses that haven't created anything in a while, so that
+        // we don't accumulate them forever
+        entries.retain(|_, window| !window.is_idle(now));
+
+        entries
+            .entry(addr)
+            .or_default()
+            .hit(now, limit)
+            .then_some(())
+            .ok_or(LimitExceeded::PrivateWorlds)
+    }
+}
+
+/// Sliding, one-minute window of events.
+#[derive(Debug, Default)]
+pub(crate) struct RateWindow {
+    hits: VecDeque<Instant>,
+}
+
+impl RateWindow {
+    const LENGTH: Duration = Duration::from_secs(60);
+
+    /// Records an event, returning `false` if there were already `limit`
+    /// events within the window (in which case the event is not recorded).
+    pub fn hit(&mut self, now: Instant, limit: usize) -> bool {
+        self.forget(now);
+
+        if self.hits.len() >= limit {
+            return false;
+        }
+
+        self.hits.push_back(now);
+
+        true
+    }
+
+    fn is_idle(&mut self, now: Instant) -> bool {
+        self.forget(now);
+        self.hits.is_empty()
+    }
+
+    fn forget(&mut self, now: Instant) {
+        while let Some(hit) = self.hits.front()
+            && now.duration_since(*hit) >= Self::LENGTH
+        {
+            self.hits.pop_front();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rate_window() {
+        let mut target = RateWindow::default();
+        let t0 = Instant::now();
+        let sec = |n| t0 + Duration::from_secs(n);
+
+        assert!(target.hit(sec(0), 2));
+        assert!(target.hit(sec(10), 2));
+        assert!(!target.hit(sec(20), 2));
+        assert!(!target.hit(sec(59), 2));
+
+        // First hit is now out of the window
+        assert!(target.hit(sec(60), 2));
+        assert!(!target.hit(sec(61), 2));
+
+        assert!(!target.is_idle(sec(100)));
+        assert!(target.is_idle(sec(120)));
+    }
+
+    #[test]
+    fn private_worlds() {
+        let target = PrivateWorldsLimiter::default();
+        let a1 = IpAddr::from([10, 0, 0, 1]);
+        let a2 = IpAddr::from([10, 0, 0, 2]);
+
+        assert_eq!(Ok(()), target.hit(a1, 2));
+        assert_eq!(Ok(()), target.hit(a1, 2));
+        assert_eq!(Err(LimitExceeded::PrivateWorlds), target.hit(a1, 2));
+        assert_eq!(Ok(()), target.hit(a2, 2));
+    }
+}
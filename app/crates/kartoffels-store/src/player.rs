@@ -0,0 +1,158 @@
+use anyhow::{anyhow, Error};
+use kartoffels_utils::Id;
+use kartoffels_world::prelude::BotId;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::fmt;
+use std::str::FromStr;
+
+/// Persistent identity of someone who's connected through SSH using a public
+/// key.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Player {
+    pub id: PlayerId,
+    pub admin: bool,
+
+    /// Bots uploaded by this player, most recent first
+    pub bots: VecDeque<PlayerBot>,
+
+    /// Private worlds owned by this player
+    pub worlds: Vec<Id>,
+
+    pub stats: PlayerStats,
+}
+
+impl Player {
+    /// How many uploaded bots we remember per player.
+    pub const MAX_BOTS: usize = 256;
+
+    pub(crate) fn new(id: PlayerId) -> Self {
+        Self {
+            id,
+            admin: false,
+            bots: Default::default(),
+            worlds: Default::default(),
+            stats: Default::default(),
+        }
+    }
+
+    pub fn add_bot(&mut self, world: Id, id: BotId) {
+        if self.bots.len() >= Self::MAX_BOTS {
+            self.bots.pop_back();
+        }
+
+        self.bots.push_front(PlayerBot { world, id });
+        self.stats.bots_uploaded += 1;
+    }
+
+    pub fn add_world(&mut self, id: Id) {
+        if !self.worlds.contains(&id) {
+            self.worlds.push(id);
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlayerBot {
+    pub world: Id,
+    pub id: BotId,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlayerStats {
+    pub sessions: u32,
+    pub bots_uploaded: u32,
+}
+
+/// SHA256 fingerprint of player's public key, e.g. `SHA256:q0u/8k...`.
+#[derive(
+    Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(try_from = "String", into = "String")]
+pub struct PlayerId(String);
+
+impl PlayerId {
+    const PREFIX: &str = "SHA256:";
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl FromStr for PlayerId {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let Some(hash) = s.strip_prefix(Self::PREFIX) else {
+            return Err(anyhow!(
+                "player id must start with `{}`",
+                Self::PREFIX
+            ));
+        };
+
+        if hash.is_empty()
+            || !hash
+                .chars()
+                .all(|ch| ch.is_ascii_alphanumeric() || "+/=".contains(ch))
+        {
+            return Err(anyhow!("player id contains forbidden characters"));
+        }
+
+        Ok(Self(s.to_owned()))
+    }
+}
+
+impl TryFrom<String> for PlayerId {
+    type Error = Error;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl From<PlayerId> for String {
+    fn from(id: PlayerId) -> Self {
+        id.0
+    }
+}
+
+impl fmt::Display for PlayerId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_str() {
+        let id = PlayerId::from_str("SHA256:q0u/8k+XzY=").unwrap();
+
+        assert_eq!("SHA256:q0u/8k+XzY=", id.as_str());
+
+        assert_eq!(
+            "player id must start with `SHA256:`",
+            PlayerId::from_str("MD5:1234").unwrap_err().to_string(),
+        );
+
+        assert_eq!(
+            "player id contains forbidden characters",
+            PlayerId::from_str("SHA256:12 34").unwrap_err().to_string(),
+        );
+    }
+
+    #[test]
+    fn add_bot() {
+        let mut target = Player::new("SHA256:1234".parse().unwrap());
+
+        for idx in 0..(Player::MAX_BOTS + 10) {
+            target.add_bot(Id::new(1), BotId::new(idx as u64 + 1));
+        }
+
+        assert_eq!(Player::MAX_BOTS, target.bots.len());
+        assert_eq!(BotId::new(266), target.bots[0].id);
+        assert_eq!(266, target.stats.bots_uploaded);
+    }
+}
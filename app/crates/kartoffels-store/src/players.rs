@@ -0,0 +1,270 @@
+use crate::{Player, PlayerId};
+use ahash::AHashMap;
+use anyhow::{Context, Result};
+use itertools::Itertools;
+use std::io::ErrorKind;
+use std::iter;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use tokio::sync::{mpsc, oneshot};
+use tokio::{fs, task};
+use tracing::{info, warn};
+
+#[derive(Debug, Default)]
+pub struct Players {
+    entries: Arc<Mutex<AHashMap<PlayerId, Player>>>,
+
+    /// Writer task, present if players are persisted on disk - see
+    /// [`Players::write()`]
+    writer: Option<mpsc::UnboundedSender<WriterRequest>>,
+}
+
+impl Players {
+    pub async fn new(dir: Option<&Path>) -> Result<Self> {
+        let Some(dir) = dir else {
+            return Ok(Default::default());
+        };
+
+        let path = dir.join("players.json");
+
+        let entries = match fs::read(&path).await {
+            Ok(entries) => {
+                info!(?path, "loading players");
+
+                serde_json::from_slice::<Vec<Player>>(&entries)
+                    .with_context(|| {
+                        format!("couldn't parse players `{}`", path.display())
+                    })?
+                    .into_iter()
+                    .map(|player| (player.id.clone(), player))
+                    .collect()
+            }
+
+            Err(err) if err.kind() == ErrorKind::NotFound => Default::default(),
+
+            Err(err) => {
+                return Err(err).with_context(|| {
+                    format!("couldn't load players `{}`", path.display())
+                });
+            }
+        };
+
+        let entries = Arc::new(Mutex::new(entries));
+        let (tx, rx) = mpsc::unbounded_channel();
+
+        tokio::spawn(Self::write(path, entries.clone(), rx));
+
+        Ok(Self {
+            entries,
+            writer: Some(tx),
+        })
+    }
+
+    /// Returns given player, creating them if this is the first time we see
+    /// them.
+    pub fn login(&self, id: PlayerId) -> Player {
+        self.with(id, true, |player| {
+            player.stats.sessions += 1;
+            player.clone()
+        })
+        .unwrap()
+    }
+
+    pub fn get(&self, id: &PlayerId) -> Option<Player> {
+        self.entries.lock().unwrap().get(id).cloned()
+    }
+
+    pub fn list(&self) -> Vec<Player> {
+        self.entries
+            .lock()
+            .unwrap()
+            .values()
+            .sorted_by(|a, b| a.id.cmp(&b.id))
+            .cloned()
+            .collect()
+    }
+
+    pub fn update<T>(
+        &self,
+        id: PlayerId,
+        f: impl FnOnce(&mut Player) -> T,
+    ) -> Result<T> {
+        let msg = format!("couldn't find player `{id}`");
+
+        self.with(id, false, f).context(msg)
+    }
+
+    /// Waits until all of the changes made so far are written to disk.
+    pub async fn flush(&self) {
+        let Some(writer) = &self.writer else {
+            return;
+        };
+
+        let (tx, rx) = oneshot::channel();
+
+        if writer.send(WriterRequest::Flush { tx }).is_ok() {
+            _ = rx.await;
+        }
+    }
+
+    fn with<T>(
+        &self,
+        id: PlayerId,
+        create: bool,
+        f: impl FnOnce(&mut Player) -> T,
+    ) -> Option<T> {
+        let mut entries = self.entries.lock().unwrap();
+
+        let player = if create {
+            entries.entry(id.clone()).or_insert_with(|| Player::new(id))
+        } else {
+            entries.get_mut(&id)?
+        };
+
+        let result = f(player);
+
+        if let Some(writer) = &self.writer {
+            _ = writer.send(WriterRequest::Save);
+        }
+
+        Some(result)
+    }
+
+    /// Saves players in the background, so that callers don't have to block
+    /// the executor on disk I/O.
+    ///
+    /// Requests that pile up while we're busy writing get coalesced, so a
+    /// burst of updates results in a single write.
+    async fn write(
+        path: PathBuf,
+        entries: Arc<Mutex<AHashMap<PlayerId, Player>>>,
+        mut rx: mpsc::UnboundedReceiver<WriterRequest>,
+    ) {
+        while let Some(req) = rx.recv().await {
+            let mut flushes = Vec::new();
+            let mut dirty = false;
+
+            for req in [req]
+                .into_iter()
+                .chain(iter::from_fn(|| rx.try_recv().ok()))
+            {
+                match req {
+                    WriterRequest::Save => {
+                        dirty = true;
+                    }
+
+                    WriterRequest::Flush { tx } => {
+                        flushes.push(tx);
+                    }
+                }
+            }
+
+            if dirty {
+                let entries = {
+                    let entries = entries.lock().unwrap();
+
+                    let entries: Vec<_> = entries
+                        .values()
+                        .sorted_by(|a, b| a.id.cmp(&b.id))
+                        .collect();
+
+                    serde_json::to_vec(&entries)
+                };
+
+                let path = path.clone();
+
+                let result: Result<()> = try {
+                    let entries = entries?;
+
+                    task::spawn_blocking(move || Self::save(&path, entries))
+                        .await??;
+                };
+
+                if let Err(err) = result {
+                    warn!("couldn't save players: {err:?}");
+                }
+            }
+
+            for tx in flushes {
+                _ = tx.send(());
+            }
+        }
+    }
+
+    fn save(path: &Path, entries: Vec<u8>) -> Result<()> {
+        let tmp_path = path.with_extension("json.new");
+
+        // Write-and-rename, so that we don't end up with a half-written file
+        // if something goes wrong
+        std::fs::write(&tmp_path, entries)
+            .and_then(|_| std::fs::rename(&tmp_path, path))
+            .with_context(|| {
+                format!("couldn't write players `{}`", path.display())
+            })?;
+
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+enum WriterRequest {
+    Save,
+    Flush { tx: oneshot::Sender<()> },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kartoffels_utils::Id;
+    use kartoffels_world::prelude::BotId;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn smoke() {
+        let dir = TempDir::new().unwrap();
+        let p1: PlayerId = "SHA256:1111".parse().unwrap();
+        let p2: PlayerId = "SHA256:2222".parse().unwrap();
+
+        {
+            let target = Players::new(Some(dir.path())).await.unwrap();
+
+            assert!(target.get(&p1).is_none());
+            assert!(target.update(p1.clone(), |_| ()).is_err());
+
+            let player = target.login(p1.clone());
+
+            assert_eq!(p1, player.id);
+            assert!(!player.admin);
+            assert_eq!(1, player.stats.sessions);
+
+            target.login(p2.clone());
+
+            target
+                .update(p1.clone(), |player| {
+                    player.admin = true;
+                    player.add_bot(Id::new(1), BotId::new(2));
+                    player.add_world(Id::new(3));
+                })
+                .unwrap();
+
+            target.flush().await;
+        }
+
+        // ---
+
+        let target = Players::new(Some(dir.path())).await.unwrap();
+        let player = target.login(p1.clone());
+
+        assert!(player.admin);
+        assert_eq!(2, player.stats.sessions);
+        assert_eq!(1, player.stats.bots_uploaded);
+        assert_eq!(BotId::new(2), player.bots[0].id);
+        assert_eq!(vec![Id::new(3)], player.worlds);
+
+        let players = target.list();
+
+        assert_eq!(2, players.len());
+        assert_eq!(p1, players[0].id);
+        assert_eq!(p2, players[1].id);
+    }
+}
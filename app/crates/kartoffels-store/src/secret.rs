@@ -1,5 +1,6 @@
 use anyhow::{anyhow, Error, Result};
 use std::str::FromStr;
+use subtle::ConstantTimeEq;
 
 #[derive(Clone, Debug)]
 pub struct Secret(String);
@@ -10,6 +11,13 @@ impl Secret {
     pub fn as_str(&self) -> &str {
         &self.0
     }
+
+    /// Checks whether given string matches the secret, in constant time - so
+    /// that the secret can't be guessed by measuring how long it takes us to
+    /// reject it.
+    pub fn matches(&self, other: &str) -> bool {
+        self.0.as_bytes().ct_eq(other.as_bytes()).into()
+    }
 }
 
 impl FromStr for Secret {
@@ -1,7 +1,10 @@
+use crate::{Player, PlayerId, RateWindow};
 use derivative::Derivative;
 use kartoffels_utils::Id;
+use rand::{Rng, RngCore};
 use serde::{Deserialize, Serialize};
 use std::fmt;
+use std::net::IpAddr;
 use std::sync::{Arc, Mutex};
 use tokio::sync::oneshot;
 
@@ -48,10 +51,66 @@ impl Drop for Session {
 #[derive(Debug, Default)]
 pub struct SessionEntry {
     role: SessionRole,
+    player: Option<PlayerId>,
+    owner: Arc<str>,
+    addr: Option<IpAddr>,
     upload: Option<oneshot::Sender<Vec<u8>>>,
+    uploads: RateWindow,
 }
 
 impl SessionEntry {
+    pub(crate) fn new(
+        rng: &mut impl RngCore,
+        player: Option<&Player>,
+        addr: Option<IpAddr>,
+    ) -> Self {
+        let role = if player.is_some_and(|player| player.admin) {
+            SessionRole::Admin
+        } else {
+            SessionRole::User
+        };
+
+        // Anonymous sessions get a random key instead of reusing `SessionId`,
+        // since the latter is a credential (it allows to upload bots)
+        let owner = match player {
+            Some(player) => player.id.as_str().into(),
+            None => format!("session:{}", rng.gen::<Id>()).into(),
+        };
+
+        Self {
+            role,
+            player: player.map(|player| player.id.clone()),
+            owner,
+            addr,
+            upload: None,
+            uploads: Default::default(),
+        }
+    }
+
+    /// Returns the player behind this session, if they've authenticated using
+    /// a public key.
+    pub fn player(&self) -> Option<&PlayerId> {
+        self.player.as_ref()
+    }
+
+    /// Returns the key bots uploaded within this session are tagged with, see
+    /// [`kartoffels_world::prelude::CreateBotRequest::owner()`].
+    ///
+    /// For players it's stable across sessions, for everybody else it lasts
+    /// until the session is closed.
+    pub fn owner(&self) -> &Arc<str> {
+        &self.owner
+    }
+
+    /// Returns the address this session has been opened from.
+    pub fn addr(&self) -> Option<IpAddr> {
+        self.addr
+    }
+
+    pub(crate) fn uploads_mut(&mut self) -> &mut RateWindow {
+        &mut self.uploads
+    }
+
     pub fn is_admin(&self) -> bool {
         self.role == SessionRole::Admin
     }
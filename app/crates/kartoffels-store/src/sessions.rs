@@ -1,29 +1,54 @@
-use crate::{Session, SessionEntry, SessionId};
+use crate::{LimitExceeded, Session, SessionEntry, SessionId};
 use ahash::AHashMap;
 use rand::{Rng, RngCore};
 use std::collections::hash_map;
+use std::net::IpAddr;
 use std::sync::{Arc, Mutex};
 use tracing::info;
 
 #[derive(Debug, Default)]
 pub struct Sessions {
     entries: Arc<Mutex<AHashMap<SessionId, Arc<Mutex<SessionEntry>>>>>,
+
+    /// Number of sessions open from each address
+    addrs: Arc<Mutex<AHashMap<IpAddr, usize>>>,
 }
 
 impl Sessions {
-    pub fn create(&self, rng: &mut impl RngCore) -> Session {
+    /// Creates a new session, making sure there are at most `max_per_addr`
+    /// sessions open from the entry's address.
+    pub fn create(
+        &self,
+        rng: &mut impl RngCore,
+        entry: SessionEntry,
+        max_per_addr: usize,
+    ) -> Result<Session, LimitExceeded> {
+        let addr = entry.addr();
+
+        if let Some(addr) = addr {
+            let mut addrs = self.addrs.lock().unwrap();
+            let count = addrs.entry(addr).or_default();
+
+            // Checked under the same lock as the increment, so that concurrent
+            // connections can't sneak past the limit
+            if *count >= max_per_addr {
+                return Err(LimitExceeded::Sessions);
+            }
+
+            *count += 1;
+        }
+
         let (id, entry) = {
             let mut entries = self.entries.lock().unwrap();
+            let entry = Arc::new(Mutex::new(entry));
 
             loop {
                 let id = SessionId(rng.gen());
 
-                if let hash_map::Entry::Vacant(entry) = entries.entry(id) {
+                if let hash_map::Entry::Vacant(slot) = entries.entry(id) {
                     info!(?id, "session created");
 
-                    let entry = entry
-                        .insert(Arc::new(Mutex::new(SessionEntry::default())))
-                        .clone();
+                    slot.insert(entry.clone());
 
                     break (id, entry);
                 }
@@ -31,11 +56,24 @@ impl Sessions {
         };
 
         let entries = self.entries.clone();
+        let addrs = self.addrs.clone();
 
-        Session::new(id, entry, move || {
+        Ok(Session::new(id, entry, move || {
             info!(?id, "session destroyed");
             entries.lock().unwrap().remove(&id);
-        })
+
+            if let Some(addr) = addr {
+                let mut addrs = addrs.lock().unwrap();
+
+                if let Some(count) = addrs.get_mut(&addr) {
+                    *count -= 1;
+
+                    if *count == 0 {
+                        addrs.remove(&addr);
+                    }
+                }
+            }
+        }))
     }
 
     pub fn first_id(&self) -> Option<SessionId> {
@@ -68,9 +106,15 @@ mod tests {
         let target = Sessions::default();
         let mut rng = ChaCha8Rng::from_seed(Default::default());
 
-        let s1 = target.create(&mut rng);
-        let s2 = target.create(&mut rng);
-        let s3 = target.create(&mut rng);
+        let s1 = target
+            .create(&mut rng, Default::default(), usize::MAX)
+            .unwrap();
+        let s2 = target
+            .create(&mut rng, Default::default(), usize::MAX)
+            .unwrap();
+        let s3 = target
+            .create(&mut rng, Default::default(), usize::MAX)
+            .unwrap();
 
         assert_eq!(
             SessionId(Id::from_str("d640-5f89-2fef-003e").unwrap()),
@@ -131,4 +175,48 @@ mod tests {
         assert_eq!(None, target.with(s2_id, |s| s.is_admin()));
         assert_eq!(None, target.with(s3_id, |s| s.is_admin()));
     }
+
+    #[test]
+    fn count_by_addr() {
+        let target = Sessions::default();
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let a1 = IpAddr::from([10, 0, 0, 1]);
+        let a2 = IpAddr::from([10, 0, 0, 2]);
+
+        let mut create = |addr| {
+            let entry = SessionEntry::new(&mut rng, None, addr);
+
+            target.create(&mut rng, entry, 2)
+        };
+
+        let count = |addr| {
+            target
+                .addrs
+                .lock()
+                .unwrap()
+                .get(&addr)
+                .copied()
+                .unwrap_or_default()
+        };
+
+        let s1 = create(Some(a1)).unwrap();
+        let _s2 = create(Some(a1)).unwrap();
+        let s3 = create(Some(a2)).unwrap();
+        let _s4 = create(None).unwrap();
+
+        assert_eq!(2, count(a1));
+        assert_eq!(1, count(a2));
+        assert_eq!(Err(LimitExceeded::Sessions), create(Some(a1)).map(drop));
+
+        drop(s1);
+
+        assert_eq!(1, count(a1));
+        assert!(create(Some(a1)).is_ok());
+        assert_eq!(1, count(a1));
+
+        drop(s3);
+
+        assert_eq!(0, count(a2));
+        assert!(!target.addrs.lock().unwrap().contains_key(&a2));
+    }
 }
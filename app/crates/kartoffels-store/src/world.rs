@@ -1,13 +1,26 @@
+use crate::PlayerId;
 use anyhow::{anyhow, Error};
+use serde::{Deserialize, Serialize};
 use std::fmt;
 use std::str::FromStr;
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum WorldType {
     Public,
     Private,
 }
 
+/// Metadata stored next to world's file, in `<id>.meta`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct WorldMeta {
+    pub ty: WorldType,
+
+    /// Player who owns this world, if any - only private worlds have owners
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub owner: Option<PlayerId>,
+}
+
 impl fmt::Display for WorldType {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
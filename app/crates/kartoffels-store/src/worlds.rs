@@ -1,4 +1,4 @@
-use crate::WorldType;
+use crate::{PlayerId, WorldMeta, WorldType};
 use ahash::AHashMap;
 use anyhow::{anyhow, Context, Result};
 use arc_swap::ArcSwap;
@@ -6,6 +6,7 @@ use itertools::Itertools;
 use kartoffels_utils::{ArcSwapExt, Id};
 use kartoffels_world::prelude::{Config as WorldConfig, Handle as WorldHandle};
 use std::collections::hash_map;
+use std::io::ErrorKind;
 use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
@@ -64,12 +65,18 @@ impl Worlds {
                     .parse()
                     .context("couldn't extract world id from path")?;
 
+                let meta = Self::load_meta(&meta_path(dir, id))
+                    .await
+                    .context("couldn't load world's metadata")?;
+
                 let handle = kartoffels_world::resume(id, &path)?;
 
                 entries.insert(
                     id,
                     WorldEntry {
-                        ty: WorldType::Public,
+                        ty: meta.ty,
+                        owner: meta.owner,
+                        persistent: true,
                         handle: Some(handle),
                     },
                 );
@@ -83,21 +90,56 @@ impl Worlds {
         Ok(entries)
     }
 
-    pub fn create(
+    async fn load_meta(path: &Path) -> Result<WorldMeta> {
+        match fs::read(path).await {
+            Ok(meta) => Ok(serde_json::from_slice(&meta)?),
+
+            // Worlds created before we've started to keep metadata around
+            // were all public
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(WorldMeta {
+                ty: WorldType::Public,
+                owner: None,
+            }),
+
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    pub async fn create(
         &self,
         testing: bool,
         dir: Option<&Path>,
         ty: WorldType,
+        owner: Option<PlayerId>,
+        persistent: bool,
         config: WorldConfig,
     ) -> Result<WorldHandle> {
-        debug!(?ty, ?config, "creating world");
+        debug!(?ty, ?owner, ?persistent, ?config, "creating world");
 
         assert!(config.id.is_none());
         assert!(config.path.is_none());
 
-        let id = self.create_alloc(testing, ty)?;
-        let config = self.create_config(dir, ty, config, id);
-        let handle = self.create_spawn(ty, config);
+        let dir = dir.filter(|_| persistent);
+        let meta = WorldMeta { ty, owner };
+        let id = self.create_alloc(testing, &meta, persistent)?;
+
+        if let Err(err) = self.create_meta(dir, &meta, id).await {
+            self.create_rollback(None, id).await;
+
+            return Err(err);
+        }
+
+        let config = self.create_config(dir, config, id);
+
+        let handle = match self.create_spawn(ty, persistent, config) {
+            Ok(handle) => handle,
+
+            Err(err) => {
+                self.create_rollback(dir, id).await;
+
+                return Err(err);
+            }
+        };
 
         if let WorldType::Public = ty {
             self.rebuild_public_idx();
@@ -108,7 +150,12 @@ impl Worlds {
         Ok(handle)
     }
 
-    fn create_alloc(&self, testing: bool, ty: WorldType) -> Result<Id> {
+    fn create_alloc(
+        &self,
+        testing: bool,
+        meta: &WorldMeta,
+        persistent: bool,
+    ) -> Result<Id> {
         let mut id = None;
 
         self.entries.try_rcu(|entries| {
@@ -128,7 +175,12 @@ impl Worlds {
                 };
 
                 if let hash_map::Entry::Vacant(entry) = entries.entry(id) {
-                    entry.insert(WorldEntry { ty, handle: None });
+                    entry.insert(WorldEntry {
+                        ty: meta.ty,
+                        owner: meta.owner.clone(),
+                        persistent,
+                        handle: None,
+                    });
 
                     break id;
                 }
@@ -140,25 +192,45 @@ impl Worlds {
         Ok(id.unwrap())
     }
 
+    async fn create_meta(
+        &self,
+        dir: Option<&Path>,
+        meta: &WorldMeta,
+        id: Id,
+    ) -> Result<()> {
+        let Some(dir) = dir else {
+            return Ok(());
+        };
+
+        let path = meta_path(dir, id);
+        let meta = serde_json::to_vec(meta)?;
+
+        fs::write(&path, meta).await.with_context(|| {
+            format!("couldn't write world's metadata `{}`", path.display())
+        })?;
+
+        Ok(())
+    }
+
     fn create_config(
         &self,
         dir: Option<&Path>,
-        ty: WorldType,
         mut config: WorldConfig,
         id: Id,
     ) -> WorldConfig {
         config.id = Some(id);
-
-        if let WorldType::Public = ty {
-            config.path = dir.map(|dir| path(dir, id));
-        }
-
+        config.path = dir.map(|dir| path(dir, id));
         config
     }
 
-    fn create_spawn(&self, ty: WorldType, config: WorldConfig) -> WorldHandle {
+    fn create_spawn(
+        &self,
+        ty: WorldType,
+        persistent: bool,
+        config: WorldConfig,
+    ) -> Result<WorldHandle> {
         let id = config.id.unwrap();
-        let handle = kartoffels_world::create(config);
+        let handle = kartoffels_world::create(config)?;
 
         self.entries.rcu(|entries| {
             let mut entries = (**entries).clone();
@@ -167,10 +239,10 @@ impl Worlds {
             entries
         });
 
-        match ty {
-            WorldType::Public => handle,
+        let handle = match (ty, persistent) {
+            (WorldType::Public, _) | (WorldType::Private, true) => handle,
 
-            WorldType::Private => handle.on_last_drop({
+            (WorldType::Private, false) => handle.on_last_drop({
                 let entries = self.entries.clone();
 
                 move || {
@@ -184,6 +256,23 @@ impl Worlds {
                     });
                 }
             }),
+        };
+
+        Ok(handle)
+    }
+
+    /// Undoes [`Self::create_alloc()`] and [`Self::create_meta()`] for a world
+    /// that couldn't be created.
+    async fn create_rollback(&self, dir: Option<&Path>, id: Id) {
+        self.entries.rcu(|entries| {
+            let mut entries = (**entries).clone();
+
+            entries.remove(&id);
+            entries
+        });
+
+        if let Some(dir) = dir {
+            _ = fs::remove_file(meta_path(dir, id)).await;
         }
     }
 
@@ -267,7 +356,7 @@ impl Worlds {
             handle.shutdown().await?;
         }
 
-        if let WorldType::Public = entry.ty
+        if entry.persistent
             && let Some(dir) = dir
         {
             let path = path(dir, id);
@@ -275,6 +364,21 @@ impl Worlds {
             fs::remove_file(&path).await.with_context(|| {
                 format!("couldn't remove world's file `{}`", path.display())
             })?;
+
+            let path = meta_path(dir, id);
+
+            match fs::remove_file(&path).await {
+                Err(err) if err.kind() != ErrorKind::NotFound => {
+                    return Err(err).with_context(|| {
+                        format!(
+                            "couldn't remove world's metadata `{}`",
+                            path.display()
+                        )
+                    });
+                }
+
+                _ => (),
+            }
         }
 
         Ok(())
@@ -288,6 +392,8 @@ impl Worlds {
 
                 let val = WorldEntry {
                     ty: WorldType::Public,
+                    owner: None,
+                    persistent: true,
                     handle: Some(handle),
                 };
 
@@ -311,6 +417,14 @@ impl Worlds {
             .collect()
     }
 
+    pub fn get(&self, id: Id) -> Option<WorldHandle> {
+        self.entries.load().get(&id)?.handle.clone()
+    }
+
+    pub fn owner(&self, id: Id) -> Option<PlayerId> {
+        self.entries.load().get(&id)?.owner.clone()
+    }
+
     pub fn public(&self) -> Arc<Vec<WorldHandle>> {
         self.public_idx.load_full()
     }
@@ -326,7 +440,7 @@ impl Worlds {
 
     pub async fn shutdown(&self) -> Result<()> {
         for entry in self.entries.load().values() {
-            if let WorldType::Public = entry.ty
+            if entry.persistent
                 && let Some(handle) = &entry.handle
             {
                 handle.shutdown().await?;
@@ -346,6 +460,8 @@ impl Worlds {
 #[derive(Clone, Debug)]
 struct WorldEntry {
     ty: WorldType,
+    owner: Option<PlayerId>,
+    persistent: bool,
     handle: Option<WorldHandle>,
 }
 
@@ -353,6 +469,10 @@ fn path(dir: &Path, id: Id) -> PathBuf {
     dir.join(id.to_string()).with_extension("world")
 }
 
+fn meta_path(dir: &Path, id: Id) -> PathBuf {
+    dir.join(id.to_string()).with_extension("meta")
+}
+
 fn build_public_idx(entries: &AHashMap<Id, WorldEntry>) -> Vec<WorldHandle> {
     entries
         .values()
@@ -365,15 +485,15 @@ fn build_public_idx(entries: &AHashMap<Id, WorldEntry>) -> Vec<WorldHandle> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::array;
+    use kartoffels_world::prelude::Clock;
 
     #[tokio::test]
     async fn smoke() {
         let target = Worlds::new(None).await.unwrap();
 
-        let [h1, h2, h3, h4] = array::from_fn(|idx| {
-            let idx = idx + 1;
+        let mut handles = Vec::new();
 
+        for idx in 1..=4 {
             let ty = if idx % 2 == 0 {
                 WorldType::Public
             } else {
@@ -385,8 +505,15 @@ mod tests {
                 ..Default::default()
             };
 
-            target.create(true, None, ty, config).unwrap()
-        });
+            handles.push(
+                target
+                    .create(true, None, ty, None, false, config)
+                    .await
+                    .unwrap(),
+            );
+        }
+
+        let [h1, h2, h3, h4] = handles.try_into().unwrap();
 
         assert_eq!(h1.id(), Id::new(1));
         assert_eq!(h2.id(), Id::new(2));
@@ -452,4 +579,55 @@ mod tests {
 
         assert_eq!(vec![(WorldType::Public, h4.id())], list(None));
     }
+
+    #[tokio::test]
+    async fn persistence() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = Worlds::new(Some(dir.path())).await.unwrap();
+
+        let owner: PlayerId = "SHA256:1111".parse().unwrap();
+
+        let create = |ty: WorldType,
+                      owner: Option<PlayerId>,
+                      persistent: bool| {
+            let config = WorldConfig {
+                clock: Clock::manual(),
+                ..Default::default()
+            };
+
+            target.create(true, Some(dir.path()), ty, owner, persistent, config)
+        };
+
+        let h1 = create(WorldType::Public, None, true).await.unwrap();
+        let h2 = create(WorldType::Private, Some(owner.clone()), true)
+            .await
+            .unwrap();
+        let _h3 = create(WorldType::Private, None, false).await.unwrap();
+
+        target.shutdown().await.unwrap();
+
+        // ---
+
+        let target = Worlds::new(Some(dir.path())).await.unwrap();
+
+        let actual: Vec<_> = target
+            .list(None)
+            .into_iter()
+            .map(|(ty, handle)| (ty, handle.id()))
+            .collect();
+
+        let expected =
+            vec![(WorldType::Public, h1.id()), (WorldType::Private, h2.id())];
+
+        assert_eq!(expected, actual);
+        assert_eq!(None, target.owner(h1.id()));
+        assert_eq!(Some(owner), target.owner(h2.id()));
+
+        // ---
+
+        target.delete(Some(dir.path()), h2.id()).await.unwrap();
+
+        assert!(!path(dir.path(), h2.id()).exists());
+        assert!(!meta_path(dir.path(), h2.id()).exists());
+    }
 }
@@ -1,3 +1,5 @@
+mod run_tournament;
 mod world_to_json;
 
+pub use self::run_tournament::*;
 pub use self::world_to_json::*;
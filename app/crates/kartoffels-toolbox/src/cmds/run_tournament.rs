@@ -0,0 +1,235 @@
+use anyhow::{Context, Result};
+use clap::Parser;
+use kartoffels_world::prelude::*;
+use prettytable::{row, Table};
+use serde::Serialize;
+use std::cmp::Reverse;
+use std::fs;
+use std::path::PathBuf;
+
+/// Runs a couple of headless matches between given bots and prints their
+/// ranking.
+#[derive(Debug, Parser)]
+pub struct RunTournamentCmd {
+    /// Paths to bots' ELF files
+    #[clap(required = true)]
+    bots: Vec<PathBuf>,
+
+    #[clap(long, default_value = "arena:radius=20")]
+    theme: Theme,
+
+    #[clap(
+        long,
+        default_value = "auto-respawn=true,max-alive-bots=64,max-queued-bots=64"
+    )]
+    policy: Policy,
+
+    /// Number of matches to run
+    #[clap(long, default_value_t = 8)]
+    matches: u32,
+
+    /// Number of ticks each match lasts
+    #[clap(long, default_value_t = 1_000_000)]
+    ticks: u32,
+
+    /// Seed from which seeds of particular matches are derived
+    #[clap(long, default_value_t = 0)]
+    seed: u64,
+
+    /// Print the results as JSON instead of a table
+    #[clap(long)]
+    json: bool,
+}
+
+impl RunTournamentCmd {
+    pub(crate) fn run(self) -> Result<()> {
+        let fws: Vec<_> = self
+            .bots
+            .iter()
+            .map(|path| {
+                fs::read(path).with_context(|| {
+                    format!("couldn't read {}", path.display())
+                })
+            })
+            .collect::<Result<_>>()?;
+
+        let entries = self.run_matches(&fws)?;
+
+        if self.json {
+            println!("{}", serde_json::to_string_pretty(&entries)?);
+        } else {
+            let mut table = Table::init(vec![]);
+
+            table.set_titles(row!["#", "bot", "wins", "score", "deaths"]);
+
+            for (place, entry) in entries.iter().enumerate() {
+                table.add_row(row![
+                    place + 1,
+                    entry.bot,
+                    entry.wins,
+                    entry.score,
+                    entry.deaths
+                ]);
+            }
+
+            table.printstd();
+        }
+
+        Ok(())
+    }
+
+    fn run_matches(&self, fws: &[Vec<u8>]) -> Result<Vec<Entry>> {
+        let mut entries: Vec<_> = self
+            .bots
+            .iter()
+            .map(|path| Entry {
+                bot: path.display().to_string(),
+                score: 0,
+                wins: 0,
+                deaths: 0,
+            })
+            .collect();
+
+        let rt = tokio::runtime::Builder::new_multi_thread()
+            .enable_all()
+            .build()?;
+
+        for idx in 0..self.matches {
+            let results = rt
+                .block_on(self.run_match(idx, fws))
+                .with_context(|| format!("couldn't run match #{idx}"))?;
+
+            let best = results.iter().map(|res| res.score).max();
+
+            for (entry, res) in entries.iter_mut().zip(results) {
+                entry.score += res.score;
+                entry.deaths += res.deaths;
+
+                if res.score > 0 && Some(res.score) == best {
+                    entry.wins += 1;
+                }
+            }
+        }
+
+        entries.sort_by_key(|entry| {
+            (Reverse(entry.wins), Reverse(entry.score), entry.deaths)
+        });
+
+        Ok(entries)
+    }
+
+    async fn run_match(
+        &self,
+        idx: u32,
+        fws: &[Vec<u8>],
+    ) -> Result<Vec<MatchResult>> {
+        let seed = {
+            let mut seed = [0; 32];
+
+            seed[0..8].copy_from_slice(&self.seed.to_le_bytes());
+            seed[8..12].copy_from_slice(&idx.to_le_bytes());
+            seed
+        };
+
+        let world = kartoffels_world::create(Config {
+            clock: Clock::Unlimited,
+            events: false,
+            id: None,
+            name: format!("match-{idx}"),
+            path: None,
+            policy: self.policy.clone(),
+            seed: Some(seed),
+            sequential: false,
+            theme: Some(self.theme.clone()),
+        })?;
+
+        // Pause the world so that all bots get spawned at the same time -
+        // `tick()` resumes it and then pauses it again once the match is over
+        world.pause().await?;
+
+        let mut ids = Vec::new();
+
+        for fw in fws {
+            ids.push(
+                world.create_bot(CreateBotRequest::new(fw.clone())).await?,
+            );
+        }
+
+        world.tick(self.ticks).await?;
+
+        // Snapshots are sent periodically, so wait for one that's been taken
+        // after the world got paused
+        let version = world.version();
+        let mut snapshots = world.snapshots();
+
+        let snapshot = loop {
+            let snapshot = snapshots.next().await?;
+
+            if snapshot.version > version {
+                break snapshot;
+            }
+        };
+
+        world.shutdown().await?;
+
+        // Scores are summed from lives rather than taken from stats, since
+        // stats get refreshed only once a second
+        let results = ids
+            .into_iter()
+            .map(|id| {
+                let lives = snapshot.lives.get(id);
+
+                MatchResult {
+                    score: lives
+                        .map(|lives| lives.iter().map(|life| life.score).sum())
+                        .unwrap_or_default(),
+                    deaths: lives.map(|lives| lives.len).unwrap_or_default(),
+                }
+            })
+            .collect();
+
+        Ok(results)
+    }
+}
+
+#[derive(Debug)]
+struct MatchResult {
+    score: u32,
+    deaths: u32,
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize)]
+struct Entry {
+    bot: String,
+    wins: u32,
+    score: u32,
+    deaths: u32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kartoffels_prefabs::{DUMMY, ROBERTO};
+
+    #[test]
+    fn determinism() {
+        let cmd = RunTournamentCmd {
+            bots: ["a", "b", "c", "d", "e"].map(Into::into).to_vec(),
+            theme: "arena:radius=6".parse().unwrap(),
+            policy: "auto-respawn=true,max-alive-bots=64,max-queued-bots=64"
+                .parse()
+                .unwrap(),
+            matches: 2,
+            ticks: 50_000,
+            seed: 1234,
+            json: false,
+        };
+
+        let fws =
+            [ROBERTO, ROBERTO, ROBERTO, ROBERTO, DUMMY].map(<[u8]>::to_vec);
+        let actual = cmd.run_matches(&fws).unwrap();
+
+        assert!(actual.iter().any(|entry| entry.score > 0));
+        assert_eq!(actual, cmd.run_matches(&fws).unwrap());
+    }
+}
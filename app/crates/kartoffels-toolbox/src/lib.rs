@@ -6,12 +6,14 @@ use clap::Parser;
 
 #[derive(Debug, Parser)]
 pub enum Cmd {
+    RunTournament(Box<RunTournamentCmd>),
     WorldToJson(WorldToJsonCmd),
 }
 
 impl Cmd {
     pub fn run(self) -> Result<()> {
         match self {
+            Cmd::RunTournament(cmd) => cmd.run(),
             Cmd::WorldToJson(cmd) => cmd.run(),
         }
     }
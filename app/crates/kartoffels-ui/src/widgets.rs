@@ -6,6 +6,7 @@ mod input;
 mod msg;
 mod spinner;
 mod term;
+mod virtual_term;
 mod vtable;
 
 pub use self::backdrop::*;
@@ -16,6 +17,7 @@ pub use self::input::*;
 pub use self::msg::*;
 pub use self::spinner::*;
 pub use self::term::*;
+pub use self::virtual_term::*;
 pub use self::vtable::*;
 use crate::Ui;
 use ratatui::widgets::Widget;
@@ -0,0 +1,339 @@
+use crate::{Ui, UiWidget};
+use ratatui::layout::Position;
+use ratatui::style::{Color, Modifier, Style};
+use termwiz::cell::{Intensity, Underline};
+use termwiz::color::ColorSpec;
+use termwiz::escape::csi::{Cursor, Edit, EraseInDisplay, EraseInLine, Sgr};
+use termwiz::escape::parser::Parser;
+use termwiz::escape::{Action, ControlCode, CSI};
+
+/// Virtual terminal, interpreting a subset of ANSI escape sequences.
+///
+/// Supported are:
+///
+/// - colors and text attributes (`ESC [ ... m`),
+/// - cursor movement (`ESC [ H`, `ESC [ A` etc.),
+/// - erasing the screen and lines (`ESC [ J`, `ESC [ K`).
+///
+/// Other sequences are ignored. Line feed moves the cursor to the beginning of
+/// the next line (as if it was preceded by carriage return), since that's what
+/// firmware's `println!()` relies on; once the cursor goes past the last line,
+/// the screen gets scrolled up.
+#[derive(Clone, Debug)]
+pub struct VirtualTerm {
+    width: u16,
+    height: u16,
+    cells: Vec<(char, Style)>,
+    cursor: Position,
+    saved_cursor: Position,
+    style: Style,
+}
+
+impl VirtualTerm {
+    const TAB_WIDTH: u16 = 8;
+
+    pub fn new(width: u16, height: u16) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![Self::BLANK; (width as usize) * (height as usize)],
+            cursor: Position::default(),
+            saved_cursor: Position::default(),
+            style: Style::default(),
+        }
+    }
+
+    pub fn write(&mut self, s: &str) {
+        if self.width == 0 || self.height == 0 {
+            return;
+        }
+
+        Parser::new().parse(s.as_bytes(), |action| {
+            self.apply(action);
+        });
+    }
+
+    fn apply(&mut self, action: Action) {
+        match action {
+            Action::Print(ch) => {
+                self.print(ch);
+            }
+
+            Action::PrintString(s) => {
+                for ch in s.chars() {
+                    self.print(ch);
+                }
+            }
+
+            Action::Control(ControlCode::LineFeed) => {
+                self.cursor.x = 0;
+                self.line_feed();
+            }
+
+            Action::Control(ControlCode::CarriageReturn) => {
+                self.cursor.x = 0;
+            }
+
+            Action::Control(ControlCode::Backspace) => {
+                self.cursor.x = self.cursor.x.saturating_sub(1);
+            }
+
+            Action::Control(ControlCode::HorizontalTab) => {
+                let x = (self.cursor.x / Self::TAB_WIDTH + 1) * Self::TAB_WIDTH;
+
+                self.cursor.x = x.min(self.width - 1);
+            }
+
+            Action::CSI(CSI::Sgr(sgr)) => {
+                self.apply_sgr(sgr);
+            }
+
+            Action::CSI(CSI::Cursor(cursor)) => {
+                self.apply_cursor(cursor);
+            }
+
+            Action::CSI(CSI::Edit(edit)) => {
+                self.apply_edit(edit);
+            }
+
+            _ => (),
+        }
+    }
+
+    fn apply_sgr(&mut self, sgr: Sgr) {
+        match sgr {
+            Sgr::Reset => {
+                self.style = Style::default();
+            }
+
+            Sgr::Intensity(Intensity::Bold) => {
+                self.style = self
+                    .style
+                    .add_modifier(Modifier::BOLD)
+                    .remove_modifier(Modifier::DIM);
+            }
+
+            Sgr::Intensity(Intensity::Half) => {
+                self.style = self
+                    .style
+                    .add_modifier(Modifier::DIM)
+                    .remove_modifier(Modifier::BOLD);
+            }
+
+            Sgr::Intensity(Intensity::Normal) => {
+                self.style =
+                    self.style.remove_modifier(Modifier::BOLD | Modifier::DIM);
+            }
+
+            Sgr::Underline(Underline::None) => {
+                self.style = self.style.remove_modifier(Modifier::UNDERLINED);
+            }
+
+            Sgr::Underline(_) => {
+                self.style = self.style.add_modifier(Modifier::UNDERLINED);
+            }
+
+            Sgr::Italic(on) => {
+                self.toggle(Modifier::ITALIC, on);
+            }
+
+            Sgr::Inverse(on) => {
+                self.toggle(Modifier::REVERSED, on);
+            }
+
+            Sgr::StrikeThrough(on) => {
+                self.toggle(Modifier::CROSSED_OUT, on);
+            }
+
+            Sgr::Foreground(color) => {
+                self.style.fg = Self::color(color);
+            }
+
+            Sgr::Background(color) => {
+                self.style.bg = Self::color(color);
+            }
+
+            _ => (),
+        }
+    }
+
+    fn apply_cursor(&mut self, cursor: Cursor) {
+        let Position { x, y } = self.cursor;
+
+        match cursor {
+            Cursor::Position { line, col }
+            | Cursor::CharacterAndLinePosition { line, col } => {
+                self.move_to(col.as_zero_based(), line.as_zero_based());
+            }
+
+            Cursor::CharacterAbsolute(col)
+            | Cursor::CharacterPositionAbsolute(col) => {
+                self.move_to(col.as_zero_based(), y as u32);
+            }
+
+            Cursor::LinePositionAbsolute(line) => {
+                self.move_to(x as u32, line.saturating_sub(1));
+            }
+
+            Cursor::Up(n) | Cursor::LinePositionBackward(n) => {
+                self.move_to(x as u32, (y as u32).saturating_sub(n));
+            }
+
+            Cursor::Down(n) | Cursor::LinePositionForward(n) => {
+                self.move_to(x as u32, (y as u32).saturating_add(n));
+            }
+
+            Cursor::Left(n) | Cursor::CharacterPositionBackward(n) => {
+                self.move_to((x as u32).saturating_sub(n), y as u32);
+            }
+
+            Cursor::Right(n) | Cursor::CharacterPositionForward(n) => {
+                self.move_to((x as u32).saturating_add(n), y as u32);
+            }
+
+            Cursor::NextLine(n) => {
+                self.move_to(0, (y as u32).saturating_add(n));
+            }
+
+            Cursor::PrecedingLine(n) => {
+                self.move_to(0, (y as u32).saturating_sub(n));
+            }
+
+            Cursor::SaveCursor => {
+                self.saved_cursor = self.cursor;
+            }
+
+            Cursor::RestoreCursor => {
+                self.cursor = self.saved_cursor;
+            }
+
+            _ => (),
+        }
+    }
+
+    fn apply_edit(&mut self, edit: Edit) {
+        let cursor = self.idx(self.cursor);
+        let line_start = self.idx(Position::new(0, self.cursor.y));
+        let line_end = line_start + self.width as usize;
+
+        match edit {
+            Edit::EraseInDisplay(EraseInDisplay::EraseToEndOfDisplay) => {
+                self.erase(cursor, self.cells.len());
+            }
+
+            Edit::EraseInDisplay(EraseInDisplay::EraseToStartOfDisplay) => {
+                self.erase(0, cursor + 1);
+            }
+
+            Edit::EraseInDisplay(
+                EraseInDisplay::EraseDisplay | EraseInDisplay::EraseScrollback,
+            ) => {
+                self.erase(0, self.cells.len());
+            }
+
+            Edit::EraseInLine(EraseInLine::EraseToEndOfLine) => {
+                self.erase(cursor, line_end);
+            }
+
+            Edit::EraseInLine(EraseInLine::EraseToStartOfLine) => {
+                self.erase(line_start, cursor + 1);
+            }
+
+            Edit::EraseInLine(EraseInLine::EraseLine) => {
+                self.erase(line_start, line_end);
+            }
+
+            Edit::EraseCharacter(n) => {
+                self.erase(cursor, (cursor + n as usize).min(line_end));
+            }
+
+            _ => (),
+        }
+    }
+
+    fn print(&mut self, ch: char) {
+        if self.cursor.x >= self.width {
+            self.cursor.x = 0;
+            self.line_feed();
+        }
+
+        let idx = self.idx(self.cursor);
+
+        self.cells[idx] = (ch, self.style);
+        self.cursor.x += 1;
+    }
+
+    fn line_feed(&mut self) {
+        if self.cursor.y + 1 < self.height {
+            self.cursor.y += 1;
+        } else {
+            let width = self.width as usize;
+
+            self.cells.drain(0..width);
+            self.cells.extend((0..width).map(|_| Self::BLANK));
+        }
+    }
+
+    fn move_to(&mut self, x: u32, y: u32) {
+        self.cursor = Position::new(
+            x.min(self.width as u32 - 1) as u16,
+            y.min(self.height as u32 - 1) as u16,
+        );
+    }
+
+    fn erase(&mut self, from: usize, to: usize) {
+        for cell in &mut self.cells[from..to] {
+            *cell = Self::BLANK;
+        }
+    }
+
+    fn toggle(&mut self, modifier: Modifier, on: bool) {
+        self.style = if on {
+            self.style.add_modifier(modifier)
+        } else {
+            self.style.remove_modifier(modifier)
+        };
+    }
+
+    fn idx(&self, pos: Position) -> usize {
+        // Cursor can stand right past the last column (until something gets
+        // printed there), so let's keep it within the line
+        let x = pos.x.min(self.width - 1);
+
+        (pos.y as usize) * (self.width as usize) + (x as usize)
+    }
+
+    fn color(color: ColorSpec) -> Option<Color> {
+        match color {
+            ColorSpec::Default => None,
+            ColorSpec::PaletteIndex(idx) => Some(Color::Indexed(idx)),
+
+            ColorSpec::TrueColor(color) => {
+                let (r, g, b, _) = color.to_srgb_u8();
+
+                Some(Color::Rgb(r, g, b))
+            }
+        }
+    }
+
+    const BLANK: (char, Style) = (' ', Style::new());
+}
+
+impl<T> UiWidget<T> for &VirtualTerm {
+    fn render(self, ui: &mut Ui<T>) -> Self::Response {
+        let width = self.width.min(ui.area.width);
+        let height = self.height.min(ui.area.height);
+
+        for y in 0..height {
+            for x in 0..width {
+                let (ch, style) = self.cells[self.idx(Position::new(x, y))];
+
+                ui.buf[(ui.area.x + x, ui.area.y + y)]
+                    .set_char(ch)
+                    .set_style(style);
+            }
+        }
+
+        ui.space(height);
+    }
+}
@@ -0,0 +1,64 @@
+#![feature(test)]
+
+extern crate test;
+
+use glam::uvec2;
+use kartoffels_prefabs::ROBERTO;
+use kartoffels_world::prelude::*;
+use test::Bencher;
+use tokio::runtime::Runtime;
+
+/// Measures how long it takes for a viewer to receive a snapshot of a 256x256
+/// cave with 256 bots.
+#[bench]
+fn bench_snapshots(b: &mut Bencher) {
+    let rt = Runtime::new().unwrap();
+    let world = rt.block_on(setup());
+    let mut stream = world.snapshots();
+    let mut snapshots = 0;
+
+    b.iter(|| {
+        snapshots += 1;
+
+        rt.block_on(async {
+            world.tick(1).await.unwrap();
+            stream.next().await.unwrap()
+        })
+    });
+
+    // Makes the reported throughput correspond to the amount of tile data
+    // (keyframes and diffs) the viewer has received - for comparison, a full
+    // 256x256 map is 256 KiB
+    b.bytes = stream.received_bytes() / snapshots;
+}
+
+async fn setup() -> Handle {
+    let world = kartoffels_world::create(Config {
+        clock: Clock::manual(),
+        events: false,
+        id: None,
+        name: "bench".into(),
+        path: None,
+        policy: Policy {
+            auto_respawn: true,
+            max_alive_bots: 256,
+            max_queued_bots: 256,
+            ..Default::default()
+        },
+        seed: Some(Default::default()),
+        sequential: false,
+        theme: Some(Theme::Cave(CaveTheme::new(uvec2(256, 256)))),
+    })
+    .unwrap();
+
+    for _ in 0..256 {
+        world
+            .create_bot(CreateBotRequest::new(ROBERTO))
+            .await
+            .unwrap();
+    }
+
+    // Let the bots spawn and start moving around
+    world.tick(10_000).await.unwrap();
+    world
+}
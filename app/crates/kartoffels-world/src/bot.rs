@@ -2,12 +2,14 @@ mod action;
 mod arm;
 mod battery;
 mod compass;
+mod debugger;
 mod events;
 mod id;
 mod inventory;
 mod mmio;
 mod motor;
 mod radar;
+mod radio;
 mod serial;
 mod timer;
 
@@ -15,17 +17,19 @@ pub use self::action::*;
 pub use self::arm::*;
 pub use self::battery::*;
 pub use self::compass::*;
+pub use self::debugger::*;
 pub use self::events::*;
 pub use self::id::*;
 pub use self::inventory::*;
 pub use self::mmio::*;
 pub use self::motor::*;
 pub use self::radar::*;
+pub use self::radio::*;
 pub use self::serial::*;
 pub use self::timer::*;
-use crate::{AliveBots, Clock, Dir, Map, Objects, Ticks, WorldRng};
+use crate::{AliveBots, Clock, Dir, Map, Objects, Policy, Ticks};
 use glam::IVec2;
-use kartoffels_cpu::{Cpu, Firmware};
+use kartoffels_cpu::{Cpu, CpuProfile, CpuProfiler, Firmware};
 use rand::RngCore;
 use serde::{Deserialize, Serialize};
 use std::collections::VecDeque;
@@ -38,6 +42,7 @@ pub struct AliveBot {
     pub battery: BotBattery,
     pub compass: BotCompass,
     pub cpu: Cpu,
+    pub debugger: Option<BotDebugger>,
     pub dir: Dir,
     pub events: BotEvents,
     pub fw: Firmware,
@@ -45,9 +50,17 @@ pub struct AliveBot {
     pub inventory: BotInventory,
     pub motor: BotMotor,
     pub oneshot: bool,
+    pub owner: Option<Arc<str>>,
     pub pos: IVec2,
+
+    /// How many viewers are profiling this bot, see [`Self::start_profiling()`]
+    #[serde(skip)]
+    pub profilers: u32,
+
     pub radar: BotRadar,
+    pub radio: BotRadio,
     pub serial: BotSerial,
+    pub team: Option<u8>,
     pub timer: BotTimer,
 }
 
@@ -59,6 +72,11 @@ impl AliveBot {
     const MEM_ARM: u32 = 4 * 1024;
     const MEM_RADAR: u32 = 5 * 1024;
     const MEM_COMPASS: u32 = 6 * 1024;
+    const MEM_RADIO: u32 = 7 * 1024;
+
+    /// How often the profiler samples the program counter - it's a prime
+    /// number so that the samples don't synchronize with the firmware's loops
+    const PROFILER_INTERVAL: u32 = 61;
 
     pub fn new(
         rng: &mut impl RngCore,
@@ -75,6 +93,7 @@ impl AliveBot {
             battery: Default::default(),
             compass: Default::default(),
             cpu: Cpu::new(&bot.fw),
+            debugger: None,
             dir,
             events: bot.events,
             fw: bot.fw,
@@ -82,9 +101,13 @@ impl AliveBot {
             inventory: Default::default(),
             motor: Default::default(),
             oneshot: bot.oneshot,
+            owner: bot.owner,
             pos,
+            profilers: 0,
             radar: Default::default(),
+            radio: Default::default(),
             serial: Default::default(),
+            team: bot.team,
             timer: BotTimer::new(rng),
         }
     }
@@ -97,20 +120,58 @@ impl AliveBot {
         Ticks::new(self.timer.ticks())
     }
 
+    /// Registers a new viewer of the profile and starts sampling anew.
+    ///
+    /// The profiler is shared between viewers, so this resets samples for all
+    /// of them - this way each viewer starts with a clean slate instead of
+    /// samples left over from somebody else.
+    pub fn start_profiling(&mut self) {
+        self.profilers += 1;
+
+        self.cpu
+            .set_profiler(Some(CpuProfiler::new(Self::PROFILER_INTERVAL)));
+    }
+
+    /// Unregisters a viewer of the profile, detaching the profiler once there
+    /// are no viewers left.
+    pub fn stop_profiling(&mut self) {
+        self.profilers = self.profilers.saturating_sub(1);
+
+        if self.profilers == 0 {
+            self.cpu.set_profiler(None);
+        }
+    }
+
+    /// Returns samples collected since profiling has been started, if it has.
+    pub fn profile(&self) -> Option<CpuProfile> {
+        self.cpu
+            .profiler()
+            .map(|profiler| profiler.profile(&self.fw))
+    }
+
+    /// Runs a single tick of the bot, returning the action it wants to perform.
+    ///
+    /// This doesn't modify anything but the bot itself, so bots can be ticked
+    /// in parallel - actions and cooldowns get applied afterwards.
     pub fn tick(
         &mut self,
         bots: &AliveBots,
         map: &Map,
         objects: &Objects,
-        rng: &mut WorldRng,
+        policy: &Policy,
+        cooldown: &mut Option<BotCooldown>,
     ) -> Result<Option<BotAction>, Box<str>> {
         let mut action = None;
+        let mut drained = 0;
 
         self.timer.tick();
+        self.battery
+            .tick(&policy.energy, map, self.pos, self.timer.ticks());
         self.serial.tick();
         self.arm.tick();
         self.motor.tick();
         self.radar.tick();
+        self.radio.tick();
         self.compass.tick(self.dir);
 
         self.cpu.tick(BotMmio {
@@ -119,6 +180,7 @@ impl AliveBot {
             compass: &mut self.compass,
             motor: &mut self.motor,
             radar: &mut self.radar,
+            radio: &mut self.radio,
             serial: &mut self.serial,
             timer: &mut self.timer,
 
@@ -126,21 +188,38 @@ impl AliveBot {
                 action: &mut action,
                 bots,
                 dir: &mut self.dir,
+                drained: &mut drained,
                 map,
                 objects,
+                policy,
                 pos: self.pos,
-                rng: &mut rng.0,
+                cooldown,
             },
         })?;
 
+        self.battery.drain(drained);
+
         Ok(action)
     }
+
+    /// Draws the cooldown requested during [`Self::tick()`].
+    pub fn cool_down(&mut self, cooldown: BotCooldown, rng: &mut impl RngCore) {
+        let val = cooldown.draw(rng);
+
+        match cooldown.device {
+            BotDevice::Arm => self.arm.cooldown = val,
+            BotDevice::Motor => self.motor.cooldown = val,
+            BotDevice::Radar => self.radar.cooldown = val,
+            BotDevice::Radio => self.radio.cooldown = val,
+        }
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct DeadBot {
     pub events: Arc<VecDeque<Arc<BotEvent>>>,
     pub id: BotId,
+    pub owner: Option<Arc<str>>,
     pub serial: Arc<VecDeque<u32>>,
 }
 
@@ -151,7 +230,9 @@ pub struct QueuedBot {
     pub fw: Firmware,
     pub id: BotId,
     pub oneshot: bool,
+    pub owner: Option<Arc<str>>,
     pub pos: Option<IVec2>,
     pub requeued: bool, // TODO rename to `reincarnated`
     pub serial: BotSerial,
+    pub team: Option<u8>,
 }
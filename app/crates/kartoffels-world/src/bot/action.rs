@@ -1,3 +1,4 @@
+use crate::BotRadioMessage;
 use glam::IVec2;
 
 /// Action to apply on the world after [`AliveBot::tick()`] finishes.
@@ -8,8 +9,21 @@ use glam::IVec2;
 // TODO ^ this is refactorable
 #[derive(Debug)]
 pub enum BotAction {
-    ArmDrop { at: IVec2, idx: u8 },
-    ArmPick { at: IVec2 },
-    ArmStab { at: IVec2 },
-    MotorMove { at: IVec2 },
+    ArmDrop {
+        at: IVec2,
+        idx: u8,
+    },
+    ArmPick {
+        at: IVec2,
+    },
+    ArmStab {
+        at: IVec2,
+    },
+    MotorMove {
+        at: IVec2,
+    },
+    RadioSend {
+        range: u8,
+        data: [u32; BotRadioMessage::LEN],
+    },
 }
@@ -1,11 +1,11 @@
-use super::BotAction;
+use super::{BotAction, BotDevice};
 use crate::{AliveBot, BotMmioContext};
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct BotArm {
-    cooldown: u32,
+    pub(super) cooldown: u32,
 }
 
 impl BotArm {
@@ -34,7 +34,8 @@ impl BotArm {
                         at: ctxt.pos + *ctxt.dir,
                     });
 
-                    self.cooldown = ctxt.cooldown(60_000, 15);
+                    ctxt.cooldown(BotDevice::Arm, 60_000, 15);
+                    ctxt.drain(ctxt.policy.energy.arm);
                 }
 
                 Ok(())
@@ -46,7 +47,8 @@ impl BotArm {
                         at: ctxt.pos + *ctxt.dir,
                     });
 
-                    self.cooldown = ctxt.cooldown(60_000, 15);
+                    ctxt.cooldown(BotDevice::Arm, 60_000, 15);
+                    ctxt.drain(ctxt.policy.energy.arm);
                 }
 
                 Ok(())
@@ -59,7 +61,8 @@ impl BotArm {
                         idx,
                     });
 
-                    self.cooldown = ctxt.cooldown(60_000, 15);
+                    ctxt.cooldown(BotDevice::Arm, 60_000, 15);
+                    ctxt.drain(ctxt.policy.energy.arm);
                 }
 
                 Ok(())
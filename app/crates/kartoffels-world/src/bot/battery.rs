@@ -1,5 +1,6 @@
-use crate::AliveBot;
+use crate::{AliveBot, Dir, EnergyPolicy, Map, TileKind};
 use anyhow::Result;
+use glam::IVec2;
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -8,6 +9,45 @@ pub struct BotBattery {
 }
 
 impl BotBattery {
+    pub const CAPACITY: u32 = 4096;
+    pub const CHARGE_EVERY: u64 = 64;
+
+    pub fn tick(
+        &mut self,
+        policy: &EnergyPolicy,
+        map: &Map,
+        pos: IVec2,
+        ticks: u64,
+    ) {
+        if policy.charge == 0
+            || ticks % Self::CHARGE_EVERY != 0
+            || self.energy >= Self::CAPACITY
+        {
+            return;
+        }
+
+        let is_near_charger = Dir::all()
+            .into_iter()
+            .any(|dir| map.get(pos + dir).kind == TileKind::CHARGER);
+
+        if is_near_charger {
+            self.energy = (self.energy + policy.charge).min(Self::CAPACITY);
+        }
+    }
+
+    pub fn drain(&mut self, energy: u32) {
+        self.energy = self.energy.saturating_sub(energy);
+    }
+
+    #[cfg(test)]
+    pub fn energy(&self) -> u32 {
+        self.energy
+    }
+
+    pub fn is_depleted(&self) -> bool {
+        self.energy == 0
+    }
+
     pub fn mmio_load(&self, addr: u32) -> Result<u32, ()> {
         match addr {
             AliveBot::MEM_BATTERY => Ok(self.energy),
@@ -23,6 +63,66 @@ impl BotBattery {
 
 impl Default for BotBattery {
     fn default() -> Self {
-        Self { energy: 4096 }
+        Self {
+            energy: Self::CAPACITY,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use glam::{ivec2, uvec2};
+
+    #[test]
+    fn smoke() {
+        let policy = EnergyPolicy {
+            charge: 100,
+            ..Default::default()
+        };
+
+        let map = {
+            let mut map = Map::new(uvec2(3, 3));
+
+            map.fill(TileKind::FLOOR);
+            map.set(ivec2(1, 0), TileKind::CHARGER);
+            map
+        };
+
+        let mut target = BotBattery::default();
+
+        target.drain(1000);
+
+        assert_eq!(3096, target.energy());
+        assert_eq!(Ok(3096), target.mmio_load(AliveBot::MEM_BATTERY));
+
+        // ---
+
+        // Not standing next to a charger
+        target.tick(&policy, &map, ivec2(2, 2), 0);
+
+        assert_eq!(3096, target.energy());
+
+        // Standing next to a charger, but not on the charging tick
+        target.tick(&policy, &map, ivec2(1, 1), 1);
+
+        assert_eq!(3096, target.energy());
+
+        // Standing next to a charger
+        target.tick(&policy, &map, ivec2(1, 1), 0);
+
+        assert_eq!(3196, target.energy());
+
+        for _ in 0..100 {
+            target.tick(&policy, &map, ivec2(1, 1), 0);
+        }
+
+        assert_eq!(BotBattery::CAPACITY, target.energy());
+
+        // ---
+
+        target.drain(5000);
+
+        assert!(target.is_depleted());
     }
 }
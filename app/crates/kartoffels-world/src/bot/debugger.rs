@@ -0,0 +1,122 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::mem;
+
+/// Debugging session attached to a bot - allows to pause the bot, step through
+/// its firmware instruction by instruction and stop it on breakpoints.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct BotDebugger {
+    breakpoints: BTreeSet<u32>,
+    paused: bool,
+    steps: u32,
+
+    /// When set, the breakpoint at the current pc is ignored - this allows to
+    /// resume a bot that's been stopped on a breakpoint
+    resumed: bool,
+}
+
+impl BotDebugger {
+    /// Returns whether the bot should get ticked, given its current pc.
+    pub fn tick(&mut self, pc: u32) -> bool {
+        if self.paused {
+            if self.steps == 0 {
+                return false;
+            }
+
+            self.steps -= 1;
+
+            return true;
+        }
+
+        if !mem::take(&mut self.resumed) && self.breakpoints.contains(&pc) {
+            self.paused = true;
+
+            return false;
+        }
+
+        true
+    }
+
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    pub fn resume(&mut self) {
+        self.paused = false;
+        self.steps = 0;
+        self.resumed = true;
+    }
+
+    pub fn step(&mut self) {
+        if self.paused {
+            self.steps += 1;
+        }
+    }
+
+    pub fn add_breakpoint(&mut self, addr: u32) {
+        self.breakpoints.insert(addr);
+    }
+
+    pub fn remove_breakpoint(&mut self, addr: u32) {
+        self.breakpoints.remove(&addr);
+    }
+
+    pub fn breakpoints(&self) -> &BTreeSet<u32> {
+        &self.breakpoints
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Returns whether this debugger doesn't affect the bot anymore and can be
+    /// detached.
+    pub fn is_idle(&self) -> bool {
+        !self.paused && self.breakpoints.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn breakpoints() {
+        let mut target = BotDebugger::default();
+
+        target.add_breakpoint(0x1008);
+
+        assert!(target.tick(0x1000));
+        assert!(target.tick(0x1004));
+        assert!(!target.tick(0x1008));
+        assert!(!target.tick(0x1008));
+        assert!(target.is_paused());
+
+        // ---
+
+        target.step();
+
+        assert!(target.tick(0x1008));
+        assert!(!target.tick(0x100c));
+
+        // ---
+
+        target.resume();
+
+        assert!(target.tick(0x100c));
+        assert!(target.tick(0x1010));
+        assert!(!target.tick(0x1008));
+
+        // ---
+
+        target.resume();
+
+        assert!(target.tick(0x1008));
+        assert!(!target.is_idle());
+
+        target.remove_breakpoint(0x1008);
+
+        assert!(target.tick(0x1008));
+        assert!(target.is_idle());
+    }
+}
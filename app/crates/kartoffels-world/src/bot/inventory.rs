@@ -25,13 +25,30 @@ impl BotInventory {
             .remove(idx as usize)
             .map(|obj| (obj.id, obj.obj))
     }
+
+    /// Takes the most recently picked object of given kind.
+    pub fn take_kind(&mut self, kind: u8) -> Option<(ObjectId, Object)> {
+        self.take_if(|obj| obj.kind == kind)
+    }
+
+    /// Takes the most recently picked object matching given predicate.
+    pub fn take_if(
+        &mut self,
+        f: impl Fn(&Object) -> bool,
+    ) -> Option<(ObjectId, Object)> {
+        let idx = self.objects.iter().position(|obj| f(&obj.obj))?;
+
+        self.take(idx as u8)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Object> + '_ {
+        self.objects.iter().map(|obj| obj.obj)
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 struct BotInventoryObject {
     id: ObjectId,
-
-    #[serde(flatten)]
     obj: Object,
 }
 
@@ -61,5 +78,10 @@ mod tests {
 
         assert_eq!(255, target.take(0).unwrap().1.kind);
         assert_eq!(1, target.take(28).unwrap().1.kind);
+
+        assert_eq!(10, target.take_kind(10).unwrap().1.kind);
+        assert!(target.take_kind(10).is_none());
+        assert!(target.iter().all(|obj| obj.kind != 10));
+        assert_eq!(2, target.take_if(|obj| obj.kind < 3).unwrap().1.kind);
     }
 }
@@ -1,12 +1,11 @@
 use super::{
-    BotAction, BotArm, BotBattery, BotCompass, BotMotor, BotRadar, BotSerial,
-    BotTimer,
+    BotAction, BotArm, BotBattery, BotCompass, BotMotor, BotRadar, BotRadio,
+    BotSerial, BotTimer,
 };
-use crate::{AliveBots, Dir, Map, Objects};
+use crate::{AliveBots, Dir, Map, Objects, Policy};
 use glam::IVec2;
 use kartoffels_cpu::Mmio;
-use rand::Rng;
-use rand_chacha::ChaCha8Rng;
+use rand::{Rng, RngCore};
 
 pub struct BotMmio<'a> {
     pub arm: &'a mut BotArm,
@@ -14,6 +13,7 @@ pub struct BotMmio<'a> {
     pub compass: &'a mut BotCompass,
     pub motor: &'a mut BotMotor,
     pub radar: &'a mut BotRadar,
+    pub radio: &'a mut BotRadio,
     pub serial: &'a mut BotSerial,
     pub timer: &'a mut BotTimer,
     pub ctxt: BotMmioContext<'a>,
@@ -29,6 +29,7 @@ impl Mmio for BotMmio<'_> {
             .or_else(|_| self.arm.mmio_load(addr))
             .or_else(|_| self.radar.mmio_load(addr))
             .or_else(|_| self.compass.mmio_load(addr))
+            .or_else(|_| self.radio.mmio_load(addr))
     }
 
     fn store(mut self, addr: u32, val: u32) -> Result<(), ()> {
@@ -39,6 +40,7 @@ impl Mmio for BotMmio<'_> {
             .or_else(|_| self.motor.mmio_store(&mut self.ctxt, addr, val))
             .or_else(|_| self.arm.mmio_store(&mut self.ctxt, addr, val))
             .or_else(|_| self.radar.mmio_store(&mut self.ctxt, addr, val))
+            .or_else(|_| self.radio.mmio_store(&mut self.ctxt, addr, val))
     }
 }
 
@@ -46,18 +48,67 @@ pub struct BotMmioContext<'a> {
     pub action: &'a mut Option<BotAction>,
     pub bots: &'a AliveBots,
     pub dir: &'a mut Dir,
+    pub drained: &'a mut u32,
     pub map: &'a Map,
     pub objects: &'a Objects,
+    pub policy: &'a Policy,
     pub pos: IVec2,
-    pub rng: &'a mut ChaCha8Rng,
+    pub cooldown: &'a mut Option<BotCooldown>,
 }
 
 impl BotMmioContext<'_> {
-    pub fn cooldown(&mut self, base: u32, off_percentage: u32) -> u32 {
+    /// Puts given device on a cooldown of `base` ticks, give or take
+    /// `off_percentage`.
+    ///
+    /// The exact value is random, but it's not drawn here - see
+    /// [`BotCooldown`].
+    pub fn cooldown(
+        &mut self,
+        device: BotDevice,
+        base: u32,
+        off_percentage: u32,
+    ) {
         let off = base * off_percentage / 100;
-        let min = base - off;
-        let max = base + off;
 
-        self.rng.gen_range(min..=max)
+        *self.cooldown = Some(BotCooldown {
+            device,
+            min: base - off,
+            max: base + off,
+        });
     }
+
+    /// Marks given amount of energy as used; battery gets drained after the
+    /// CPU finishes its tick.
+    pub fn drain(&mut self, energy: u32) {
+        *self.drained = self.drained.saturating_add(energy);
+    }
+}
+
+/// Cooldown requested by a device during bot's tick.
+///
+/// CPUs of all bots are run in parallel, so the cooldown can't be drawn from
+/// world's rng straight away - that would make the outcome depend on the order
+/// in which threads get to it. Instead, the cooldown is drawn later, when
+/// bot's outcome is applied, in the bots' order - see [`AliveBot::cool_down()`].
+///
+/// [`AliveBot::cool_down()`]: crate::AliveBot::cool_down
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BotCooldown {
+    pub device: BotDevice,
+    pub min: u32,
+    pub max: u32,
+}
+
+impl BotCooldown {
+    pub fn draw(&self, rng: &mut impl RngCore) -> u32 {
+        rng.gen_range(self.min..=self.max)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BotDevice {
+    Arm,
+    Motor,
+    Radar,
+    Radio,
 }
@@ -1,10 +1,10 @@
-use super::BotAction;
+use super::{BotAction, BotDevice};
 use crate::{AliveBot, BotMmioContext};
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct BotMotor {
-    cooldown: u32,
+    pub(super) cooldown: u32,
 }
 
 impl BotMotor {
@@ -33,7 +33,8 @@ impl BotMotor {
                         at: ctxt.pos + *ctxt.dir,
                     });
 
-                    self.cooldown = ctxt.cooldown(20_000, 15);
+                    ctxt.cooldown(BotDevice::Motor, 20_000, 15);
+                    ctxt.drain(ctxt.policy.energy.motor_move);
                 }
 
                 Ok(())
@@ -45,7 +46,8 @@ impl BotMotor {
                         at: ctxt.pos + ctxt.dir.turned_back(),
                     });
 
-                    self.cooldown = ctxt.cooldown(30_000, 15);
+                    ctxt.cooldown(BotDevice::Motor, 30_000, 15);
+                    ctxt.drain(ctxt.policy.energy.motor_move);
                 }
 
                 Ok(())
@@ -55,7 +57,8 @@ impl BotMotor {
                 if self.cooldown == 0 {
                     *ctxt.dir = ctxt.dir.turned_right();
 
-                    self.cooldown = ctxt.cooldown(25_000, 15);
+                    ctxt.cooldown(BotDevice::Motor, 25_000, 15);
+                    ctxt.drain(ctxt.policy.energy.motor_turn);
                 }
 
                 Ok(())
@@ -65,7 +68,8 @@ impl BotMotor {
                 if self.cooldown == 0 {
                     *ctxt.dir = ctxt.dir.turned_left();
 
-                    self.cooldown = ctxt.cooldown(25_000, 15);
+                    ctxt.cooldown(BotDevice::Motor, 25_000, 15);
+                    ctxt.drain(ctxt.policy.energy.motor_turn);
                 }
 
                 Ok(())
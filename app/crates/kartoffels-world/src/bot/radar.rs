@@ -1,3 +1,4 @@
+use super::BotDevice;
 use crate::{AliveBot, BotMmioContext, TileKind};
 use glam::{ivec2, IVec2};
 use serde::{Deserialize, Serialize};
@@ -5,7 +6,7 @@ use serde::{Deserialize, Serialize};
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct BotRadar {
     scan: Vec<u32>,
-    cooldown: u32,
+    pub(super) cooldown: u32,
 }
 
 impl BotRadar {
@@ -63,13 +64,21 @@ impl BotRadar {
                 let out_z2;
 
                 if let Some(bot_id) = ctxt.bots.lookup_at(pos) {
+                    let team = match ctxt.bots.team(bot_id) {
+                        Some(team) => u32::from_le_bytes([0, 1, team, 0]),
+                        None => 0,
+                    };
+
                     let bot_id = bot_id.get().get();
 
-                    out_z0 = TileKind::BOT as u32;
+                    out_z0 = TileKind::BOT as u32 | team;
                     out_z1 = (bot_id >> 32) as u32;
                     out_z2 = bot_id as u32;
                 } else if let Some(object) = ctxt.objects.get_at(pos) {
-                    out_z0 = object.kind as u32;
+                    let [meta0, meta1, meta2] = object.meta;
+
+                    out_z0 =
+                        u32::from_le_bytes([object.kind, meta0, meta1, meta2]);
                     out_z1 = 0;
                     out_z2 = 0;
                 } else {
@@ -84,7 +93,8 @@ impl BotRadar {
             }
         }
 
-        self.cooldown = range.cooldown(ctxt);
+        range.cooldown(ctxt);
+        ctxt.drain(ctxt.policy.energy.radar);
     }
 }
 
@@ -127,13 +137,15 @@ impl BotRadarRange {
         (z * len * len + y * len + x) as usize
     }
 
-    fn cooldown(&self, ctxt: &mut BotMmioContext) -> u32 {
-        match self {
-            Self::D3 => ctxt.cooldown(10_000, 10),
-            Self::D5 => ctxt.cooldown(15_000, 15),
-            Self::D7 => ctxt.cooldown(22_000, 25),
-            Self::D9 => ctxt.cooldown(30_000, 30),
-        }
+    fn cooldown(&self, ctxt: &mut BotMmioContext) {
+        let (base, off_percentage) = match self {
+            Self::D3 => (10_000, 10),
+            Self::D5 => (15_000, 15),
+            Self::D7 => (22_000, 25),
+            Self::D9 => (30_000, 30),
+        };
+
+        ctxt.cooldown(BotDevice::Radar, base, off_percentage);
     }
 }
 
@@ -142,6 +154,7 @@ mod tests {
     use super::*;
     use crate::{
         AliveBots, BotId, Dir, Map, Object, ObjectId, ObjectKind, Objects,
+        Policy,
     };
     use glam::uvec2;
     use indoc::indoc;
@@ -353,16 +366,18 @@ mod tests {
         };
 
         let mut radar = BotRadar::default();
-        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut cooldown = None;
 
         let mut ctxt = BotMmioContext {
             action: &mut None,
             bots: &bots,
             dir: &mut case.dir,
+            drained: &mut 0,
             map: &map,
             objects: &objects,
+            policy: &Policy::default(),
             pos: case.pos,
-            rng: &mut rng,
+            cooldown: &mut cooldown,
         };
 
         radar
@@ -382,6 +397,10 @@ mod tests {
             radar.scanned_tiles(range).trim()
         );
 
-        assert_eq!(case.expected_cooldown, radar.cooldown);
+        let cooldown = cooldown.unwrap();
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        assert_eq!(BotDevice::Radar, cooldown.device);
+        assert_eq!(case.expected_cooldown, cooldown.draw(&mut rng));
     }
 }
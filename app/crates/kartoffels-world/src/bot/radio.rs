@@ -0,0 +1,205 @@
+use super::{BotAction, BotDevice};
+use crate::{AliveBot, BotId, BotMmioContext};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct BotRadio {
+    pub(super) cooldown: u32,
+    inbox: VecDeque<BotRadioMessage>,
+    outbox: [u32; BotRadioMessage::LEN],
+}
+
+impl BotRadio {
+    pub const INBOX_SIZE: usize = 16;
+    pub const MAX_RANGE: u8 = 16;
+
+    const MEM_OUTBOX: u32 = AliveBot::MEM_RADIO + 8;
+    const MEM_INBOX: u32 = AliveBot::MEM_RADIO + 64;
+
+    pub fn tick(&mut self) {
+        self.cooldown = self.cooldown.saturating_sub(1);
+    }
+
+    pub fn receive(&mut self, msg: BotRadioMessage) {
+        if self.inbox.len() >= Self::INBOX_SIZE {
+            self.inbox.pop_front();
+        }
+
+        self.inbox.push_back(msg);
+    }
+
+    pub fn mmio_load(&self, addr: u32) -> Result<u32, ()> {
+        match addr {
+            AliveBot::MEM_RADIO => Ok((self.cooldown == 0) as u32),
+
+            addr if addr == AliveBot::MEM_RADIO + 4 => {
+                Ok(self.inbox.len() as u32)
+            }
+
+            addr if let Some(idx) = Self::idx(addr, Self::MEM_OUTBOX, 8) => {
+                Ok(self.outbox[idx])
+            }
+
+            addr if let Some(idx) = Self::idx(addr, Self::MEM_INBOX, 10) => {
+                let Some(msg) = self.inbox.front() else {
+                    return Ok(0);
+                };
+
+                let from = msg.from.get().get();
+
+                Ok(match idx {
+                    0 => (from >> 32) as u32,
+                    1 => from as u32,
+                    idx => msg.data[idx - 2],
+                })
+            }
+
+            _ => Err(()),
+        }
+    }
+
+    pub fn mmio_store(
+        &mut self,
+        ctxt: &mut BotMmioContext,
+        addr: u32,
+        val: u32,
+    ) -> Result<(), ()> {
+        match (addr, val.to_le_bytes()) {
+            (AliveBot::MEM_RADIO, [0x01, range, 0x00, 0x00])
+                if (1..=Self::MAX_RANGE).contains(&range) =>
+            {
+                if self.cooldown == 0 {
+                    *ctxt.action = Some(BotAction::RadioSend {
+                        range,
+                        data: self.outbox,
+                    });
+
+                    ctxt.cooldown(BotDevice::Radio, 20_000, 15);
+                    ctxt.drain(ctxt.policy.energy.radio);
+                }
+
+                Ok(())
+            }
+
+            (AliveBot::MEM_RADIO, [0x02, 0x00, 0x00, 0x00]) => {
+                self.inbox.pop_front();
+
+                Ok(())
+            }
+
+            (addr, _)
+                if let Some(idx) = Self::idx(addr, Self::MEM_OUTBOX, 8) =>
+            {
+                self.outbox[idx] = val;
+
+                Ok(())
+            }
+
+            _ => Err(()),
+        }
+    }
+
+    fn idx(addr: u32, base: u32, len: usize) -> Option<usize> {
+        let idx = (addr.checked_sub(base)? / 4) as usize;
+
+        (addr % 4 == 0 && idx < len).then_some(idx)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BotRadioMessage {
+    pub from: BotId,
+    pub data: [u32; Self::LEN],
+}
+
+impl BotRadioMessage {
+    pub const LEN: usize = 8;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AliveBots, Dir, EnergyPolicy, Map, Objects, Policy};
+    use glam::ivec2;
+
+    #[test]
+    fn smoke() {
+        let mut target = BotRadio::default();
+        let mut action = None;
+        let mut drained = 0;
+        let mut cooldown = None;
+
+        let policy = Policy {
+            energy: EnergyPolicy {
+                radio: 100,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let mut ctxt = BotMmioContext {
+            action: &mut action,
+            bots: &AliveBots::default(),
+            dir: &mut Dir::N,
+            drained: &mut drained,
+            map: &Map::default(),
+            objects: &Objects::default(),
+            policy: &policy,
+            pos: ivec2(0, 0),
+            cooldown: &mut cooldown,
+        };
+
+        let mem = |off: u32| AliveBot::MEM_RADIO + off;
+
+        // ---
+
+        assert_eq!(Ok(1), target.mmio_load(mem(0)));
+        assert_eq!(Ok(0), target.mmio_load(mem(4)));
+
+        target.mmio_store(&mut ctxt, mem(8), 123).unwrap();
+        target.mmio_store(&mut ctxt, mem(36), 456).unwrap();
+        target.mmio_store(&mut ctxt, mem(40), 789).unwrap_err();
+        target.mmio_store(&mut ctxt, mem(0), 0x00000401).unwrap();
+
+        assert_eq!(Some(BotDevice::Radio), ctxt.cooldown.map(|c| c.device));
+        assert_eq!(100, *ctxt.drained);
+
+        match ctxt.action.take() {
+            Some(BotAction::RadioSend { range, data }) => {
+                assert_eq!(4, range);
+                assert_eq!([123, 0, 0, 0, 0, 0, 0, 456], data);
+            }
+
+            action => panic!("unexpected action: {action:?}"),
+        }
+
+        target
+            .mmio_store(&mut ctxt, mem(0), 0x00000001)
+            .unwrap_err();
+
+        target
+            .mmio_store(&mut ctxt, mem(0), 0x00001101)
+            .unwrap_err();
+
+        // ---
+
+        for idx in 0..=(BotRadio::INBOX_SIZE as u32) {
+            target.receive(BotRadioMessage {
+                from: BotId::new(0x1122334455667788),
+                data: [idx, 0, 0, 0, 0, 0, 0, 1],
+            });
+        }
+
+        assert_eq!(Ok(16), target.mmio_load(mem(4)));
+        assert_eq!(Ok(0x11223344), target.mmio_load(mem(64)));
+        assert_eq!(Ok(0x55667788), target.mmio_load(mem(68)));
+        assert_eq!(Ok(1), target.mmio_load(mem(72)));
+        assert_eq!(Ok(1), target.mmio_load(mem(100)));
+
+        target.mmio_store(&mut ctxt, mem(0), 0x00000002).unwrap();
+
+        assert_eq!(Ok(15), target.mmio_load(mem(4)));
+        assert_eq!(Ok(2), target.mmio_load(mem(72)));
+    }
+}
@@ -1,4 +1,5 @@
 use crate::AliveBot;
+use anyhow::{anyhow, Result};
 use serde::{Deserialize, Serialize};
 use std::collections::VecDeque;
 use std::mem;
@@ -10,6 +11,9 @@ pub struct BotSerial {
     next: VecDeque<u32>,
     buffering: bool,
 
+    /// Characters sent to the bot, waiting to be read by the firmware
+    input: VecDeque<u32>,
+
     #[serde(skip_serializing)]
     #[serde(skip_deserializing)]
     snapshot: Option<Arc<VecDeque<u32>>>,
@@ -17,6 +21,7 @@ pub struct BotSerial {
 
 impl BotSerial {
     const CAPACITY: usize = 256;
+    const INPUT_CAPACITY: usize = 256;
 
     pub fn tick(&mut self) {
         // no-op
@@ -28,8 +33,37 @@ impl BotSerial {
             .clone()
     }
 
-    pub fn mmio_load(&self, _addr: u32) -> Result<u32, ()> {
-        Err(())
+    /// Queues given message for the firmware to read.
+    ///
+    /// Message is either queued whole or not at all, so that the firmware
+    /// never sees a partial message.
+    pub fn send(&mut self, msg: &str) -> Result<()> {
+        let len = msg.chars().count();
+
+        if self.input.len() + len > Self::INPUT_CAPACITY {
+            return Err(anyhow!(
+                "bot's serial input is full - it can hold up to {} characters",
+                Self::INPUT_CAPACITY,
+            ));
+        }
+
+        self.input.extend(msg.chars().map(|ch| ch as u32));
+
+        Ok(())
+    }
+
+    pub fn mmio_load(&self, addr: u32) -> Result<u32, ()> {
+        match addr {
+            addr if addr == AliveBot::MEM_SERIAL + 4 => {
+                Ok(self.input.len() as u32)
+            }
+
+            addr if addr == AliveBot::MEM_SERIAL + 8 => {
+                Ok(self.input.front().copied().unwrap_or(0))
+            }
+
+            _ => Err(()),
+        }
     }
 
     pub fn mmio_store(&mut self, addr: u32, val: u32) -> Result<(), ()> {
@@ -60,6 +94,11 @@ impl BotSerial {
                         }
                     }
 
+                    // serial_read()
+                    0xffffff03 => {
+                        self.input.pop_front();
+                    }
+
                     val => {
                         let buf = if self.buffering {
                             &mut self.next
@@ -86,3 +125,38 @@ impl BotSerial {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const fn mem(off: u32) -> u32 {
+        AliveBot::MEM_SERIAL + off
+    }
+
+    #[test]
+    fn input() {
+        let mut target = BotSerial::default();
+
+        assert_eq!(Ok(0), target.mmio_load(mem(4)));
+        assert_eq!(Ok(0), target.mmio_load(mem(8)));
+
+        target.send("hi!").unwrap();
+
+        assert_eq!(Ok(3), target.mmio_load(mem(4)));
+        assert_eq!(Ok('h' as u32), target.mmio_load(mem(8)));
+
+        target.mmio_store(mem(0), 0xffffff03).unwrap();
+
+        assert_eq!(Ok(2), target.mmio_load(mem(4)));
+        assert_eq!(Ok('i' as u32), target.mmio_load(mem(8)));
+
+        // Messages that don't fit get rejected whole
+        assert!(target.send(&"x".repeat(255)).is_err());
+        assert_eq!(Ok(2), target.mmio_load(mem(4)));
+
+        target.send(&"x".repeat(254)).unwrap();
+
+        assert_eq!(Ok(256), target.mmio_load(mem(4)));
+    }
+}
@@ -36,7 +36,7 @@ impl Bots {
     }
 }
 
-#[derive(Clone, Copy, Debug, Default, Resource)]
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, Resource)]
 pub struct Spawn {
     pub pos: Option<IVec2>,
     pub dir: Option<Dir>,
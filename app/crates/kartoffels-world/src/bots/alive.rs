@@ -9,6 +9,7 @@ pub struct AliveBots {
     entries: Vec<Option<Box<AliveBot>>>,
     id_to_idx: AHashMap<BotId, u8>,
     pos_to_id: AHashMap<IVec2, BotId>,
+    teams: Vec<Option<u8>>,
     count: usize,
 }
 
@@ -20,6 +21,7 @@ impl AliveBots {
             if slot.is_none() {
                 self.id_to_idx.insert(bot.id, idx);
                 self.pos_to_id.insert(bot.pos, bot.id);
+                self.teams[idx as usize] = bot.team;
                 self.count += 1;
 
                 *slot = Some(Box::new(bot));
@@ -32,15 +34,23 @@ impl AliveBots {
 
         self.id_to_idx.insert(bot.id, idx);
         self.pos_to_id.insert(bot.pos, bot.id);
+        self.teams.push(bot.team);
         self.entries.push(Some(Box::new(bot)));
         self.count += 1;
     }
 
     pub fn get(&self, id: BotId) -> Option<&AliveBot> {
         let idx = *self.id_to_idx.get(&id)?;
-        let bot = self.entries[idx as usize].as_ref().unwrap();
 
-        Some(bot)
+        // Bot might be missing if it's currently being ticked, see
+        // `Self::take()` and `Self::take_all()`
+        self.entries.get(idx as usize)?.as_deref()
+    }
+
+    pub fn get_mut(&mut self, id: BotId) -> Option<&mut AliveBot> {
+        let idx = *self.id_to_idx.get(&id)?;
+
+        self.entries[idx as usize].as_deref_mut()
     }
 
     pub fn remove(&mut self, id: BotId) -> Option<Box<AliveBot>> {
@@ -61,10 +71,31 @@ impl AliveBots {
         self.pos_to_id.get(&pos).copied()
     }
 
+    /// Returns team of given bot.
+    ///
+    /// Contrary to `self.get(id).team`, this works even if the bot is currently
+    /// being ticked.
+    pub fn team(&self, id: BotId) -> Option<u8> {
+        self.teams[*self.id_to_idx.get(&id)? as usize]
+    }
+
     pub fn take(&mut self, idx: usize) -> Option<Box<AliveBot>> {
         self.entries[idx].take()
     }
 
+    /// Takes all bots out, so that they can be ticked in parallel; entries
+    /// must be given back through [`Self::restore_all()`] before the bots are
+    /// modified in any other way.
+    pub fn take_all(&mut self) -> Vec<Option<Box<AliveBot>>> {
+        std::mem::take(&mut self.entries)
+    }
+
+    pub fn restore_all(&mut self, entries: Vec<Option<Box<AliveBot>>>) {
+        assert!(self.entries.is_empty());
+
+        self.entries = entries;
+    }
+
     pub fn insert(
         &mut self,
         idx: usize,
@@ -86,6 +117,10 @@ impl AliveBots {
         }
     }
 
+    pub fn iter(&self) -> impl Iterator<Item = &AliveBot> {
+        self.entries.iter().flatten().map(|bot| &**bot)
+    }
+
     pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut AliveBot> {
         self.entries.iter_mut().flatten().map(|bot| &mut **bot)
     }
@@ -94,6 +129,7 @@ impl AliveBots {
         self.id_to_idx.contains_key(&id)
     }
 
+    #[cfg(test)]
     pub fn len(&self) -> usize {
         self.entries.len()
     }
@@ -108,7 +144,9 @@ impl Serialize for AliveBots {
     where
         S: Serializer,
     {
-        serializer.collect_seq(self.entries.iter().flatten())
+        // Empty slots are serialized as well, so that the deserialized bots
+        // are ticked in the same order as the original ones
+        serializer.collect_seq(self.entries.iter())
     }
 }
 
@@ -118,10 +156,20 @@ impl<'de> Deserialize<'de> for AliveBots {
         D: Deserializer<'de>,
     {
         let mut this = Self::default();
-        let bots = Vec::<AliveBot>::deserialize(deserializer)?;
+        let bots = Vec::<Option<Box<AliveBot>>>::deserialize(deserializer)?;
+
+        for (idx, bot) in bots.into_iter().enumerate() {
+            let idx = u8::try_from(idx)
+                .map_err(|_| serde::de::Error::custom("too many alive bots"))?;
 
-        for bot in bots {
-            this.add(bot);
+            if let Some(bot) = &bot {
+                this.id_to_idx.insert(bot.id, idx);
+                this.pos_to_id.insert(bot.pos, bot.id);
+                this.count += 1;
+            }
+
+            this.teams.push(bot.as_ref().and_then(|bot| bot.team));
+            this.entries.push(bot);
         }
 
         Ok(this)
@@ -170,6 +218,18 @@ mod tests {
 
         // ---
 
+        let entries = target.take_all();
+
+        assert_eq!(5, entries.len());
+        assert!(target.get(BotId::new(1)).is_none());
+        assert_eq!(Some(BotId::new(1)), target.lookup_at(ivec2(10, 10)));
+
+        target.restore_all(entries);
+
+        assert!(target.get(BotId::new(1)).is_some());
+
+        // ---
+
         assert_eq!(BotId::new(4), target.remove(BotId::new(4)).unwrap().id);
         assert!(target.remove(BotId::new(4)).is_none());
 
@@ -85,6 +85,7 @@ mod tests {
         DeadBot {
             events: Default::default(),
             id: BotId::new(id),
+            owner: None,
             serial: Default::default(),
         }
     }
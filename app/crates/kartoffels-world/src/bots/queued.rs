@@ -115,9 +115,11 @@ mod tests {
             fw: Default::default(),
             id: BotId::new(id),
             oneshot: false,
+            owner: None,
             pos: None,
             requeued: false,
             serial: Default::default(),
+            team: None,
         })
     }
 
@@ -28,6 +28,9 @@ pub fn create(
             dir,
             instant,
             oneshot,
+            owner,
+            team,
+            symbols,
         } = req;
 
         debug!(
@@ -36,6 +39,8 @@ pub fn create(
             ?dir,
             ?instant,
             ?oneshot,
+            ?owner,
+            ?team,
             "creating bot",
         );
 
@@ -57,7 +62,13 @@ pub fn create(
             }
         };
 
-        let fw = match Firmware::from_elf(&src) {
+        let fw = if symbols {
+            Firmware::from_elf_with_symbols(&src)
+        } else {
+            Firmware::from_elf(&src)
+        };
+
+        let fw = match fw {
             Ok(fw) => fw,
 
             Err(err) => {
@@ -72,9 +83,11 @@ pub fn create(
             fw,
             id,
             oneshot,
+            owner,
             pos,
             requeued: false,
             serial: Default::default(),
+            team,
         });
 
         if instant {
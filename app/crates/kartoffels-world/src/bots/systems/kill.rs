@@ -1,13 +1,21 @@
-use crate::{Bots, Clock, DeadBot, Event, KillBot, Policy, QueuedBot};
+use crate::{
+    mode, Bots, Clock, DeadBot, Event, GameMode, KillBot, Map, ObjectKind,
+    Objects, Policy, QueuedBot, Round, WorldRng,
+};
 use bevy_ecs::event::EventMutator;
 use bevy_ecs::system::{Commands, Res, ResMut};
 use tracing::trace;
 
+#[allow(clippy::too_many_arguments)]
 pub fn kill(
     mut cmds: Commands,
     clock: Res<Clock>,
+    map: Res<Map>,
     policy: Res<Policy>,
     mut bots: ResMut<Bots>,
+    mut objects: ResMut<Objects>,
+    mut rng: ResMut<WorldRng>,
+    mut round: ResMut<Round>,
     mut events: EventMutator<KillBot>,
 ) {
     for event in events.read() {
@@ -29,7 +37,35 @@ pub fn kill(
         });
 
         if let Some(id) = killer {
-            cmds.send_event(Event::BotScored { id: *id });
+            round.bots.entry(*id).or_default().kills += 1;
+        }
+
+        match policy.mode.kind {
+            GameMode::Deathmatch => {
+                if let Some(id) = killer {
+                    cmds.send_event(Event::BotScored { id: *id });
+                }
+            }
+
+            GameMode::CaptureTheFlag => {
+                while let Some((id, obj)) =
+                    killed.inventory.take_kind(ObjectKind::FLAG)
+                {
+                    let pos = policy.mode.base_of(obj).unwrap_or(killed.pos);
+
+                    mode::drop_at(
+                        &mut rng.0,
+                        &map,
+                        &bots.alive,
+                        &mut objects,
+                        id,
+                        obj,
+                        pos,
+                    );
+                }
+            }
+
+            _ => (),
         }
 
         killed.log(&clock, &*reason);
@@ -53,9 +89,11 @@ pub fn kill(
                     fw: killed.fw,
                     id: killed.id,
                     oneshot: false,
+                    owner: killed.owner,
                     pos: None,
                     requeued: true,
                     serial: killed.serial,
+                    team: killed.team,
                 }));
             }
 
@@ -63,6 +101,7 @@ pub fn kill(
                 let bot = DeadBot {
                     events: killed.events.snapshot(),
                     id: killed.id,
+                    owner: killed.owner,
                     serial: killed.serial.snapshot(),
                 };
 
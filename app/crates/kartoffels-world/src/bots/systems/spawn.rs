@@ -1,11 +1,12 @@
 use crate::{
     AliveBot, AliveBots, Bots, Clock, Dir, Event, Map, Objects, Policy,
-    QueuedBot, Spawn, SpawnBot, WorldRng,
+    QueuedBot, Spawn, SpawnBot, Theme, WorldRng,
 };
 use anyhow::anyhow;
 use bevy_ecs::event::EventMutator;
 use bevy_ecs::system::{Commands, Res, ResMut};
 use glam::{IVec2, UVec2};
+use rand::seq::SliceRandom;
 use rand::{Rng, RngCore};
 use tracing::trace;
 
@@ -38,8 +39,14 @@ pub fn spawn(
     objects: Res<Objects>,
     mut rng: ResMut<WorldRng>,
     spawn: Res<Spawn>,
+    theme: Option<Res<Theme>>,
     mut events: EventMutator<SpawnBot>,
 ) {
+    let spawns = match theme.as_deref() {
+        Some(Theme::File(theme)) => theme.spawns(),
+        _ => &[],
+    };
+
     for event in events.read() {
         let bot = event
             .bot
@@ -52,6 +59,7 @@ pub fn spawn(
             &bots.alive,
             &objects,
             &spawn,
+            spawns,
             &bot,
         ) else {
             if let Some(tx) = event.tx.take() {
@@ -67,10 +75,11 @@ pub fn spawn(
 
         let bot = AliveBot::new(&mut rng.0, &clock, pos, dir, *bot);
         let id = bot.id;
+        let team = bot.team;
 
         trace!(?id, ?pos, ?dir, "spawning bot");
 
-        cmds.send_event(Event::BotBorn { id });
+        cmds.send_event(Event::BotBorn { id, team });
         bots.alive.add(bot);
 
         if let Some(tx) = event.tx.take() {
@@ -85,6 +94,7 @@ fn determine_spawn_point(
     bots: &AliveBots,
     objs: &Objects,
     spawn: &Spawn,
+    spawns: &[(IVec2, Option<Dir>)],
     bot: &QueuedBot,
 ) -> Option<(IVec2, Dir)> {
     if let Some(pos) = bot.pos {
@@ -107,9 +117,34 @@ fn determine_spawn_point(
         };
     }
 
+    if !spawns.is_empty() {
+        return sample_spawns(rng, map, bots, objs, spawns, bot);
+    }
+
     sample_map(rng, map, bots, objs, bot)
 }
 
+fn sample_spawns(
+    rng: &mut impl RngCore,
+    map: &Map,
+    bots: &AliveBots,
+    objs: &Objects,
+    spawns: &[(IVec2, Option<Dir>)],
+    bot: &QueuedBot,
+) -> Option<(IVec2, Dir)> {
+    let mut spawns = spawns.to_vec();
+
+    spawns.shuffle(rng);
+
+    let (pos, dir) = spawns
+        .into_iter()
+        .find(|(pos, _)| is_pos_legal(map, bots, objs, *pos, false))?;
+
+    let dir = dir.or(bot.dir).unwrap_or_else(|| rng.gen());
+
+    Some((pos, dir))
+}
+
 fn sample_map(
     rng: &mut impl RngCore,
     map: &Map,
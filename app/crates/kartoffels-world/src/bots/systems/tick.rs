@@ -1,54 +1,201 @@
 use crate::{
-    AliveBot, BotAction, Bots, Clock, Event, KillBot, Map, Objects, TileKind,
-    WorldRng,
+    AliveBot, AliveBots, BotAction, BotCooldown, BotRadioMessage, Bots, Clock,
+    Event, GameMode, KillBot, Map, Objects, Policy, TileKind, WorldRng,
 };
-use bevy_ecs::system::{Commands, Res, ResMut};
+use bevy_ecs::system::{Commands, Local, Res, ResMut, Resource};
+use kartoffels_cpu::Firmware;
+use rayon::prelude::*;
 
+/// Ticks all alive bots.
+///
+/// Each tick happens in two phases:
+///
+/// - first, CPUs of all bots are run in parallel against a read-only view of
+///   the world, which yields the actions bots want to perform,
+///
+/// - then those actions are applied one after another, in the order of bots'
+///   slots, together with cooldowns drawn from the world's rng.
+///
+/// This means that bots don't see changes made by other bots during the same
+/// tick, but it also means that the outcome doesn't depend on the number of
+/// threads (or their timing) - see [`SequentialTick`].
+#[allow(clippy::too_many_arguments)]
 pub fn tick(
     mut cmds: Commands,
     clock: Res<Clock>,
     map: Res<Map>,
     mut bots: ResMut<Bots>,
     mut objects: ResMut<Objects>,
+    policy: Res<Policy>,
     mut rng: ResMut<WorldRng>,
+    sequential: Option<Res<SequentialTick>>,
+    mut ticks: Local<Vec<Option<BotTick>>>,
 ) {
     for _ in 0..clock.ticks() {
-        let mut idx = 0;
-        let len = bots.alive.len();
-
-        while idx < len {
-            if let Some(bot) = bots.alive.take(idx) {
-                let id = bot.id;
-                let pos = bot.pos;
-
-                let bot = tick_bot(
-                    &mut cmds,
-                    &clock,
-                    &map,
-                    &mut bots,
-                    &mut objects,
-                    &mut rng,
-                    bot,
-                );
+        run_bots(
+            &clock,
+            &map,
+            &mut bots,
+            &objects,
+            &policy,
+            sequential.is_some(),
+            &mut ticks,
+        );
+
+        for (idx, tick) in ticks.drain(..).enumerate() {
+            let Some(tick) = tick else {
+                continue;
+            };
+
+            // Bot might've been killed by another bot during this tick, in
+            // which case its outcome is gone together with it
+            let Some(mut bot) = bots.alive.take(idx) else {
+                continue;
+            };
+
+            let id = bot.id;
+            let pos = bot.pos;
+
+            if let Some(cooldown) = tick.cooldown {
+                bot.cool_down(cooldown, &mut rng.0);
+            }
+
+            let bot = apply_bot(
+                &mut cmds,
+                &clock,
+                &map,
+                &mut bots,
+                &mut objects,
+                &policy,
+                bot,
+                tick,
+            );
+
+            bots.alive.insert(idx, id, pos, bot);
+        }
+    }
+}
+
+/// Runs CPUs of all bots, collecting their outcomes into `ticks` (one entry
+/// per bots' slot).
+fn run_bots(
+    clock: &Clock,
+    map: &Map,
+    bots: &mut Bots,
+    objects: &Objects,
+    policy: &Policy,
+    sequential: bool,
+    ticks: &mut Vec<Option<BotTick>>,
+) {
+    let mut entries = bots.alive.take_all();
+
+    let run = |bot: &mut Option<Box<AliveBot>>| {
+        run_bot(
+            clock,
+            &bots.alive,
+            map,
+            objects,
+            policy,
+            bot.as_deref_mut()?,
+        )
+    };
+
+    // Dispatching work to other threads isn't free, so for smaller worlds it's
+    // faster to just stay on the current thread
+    if sequential || entries.len() <= MIN_CHUNK_LEN {
+        ticks.extend(entries.iter_mut().map(run));
+    } else {
+        ticks.par_extend(
+            entries.par_iter_mut().with_min_len(MIN_CHUNK_LEN).map(run),
+        );
+    }
+
+    bots.alive.restore_all(entries);
+}
+
+/// When present, bots' CPUs are run on the world's thread instead of being
+/// spread across many threads - see [`Config::sequential`].
+///
+/// [`Config::sequential`]: crate::Config::sequential
+#[derive(Debug, Resource)]
+pub struct SequentialTick;
+
+/// Minimum number of bots ticked by a single thread - ticking a bot is pretty
+/// cheap, so we don't want to dispatch each one separately.
+const MIN_CHUNK_LEN: usize = 32;
+
+/// Outcome of running bot's CPU, see [`run_bot()`].
+pub struct BotTick {
+    pc: u32,
+    result: Result<Option<BotAction>, Box<str>>,
+    cooldown: Option<BotCooldown>,
+}
+
+/// Runs bot's CPU, returning `None` if there's nothing to apply afterwards -
+/// e.g. when the bot is paused on a breakpoint or it's just crunching numbers.
+fn run_bot(
+    clock: &Clock,
+    bots: &AliveBots,
+    map: &Map,
+    objects: &Objects,
+    policy: &Policy,
+    bot: &mut AliveBot,
+) -> Option<BotTick> {
+    let pc = bot.cpu.pc();
 
-                bots.alive.insert(idx, id, pos, bot);
+    if let Some(debugger) = &mut bot.debugger {
+        let was_paused = debugger.is_paused();
+
+        if !debugger.tick(pc) {
+            if !was_paused {
+                bot.log(clock, format!("stopped on breakpoint at 0x{pc:08x}"));
             }
 
-            idx += 1;
+            return None;
         }
     }
+
+    let mut cooldown = None;
+    let result = bot.tick(bots, map, objects, policy, &mut cooldown);
+
+    if let Ok(None) = result
+        && cooldown.is_none()
+        && !bot.battery.is_depleted()
+    {
+        return None;
+    }
+
+    Some(BotTick {
+        pc,
+        result,
+        cooldown,
+    })
 }
 
-fn tick_bot(
+#[allow(clippy::too_many_arguments)]
+fn apply_bot(
     cmds: &mut Commands,
     clock: &Clock,
     map: &Map,
     bots: &mut Bots,
     objects: &mut Objects,
-    rng: &mut WorldRng,
+    policy: &Policy,
     mut bot: Box<AliveBot>,
+    tick: BotTick,
 ) -> Option<Box<AliveBot>> {
-    match bot.tick(&bots.alive, map, objects, rng) {
+    let BotTick { pc, result, .. } = tick;
+
+    match result {
+        Ok(_) if bot.battery.is_depleted() => {
+            cmds.send_event(KillBot {
+                killed: Some(bot),
+                reason: "battery depleted".into(),
+                killer: None,
+            });
+
+            return None;
+        }
+
         Ok(Some(BotAction::ArmDrop { at, idx })) => {
             if let Some((id, obj)) = bot.inventory.take(idx) {
                 bot.log(
@@ -64,7 +211,13 @@ fn tick_bot(
         }
 
         Ok(Some(BotAction::ArmPick { at })) => {
-            if let Some((id, obj)) = objects.remove_at(at) {
+            let is_own_flag = policy.mode.kind == GameMode::CaptureTheFlag
+                && bot.team.is_some()
+                && objects.get_at(at).is_some_and(|obj| obj.team() == bot.team);
+
+            if is_own_flag {
+                bot.log(clock, "tried to pick own team's flag");
+            } else if let Some((id, obj)) = objects.remove_at(at) {
                 match bot.inventory.add(id, obj) {
                     Ok(_) => {
                         cmds.send_event(Event::ObjectPicked { id });
@@ -100,7 +253,16 @@ fn tick_bot(
         }
 
         Ok(Some(BotAction::ArmStab { at })) => {
-            if let Some(killed) = bots.alive.remove_at(at) {
+            let is_teammate = bot.team.is_some()
+                && bots
+                    .alive
+                    .lookup_at(at)
+                    .and_then(|id| bots.alive.get(id))
+                    .is_some_and(|other| other.team == bot.team);
+
+            if is_teammate && !policy.friendly_fire {
+                bot.log(clock, "stabbed a teammate (friendly fire is off)");
+            } else if let Some(killed) = bots.alive.remove_at(at) {
                 bot.log(clock, format!("killed {} (knife)", killed.id));
 
                 cmds.send_event(KillBot {
@@ -137,14 +299,35 @@ fn tick_bot(
             _ => (),
         },
 
+        Ok(Some(BotAction::RadioSend { range, data })) => {
+            for other in bots.alive.iter_mut() {
+                let dist = (other.pos - bot.pos).abs().max_element();
+
+                if dist <= range as i32 {
+                    other.radio.receive(BotRadioMessage { from: bot.id, data });
+                }
+            }
+        }
+
         Ok(None) => {
             //
         }
 
         Err(err) => {
+            for addr in bot.cpu.backtrace().into_iter().rev() {
+                let msg = format!("  called from {}", describe(&bot.fw, addr));
+
+                bot.log(clock, msg);
+            }
+
+            let reason = format!(
+                "firmware crashed: {err} (at {})",
+                describe(&bot.fw, pc)
+            );
+
             cmds.send_event(KillBot {
                 killed: Some(bot),
-                reason: format!("firmware crashed: {err}"),
+                reason,
                 killer: None,
             });
 
@@ -154,3 +337,12 @@ fn tick_bot(
 
     Some(bot)
 }
+
+/// Returns a human-readable location of given address, e.g.
+/// `0x00101234 in kartoffel::main`.
+fn describe(fw: &Firmware, addr: u32) -> String {
+    match fw.symbolize(addr) {
+        Some(fun) => format!("0x{addr:08x} in {fun}"),
+        None => format!("0x{addr:08x}"),
+    }
+}
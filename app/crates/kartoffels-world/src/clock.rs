@@ -1,12 +1,14 @@
 use anyhow::Result;
 use bevy_ecs::system::{Res, ResMut, Resource};
 use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use std::thread;
 use std::time::{Duration, Instant};
 use tokio::sync::oneshot;
 
-#[derive(Clone, Debug, Default, PartialEq, Eq, Resource, Serialize)]
+#[derive(
+    Clone, Debug, Default, PartialEq, Eq, Resource, Serialize, Deserialize,
+)]
 pub enum Clock {
     /// Simulates at 64k bot-ticks per second
     #[default]
@@ -131,19 +133,29 @@ impl Fuel {
         self.callback = Some(callback);
     }
 
-    pub fn tick(&mut self, clock: &Clock) {
+    /// Burns fuel for ticks simulated since the last call, returning whether
+    /// the fuel has just run out.
+    pub fn tick(&mut self, clock: &Clock) -> bool {
         self.remaining = self.remaining.saturating_sub(clock.ticks());
 
         if self.is_empty()
             && let Some(callback) = self.callback.take()
         {
             _ = callback.send(());
+
+            true
+        } else {
+            false
         }
     }
 
     pub fn is_empty(&self) -> bool {
         self.remaining == 0
     }
+
+    pub fn remaining(&self) -> u32 {
+        self.remaining
+    }
 }
 
 pub fn sleep(clock: Res<Clock>, mut mtr: ResMut<Metronome>) {
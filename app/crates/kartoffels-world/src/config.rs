@@ -1,4 +1,5 @@
 use crate::{Clock, Policy, Theme};
+use anyhow::{anyhow, Result};
 use kartoffels_utils::Id;
 use rand::SeedableRng;
 use rand_chacha::ChaCha8Rng;
@@ -13,14 +14,27 @@ pub struct Config {
     pub path: Option<PathBuf>,
     pub policy: Policy,
     pub seed: Option<<ChaCha8Rng as SeedableRng>::Seed>,
+
+    /// Whether bots should be ticked strictly one after another, on a single
+    /// thread - this is slower, but comes handy for checking that ticking
+    /// bots in parallel doesn't affect the simulation
+    pub sequential: bool,
+
     pub theme: Option<Theme>,
 }
 
 impl Config {
-    pub(crate) fn validate(&self) {
+    pub(crate) fn validate(&self) -> Result<()> {
         // We store bot indices into map's tile metadata and since those are u8,
         // we can't have than 256 bots
-        assert!(self.policy.max_alive_bots <= 256);
-        assert!(self.policy.max_queued_bots <= 256);
+        if self.policy.max_alive_bots > 256 {
+            return Err(anyhow!("max-alive-bots must be at most 256"));
+        }
+
+        if self.policy.max_queued_bots > 256 {
+            return Err(anyhow!("max-queued-bots must be at most 256"));
+        }
+
+        Ok(())
     }
 }
@@ -7,6 +7,7 @@ use crate::{BotId, ObjectId, Ticks};
 use bevy_ecs::event::Event as BevyEvent;
 use bevy_ecs::system::Resource;
 use glam::IVec2;
+use serde::Serialize;
 use tokio::sync::broadcast;
 
 #[derive(Debug, Resource)]
@@ -23,18 +24,20 @@ impl Events {
     }
 }
 
-#[derive(Clone, Copy, Debug, BevyEvent)]
+#[derive(Clone, Copy, Debug, Serialize, BevyEvent)]
+#[serde(tag = "type", rename_all = "snake_case")]
 pub enum Event {
-    BotBorn { id: BotId },
+    BotBorn { id: BotId, team: Option<u8> },
     BotDied { id: BotId, age: Ticks },
     BotMoved { id: BotId, at: IVec2 },
     BotScored { id: BotId },
     BotDiscarded { id: BotId },
     ObjectPicked { id: ObjectId },
     ObjectDropped { id: ObjectId },
+    RoundEnded { winner: Option<BotId> },
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Serialize)]
 pub struct EventLetter {
     pub event: Event,
     pub version: u64,
@@ -55,7 +55,7 @@ impl EventStream {
 
     pub async fn next_born_bot(&mut self) -> Result<BotId> {
         loop {
-            if let Event::BotBorn { id } = self.next().await?.event {
+            if let Event::BotBorn { id, .. } = self.next().await?.event {
                 return Ok(id);
             }
         }
@@ -3,14 +3,17 @@ mod systems;
 pub use self::systems::*;
 use crate::{
     BotId, Clock, Dir, EventLetter, EventStream, Map, Object, ObjectId,
-    Snapshot, SnapshotStream,
+    SnapshotPacket, SnapshotStream,
 };
 use anyhow::{anyhow, Context, Result};
 use arc_swap::{ArcSwap, Guard};
 use bevy_ecs::system::Resource;
 use derivative::Derivative;
 use glam::IVec2;
+use kartoffels_cpu::CpuProfile;
 use kartoffels_utils::Id;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
 use std::sync::Arc;
 use tokio::sync::{broadcast, mpsc, oneshot, watch};
 
@@ -56,7 +59,7 @@ impl Handle {
     }
 
     pub fn version(&self) -> u64 {
-        self.shared.snapshots.borrow().version
+        self.shared.snapshots.borrow().snapshot.version
     }
 
     pub fn on_last_drop(
@@ -69,6 +72,11 @@ impl Handle {
         self
     }
 
+    /// Simulates given number of ticks, returning once they've passed.
+    ///
+    /// With [`Clock::Manual`] the world doesn't move on its own, so this is the
+    /// only way to progress it; with other clocks the world gets resumed and
+    /// then paused once the ticks have passed.
     pub async fn tick(&self, fuel: u32) -> Result<()> {
         let (tx, rx) = oneshot::channel();
 
@@ -142,6 +150,93 @@ impl Handle {
         rx.await.context(Self::ERR)
     }
 
+    /// Sends a message to given bot's serial port, from where the firmware can
+    /// read it through `serial_read()`.
+    pub async fn send_to_bot(&self, id: BotId, msg: String) -> Result<()> {
+        let (tx, rx) = oneshot::channel();
+
+        self.send(Request::SendToBot { id, msg, tx }).await?;
+
+        rx.await.context(Self::ERR)?
+    }
+
+    /// Pauses given bot, attaching a debugger to it if necessary.
+    ///
+    /// While paused, bot doesn't get ticked at all - use [`Self::step_bot()`]
+    /// to execute its firmware instruction by instruction.
+    pub async fn pause_bot(&self, id: BotId) -> Result<()> {
+        let (tx, rx) = oneshot::channel();
+
+        self.send(Request::PauseBot { id, tx }).await?;
+
+        rx.await.context(Self::ERR)
+    }
+
+    pub async fn resume_bot(&self, id: BotId) -> Result<()> {
+        let (tx, rx) = oneshot::channel();
+
+        self.send(Request::ResumeBot { id, tx }).await?;
+
+        rx.await.context(Self::ERR)
+    }
+
+    /// Executes a single instruction of a paused bot.
+    pub async fn step_bot(&self, id: BotId) -> Result<()> {
+        let (tx, rx) = oneshot::channel();
+
+        self.send(Request::StepBot { id, tx }).await?;
+
+        rx.await.context(Self::ERR)
+    }
+
+    /// Makes given bot pause once its pc reaches given address.
+    pub async fn add_breakpoint(&self, id: BotId, addr: u32) -> Result<()> {
+        let (tx, rx) = oneshot::channel();
+
+        self.send(Request::AddBreakpoint { id, addr, tx }).await?;
+
+        rx.await.context(Self::ERR)
+    }
+
+    pub async fn remove_breakpoint(&self, id: BotId, addr: u32) -> Result<()> {
+        let (tx, rx) = oneshot::channel();
+
+        self.send(Request::RemoveBreakpoint { id, addr, tx })
+            .await?;
+
+        rx.await.context(Self::ERR)
+    }
+
+    /// Starts profiling given bot, resetting samples collected so far.
+    ///
+    /// Each call must be paired with [`Self::stop_profiling_bot()`] - profiling
+    /// lasts until all viewers stop it or until the bot dies.
+    pub async fn start_profiling_bot(&self, id: BotId) -> Result<()> {
+        let (tx, rx) = oneshot::channel();
+
+        self.send(Request::StartProfilingBot { id, tx }).await?;
+
+        rx.await.context(Self::ERR)?
+    }
+
+    /// Returns samples collected since profiling has been started, grouped by
+    /// functions.
+    pub async fn profile_bot(&self, id: BotId) -> Result<CpuProfile> {
+        let (tx, rx) = oneshot::channel();
+
+        self.send(Request::ProfileBot { id, tx }).await?;
+
+        rx.await.context(Self::ERR)?
+    }
+
+    pub async fn stop_profiling_bot(&self, id: BotId) -> Result<()> {
+        let (tx, rx) = oneshot::channel();
+
+        self.send(Request::StopProfilingBot { id, tx }).await?;
+
+        rx.await.context(Self::ERR)
+    }
+
     pub async fn set_map(&self, map: Map) -> Result<()> {
         let (tx, rx) = oneshot::channel();
 
@@ -200,6 +295,22 @@ impl Handle {
         rx.await.context(Self::ERR)
     }
 
+    /// Starts recording this world into given file, so that it can be later
+    /// re-run through [`crate::replay()`].
+    ///
+    /// Recording lasts until the world is shut down.
+    pub async fn record(&self, path: impl Into<PathBuf>) -> Result<()> {
+        let (tx, rx) = oneshot::channel();
+
+        self.send(Request::Record {
+            path: path.into(),
+            tx,
+        })
+        .await?;
+
+        rx.await.context(Self::ERR)?
+    }
+
     async fn send(&self, request: Request) -> Result<()> {
         self.shared
             .tx
@@ -227,7 +338,7 @@ pub struct SharedHandle {
     pub id: Id,
     pub name: Arc<ArcSwap<String>>,
     pub events: Option<broadcast::Sender<EventLetter>>,
-    pub snapshots: watch::Sender<Arc<Snapshot>>,
+    pub snapshots: watch::Sender<Arc<SnapshotPacket>>,
 }
 
 #[derive(Debug, Resource)]
@@ -287,6 +398,72 @@ pub enum Request {
         tx: oneshot::Sender<()>,
     },
 
+    SendToBot {
+        id: BotId,
+        msg: String,
+
+        #[derivative(Debug = "ignore")]
+        tx: oneshot::Sender<Result<()>>,
+    },
+
+    PauseBot {
+        id: BotId,
+
+        #[derivative(Debug = "ignore")]
+        tx: oneshot::Sender<()>,
+    },
+
+    ResumeBot {
+        id: BotId,
+
+        #[derivative(Debug = "ignore")]
+        tx: oneshot::Sender<()>,
+    },
+
+    StepBot {
+        id: BotId,
+
+        #[derivative(Debug = "ignore")]
+        tx: oneshot::Sender<()>,
+    },
+
+    AddBreakpoint {
+        id: BotId,
+        addr: u32,
+
+        #[derivative(Debug = "ignore")]
+        tx: oneshot::Sender<()>,
+    },
+
+    RemoveBreakpoint {
+        id: BotId,
+        addr: u32,
+
+        #[derivative(Debug = "ignore")]
+        tx: oneshot::Sender<()>,
+    },
+
+    StartProfilingBot {
+        id: BotId,
+
+        #[derivative(Debug = "ignore")]
+        tx: oneshot::Sender<Result<()>>,
+    },
+
+    ProfileBot {
+        id: BotId,
+
+        #[derivative(Debug = "ignore")]
+        tx: oneshot::Sender<Result<CpuProfile>>,
+    },
+
+    StopProfilingBot {
+        id: BotId,
+
+        #[derivative(Debug = "ignore")]
+        tx: oneshot::Sender<()>,
+    },
+
     SetMap {
         map: Map,
 
@@ -323,9 +500,16 @@ pub enum Request {
         #[derivative(Debug = "ignore")]
         tx: oneshot::Sender<()>,
     },
+
+    Record {
+        path: PathBuf,
+
+        #[derivative(Debug = "ignore")]
+        tx: oneshot::Sender<Result<()>>,
+    },
 }
 
-#[derive(Derivative)]
+#[derive(Clone, Derivative, Serialize, Deserialize)]
 #[derivative(Debug)]
 pub struct CreateBotRequest {
     #[derivative(Debug = "ignore")]
@@ -334,6 +518,10 @@ pub struct CreateBotRequest {
     pub dir: Option<Dir>,
     pub instant: bool,
     pub oneshot: bool,
+    pub owner: Option<Arc<str>>,
+    pub team: Option<u8>,
+    #[serde(default)]
+    pub symbols: bool,
 }
 
 impl CreateBotRequest {
@@ -344,6 +532,9 @@ impl CreateBotRequest {
             dir: None,
             instant: false,
             oneshot: false,
+            owner: None,
+            team: None,
+            symbols: false,
         }
     }
 
@@ -366,4 +557,25 @@ impl CreateBotRequest {
         self.oneshot = true;
         self
     }
+
+    /// Records who uploaded this bot, so that they can manage it later - the
+    /// world doesn't interpret this value, it only carries it around.
+    pub fn owner(mut self, owner: impl Into<Arc<str>>) -> Self {
+        self.owner = Some(owner.into());
+        self
+    }
+
+    pub fn team(mut self, team: impl Into<Option<u8>>) -> Self {
+        self.team = team.into();
+        self
+    }
+
+    /// Keeps firmware's symbols, so that the bot's code can be symbolized
+    /// when debugging it - see [`Firmware::from_elf_with_symbols()`].
+    ///
+    /// [`Firmware::from_elf_with_symbols()`]: kartoffels_cpu::Firmware::from_elf_with_symbols
+    pub fn with_symbols(mut self) -> Self {
+        self.symbols = true;
+        self
+    }
 }
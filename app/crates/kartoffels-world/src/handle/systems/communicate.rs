@@ -1,7 +1,9 @@
 use crate::{
-    Bots, Clock, CreateBot, Fuel, HandleRx, KillBot, Map, Objects, Paused,
-    Request, Shutdown, Spawn, WorldName, WorldRng,
+    BotDebugger, BotId, Bots, Clock, CreateBot, Fuel, HandleRx, KillBot, Map,
+    Objects, Paused, Recorder, ReplayRequest, Request, Shutdown, Spawn,
+    StartRecording, WorldName, WorldRng,
 };
+use anyhow::{anyhow, Context};
 use bevy_ecs::system::{Commands, Res, ResMut};
 use std::sync::Arc;
 use tokio::sync::mpsc::error::TryRecvError;
@@ -17,11 +19,16 @@ pub fn communicate(
     mut objects: ResMut<Objects>,
     mut paused: ResMut<Paused>,
     mut rng: ResMut<WorldRng>,
+    mut recorder: Option<ResMut<Recorder>>,
     mut rx: ResMut<HandleRx>,
     mut spawn: ResMut<Spawn>,
     name: Res<WorldName>,
 ) {
-    fuel.tick(&clock);
+    // Non-manual clocks don't wait for fuel, so once it runs out we pause the
+    // world to stop exactly after the requested number of ticks
+    if fuel.tick(&clock) && !matches!(*clock, Clock::Manual { .. }) {
+        paused.set(true);
+    }
 
     loop {
         let request = match *clock {
@@ -38,6 +45,12 @@ pub fn communicate(
 
         if let Ok(request) = &request {
             debug!(?request, "processing");
+
+            if let Some(recorder) = &mut recorder
+                && let Some(request) = ReplayRequest::new(request)
+            {
+                recorder.record(request);
+            }
         }
 
         match request {
@@ -46,6 +59,10 @@ pub fn communicate(
                 tx,
             }) => {
                 fuel.set(fuel_to_add, tx);
+
+                if !matches!(*clock, Clock::Manual { .. }) {
+                    paused.set(false);
+                }
             }
 
             Ok(Request::Pause { tx }) => {
@@ -95,6 +112,80 @@ pub fn communicate(
                 _ = tx.send(());
             }
 
+            Ok(Request::SendToBot { id, msg, tx }) => {
+                let result = match bots.alive.get_mut(id) {
+                    Some(bot) => bot.serial.send(&msg),
+                    None => Err(anyhow!("bot is not alive")),
+                };
+
+                _ = tx.send(result);
+            }
+
+            Ok(Request::PauseBot { id, tx }) => {
+                debug_bot(&mut bots, id, BotDebugger::pause);
+
+                _ = tx.send(());
+            }
+
+            Ok(Request::ResumeBot { id, tx }) => {
+                debug_bot(&mut bots, id, BotDebugger::resume);
+
+                _ = tx.send(());
+            }
+
+            Ok(Request::StepBot { id, tx }) => {
+                debug_bot(&mut bots, id, BotDebugger::step);
+
+                _ = tx.send(());
+            }
+
+            Ok(Request::AddBreakpoint { id, addr, tx }) => {
+                debug_bot(&mut bots, id, |debugger| {
+                    debugger.add_breakpoint(addr);
+                });
+
+                _ = tx.send(());
+            }
+
+            Ok(Request::RemoveBreakpoint { id, addr, tx }) => {
+                debug_bot(&mut bots, id, |debugger| {
+                    debugger.remove_breakpoint(addr);
+                });
+
+                _ = tx.send(());
+            }
+
+            Ok(Request::StartProfilingBot { id, tx }) => {
+                let result = match bots.alive.get_mut(id) {
+                    Some(bot) => {
+                        bot.start_profiling();
+                        Ok(())
+                    }
+                    None => Err(anyhow!("bot is not alive")),
+                };
+
+                _ = tx.send(result);
+            }
+
+            Ok(Request::ProfileBot { id, tx }) => {
+                let result = match bots.alive.get(id) {
+                    Some(bot) => {
+                        bot.profile().context("bot is not being profiled")
+                    }
+                    None => Err(anyhow!("bot is not alive")),
+                };
+
+                _ = tx.send(result);
+            }
+
+            Ok(Request::StopProfilingBot { id, tx }) => {
+                if let Some(bot) = bots.alive.get_mut(id) {
+                    bot.stop_profiling();
+                }
+
+                _ = tx.send(());
+            }
+
             Ok(Request::SetMap { map: new_map, tx }) => {
                 *map = new_map;
 
@@ -125,11 +216,19 @@ pub fn communicate(
                 _ = tx.send(());
             }
 
+            Ok(Request::Record { path, tx }) => {
+                cmds.insert_resource(StartRecording { path, tx: Some(tx) });
+            }
+
             Err(TryRecvError::Empty) => {
                 break;
             }
 
             Err(TryRecvError::Disconnected) => {
+                if let Some(recorder) = &mut recorder {
+                    recorder.record(ReplayRequest::Shutdown);
+                }
+
                 cmds.insert_resource(Shutdown { tx: None });
                 return;
             }
@@ -140,3 +239,17 @@ pub fn communicate(
         }
     }
 }
+
+fn debug_bot(bots: &mut Bots, id: BotId, f: impl FnOnce(&mut BotDebugger)) {
+    let Some(bot) = bots.alive.get_mut(id) else {
+        return;
+    };
+
+    f(bot.debugger.get_or_insert_default());
+
+    // Once there's nothing left for the debugger to do, drop it, so that the
+    // bot doesn't pay for it while being ticked
+    if bot.debugger.as_ref().is_some_and(BotDebugger::is_idle) {
+        bot.debugger = None;
+    }
+}
@@ -16,9 +16,11 @@ mod handle;
 mod lifecycle;
 mod lives;
 mod map;
+mod mode;
 mod object;
 mod objects;
 mod policy;
+mod replay;
 mod snapshots;
 mod spec;
 mod stats;
@@ -39,15 +41,21 @@ pub mod prelude {
     pub use crate::events::{Event, EventLetter, EventStream};
     pub use crate::handle::{CreateBotRequest, Handle, Request};
     pub use crate::map::{Map, MapBuilder, Tile, TileKind};
+    pub use crate::mode::{
+        GameMode, Round, RoundBot, RoundResult, RoundResultBot,
+    };
     pub use crate::object::{Object, ObjectId, ObjectKind};
-    pub use crate::policy::Policy;
+    pub use crate::policy::{EnergyPolicy, ModePolicy, Policy};
     pub use crate::snapshots::{
-        AliveBotSnapshot, AliveBotsSnapshot, BotSnapshot, BotsSnapshot,
-        DeadBotSnapshot, DeadBotsSnapshot, ObjectsSnapshot, QueuedBotSnapshot,
-        QueuedBotsSnapshot, Snapshot, SnapshotStream,
+        AliveBotSnapshot, AliveBotsSnapshot, BotDebuggerSnapshot, BotSnapshot,
+        BotsSnapshot, DeadBotSnapshot, DeadBotsSnapshot, MapSnapshot,
+        ObjectsSnapshot, QueuedBotSnapshot, QueuedBotsSnapshot, Snapshot,
+        SnapshotStream,
+    };
+    pub use crate::theme::{
+        ArenaTheme, CaveTheme, DungeonTheme, FileTheme, MazeTheme, Theme,
     };
-    pub use crate::theme::{ArenaTheme, CaveTheme, Theme};
-    pub use crate::utils::Dir;
+    pub use crate::utils::{Dir, Ticks};
 }
 
 pub(crate) use self::bot::*;
@@ -59,15 +67,17 @@ pub(crate) use self::handle::*;
 pub(crate) use self::lifecycle::*;
 pub(crate) use self::lives::*;
 pub(crate) use self::map::*;
+pub(crate) use self::mode::*;
 pub(crate) use self::object::*;
 pub(crate) use self::objects::*;
 pub(crate) use self::policy::*;
+pub(crate) use self::replay::*;
 pub(crate) use self::snapshots::*;
 pub(crate) use self::stats::*;
 pub(crate) use self::storage::*;
 pub(crate) use self::theme::*;
 pub(crate) use self::utils::*;
-use anyhow::Result;
+use anyhow::{Context, Result};
 use arc_swap::ArcSwap;
 use bevy_ecs::event::EventRegistry;
 use bevy_ecs::schedule::{ExecutorKind, IntoSystemConfigs, Schedule};
@@ -81,30 +91,36 @@ use std::path::Path;
 use std::sync::Arc;
 use std::thread;
 use tokio::runtime::Handle as TokioHandle;
-use tokio::sync::{broadcast, mpsc, watch};
+use tokio::sync::{broadcast, mpsc, oneshot, watch};
 use tracing::{info, info_span};
 
-pub fn create(config: Config) -> Handle {
-    config.validate();
-
+pub fn create(mut config: Config) -> Result<Handle> {
     let mut rng = config
         .seed
         .map(ChaCha8Rng::from_seed)
         .unwrap_or_else(ChaCha8Rng::from_entropy);
 
+    let mut objects = Objects::default();
+
+    if let Some(Theme::File(theme)) = &config.theme {
+        theme
+            .setup(&mut rng, &mut config.policy, &mut objects)
+            .context("couldn't set up theme")?;
+    }
+
+    config.validate()?;
+
     let id = config.id.unwrap_or_else(|| rng.gen());
 
-    let map = config
-        .theme
-        .as_ref()
-        .map(|theme| {
-            theme
-                .build(&mut rng, MapBuilder::detached())
-                .now_or_never()
-                .unwrap()
-                .unwrap()
-        })
-        .unwrap_or_default();
+    let map = match &config.theme {
+        Some(theme) => theme
+            .build(&mut rng, MapBuilder::detached())
+            .now_or_never()
+            .unwrap()
+            .context("couldn't build map")?,
+
+        None => Default::default(),
+    };
 
     let res = Resources {
         bots: Default::default(),
@@ -113,13 +129,21 @@ pub fn create(config: Config) -> Handle {
         lives: Default::default(),
         map,
         name: WorldName(Arc::new(ArcSwap::from_pointee(config.name))),
+        objects,
         path: config.path.map(WorldPath),
         policy: config.policy,
         rng: WorldRng(rng),
+        round: Default::default(),
         theme: config.theme,
     };
 
-    create_or_resume(res, config.events)
+    let mut world = create_world(res);
+
+    if config.sequential {
+        world.insert_resource(SequentialTick);
+    }
+
+    Ok(spawn_with_handle(world, config.events))
 }
 
 pub fn resume(id: Id, path: &Path) -> Result<Handle> {
@@ -133,13 +157,50 @@ pub fn resume(id: Id, path: &Path) -> Result<Handle> {
         lives: world.lives.into_owned(),
         map: world.map.into_owned(),
         name: WorldName(name),
+        objects: world.objects.into_owned(),
         path: Some(WorldPath(path.to_owned())),
         policy: world.policy.into_owned(),
         rng: WorldRng(ChaCha8Rng::from_entropy()),
+        round: world.round.into_owned(),
         theme: world.theme.map(|theme| theme.into_owned()),
     };
 
-    Ok(create_or_resume(res, false))
+    Ok(spawn_with_handle(create_world(res), false))
+}
+
+/// Re-runs a recording made through [`Handle::record()`], returning an error if
+/// the replay diverges from the recording.
+pub fn replay(path: &Path) -> Result<()> {
+    let (header, entries) = replay::load(path)?;
+    let name = Arc::new(ArcSwap::from_pointee(header.world.name.into_owned()));
+
+    let res = Resources {
+        bots: header.world.bots.into_owned(),
+        clock: replay::replayed_clock(header.clock),
+        id: WorldId(header.id),
+        lives: header.world.lives.into_owned(),
+        map: header.world.map.into_owned(),
+        name: WorldName(name),
+        objects: header.world.objects.into_owned(),
+        path: None,
+        policy: header.world.policy.into_owned(),
+        rng: WorldRng(header.world.rng.into_owned()),
+        round: header.world.round.into_owned(),
+        theme: header.world.theme.map(|theme| theme.into_owned()),
+    };
+
+    let mut world = create_world(res);
+
+    world.resource_mut::<Paused>().set(header.paused);
+    *world.resource_mut::<Spawn>() = header.spawn;
+
+    if header.fuel > 0 {
+        world
+            .resource_mut::<Fuel>()
+            .set(header.fuel, oneshot::channel().0);
+    }
+
+    replay::run(world, entries)
 }
 
 struct Resources {
@@ -149,14 +210,15 @@ struct Resources {
     lives: Lives,
     map: Map,
     name: WorldName,
+    objects: Objects,
     path: Option<WorldPath>,
     policy: Policy,
     rng: WorldRng,
+    round: Round,
     theme: Option<Theme>,
 }
 
-fn create_or_resume(res: Resources, emit_events: bool) -> Handle {
-    let mut world = create_world(res);
+fn spawn_with_handle(mut world: World, emit_events: bool) -> Handle {
     let handle = create_handle(&mut world, emit_events);
 
     spawn(world);
@@ -173,8 +235,10 @@ fn create_world(res: Resources) -> World {
     world.insert_resource(res.id);
     world.insert_resource(res.map);
     world.insert_resource(res.name);
+    world.insert_resource(res.objects);
     world.insert_resource(res.policy);
     world.insert_resource(res.rng);
+    world.insert_resource(res.round);
     world.insert_resource(res.lives);
 
     if let Some(path) = res.path {
@@ -185,8 +249,8 @@ fn create_world(res: Resources) -> World {
         world.insert_resource(theme);
     }
 
+    world.insert_resource(FinishedRounds::default());
     world.insert_resource(Fuel::default());
-    world.insert_resource(Objects::default()); // TODO persist
     world.insert_resource(Paused::default());
     world.insert_resource(Spawn::default());
     world.insert_resource(Stats::default());
@@ -274,11 +338,15 @@ fn main_schedule() -> Schedule {
         bots::spawn,
         bots::tick.run_if(active),
         bots::kill,
+        mode::score.run_if(active),
         lives::update,
+        mode::update,
         stats::update,
         events::track,
         snapshots::send,
         storage::save,
+        replay::record,
+        replay::start,
         lifecycle::log,
         clock::sleep,
         bevy_ecs::event::event_update_system,
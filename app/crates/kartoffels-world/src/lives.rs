@@ -29,7 +29,7 @@ pub fn update(
 ) {
     for event in events.read() {
         match *event {
-            Event::BotBorn { id } => match lives.entries.entry(id) {
+            Event::BotBorn { id, team } => match lives.entries.entry(id) {
                 hash_map::Entry::Occupied(entry) => {
                     Arc::make_mut(entry.into_mut()).curr.born_at = clock.now();
                 }
@@ -42,6 +42,7 @@ pub fn update(
                         },
                         prev: Default::default(),
                         len: 0,
+                        team,
                     }));
                 }
             },
@@ -78,6 +79,7 @@ pub struct BotLives {
     pub curr: CurrBotLife,
     pub prev: VecDeque<PrevBotLife>,
     pub len: u32,
+    pub team: Option<u8>,
 }
 
 impl BotLives {
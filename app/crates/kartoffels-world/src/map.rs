@@ -4,6 +4,7 @@ pub use self::builder::*;
 use ahash::HashMap;
 use bevy_ecs::system::Resource;
 use glam::{ivec2, uvec2, IVec2, UVec2};
+use rand::seq::SliceRandom;
 use rand::{Rng, RngCore};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::fmt::Write;
@@ -184,6 +185,72 @@ impl Map {
         self.size.as_ivec2() / 2
     }
 
+    /// Returns the floor tile closest to given position, if there's any.
+    pub fn nearest_floor(&self, pos: IVec2) -> Option<IVec2> {
+        let mut best: Option<(i32, IVec2)> = None;
+
+        // Tiles on the ring of radius `r` are at least `r` tiles away, so once
+        // `r` gets past the best distance so far, nothing closer remains
+        for r in 0..=self.size.max_element() as i32 {
+            if let Some((dist, _)) = best
+                && r * r > dist
+            {
+                break;
+            }
+
+            let ring = (-r..=r)
+                .flat_map(|i| [ivec2(i, -r), ivec2(i, r)])
+                .chain((1 - r..r).flat_map(|i| [ivec2(-r, i), ivec2(r, i)]));
+
+            for offset in ring {
+                let dist = offset.length_squared();
+
+                if self.get(pos + offset).is_floor()
+                    && best.is_none_or(|(best, _)| dist < best)
+                {
+                    best = Some((dist, pos + offset));
+                }
+            }
+        }
+
+        best.map(|(_, pos)| pos)
+    }
+
+    /// Places up to `count` chargers on random floor tiles, picking only
+    /// tiles surrounded by floor so that chargers never block a passage.
+    pub fn scatter_chargers(&mut self, rng: &mut impl RngCore, count: u32) {
+        if count == 0 {
+            return;
+        }
+
+        let mut candidates = Vec::new();
+
+        self.for_each(|pos, tile| {
+            if tile.is_floor() {
+                candidates.push(pos);
+            }
+        });
+
+        candidates.shuffle(rng);
+
+        let mut placed = 0;
+
+        for pos in candidates {
+            if placed >= count {
+                break;
+            }
+
+            let is_open = (-1..=1)
+                .flat_map(|dy| (-1..=1).map(move |dx| ivec2(dx, dy)))
+                .all(|offset| self.get(pos + offset).is_floor());
+
+            if is_open {
+                self.set(pos, TileKind::CHARGER);
+                placed += 1;
+            }
+        }
+    }
+
     pub fn size(&self) -> UVec2 {
         self.size
     }
@@ -253,6 +320,10 @@ impl Anchors {
         self.anchors[&id]
     }
 
+    pub fn find(&self, id: char) -> Option<IVec2> {
+        self.anchors.get(&id).copied()
+    }
+
     pub fn fill(&self, map: &mut Map, tile: impl Into<Tile>) {
         let tile = tile.into();
 
@@ -335,6 +406,7 @@ pub struct TileKind;
 impl TileKind {
     pub const BOT: u8 = b'@';
     pub const BOT_CHEVRON: u8 = b'~';
+    pub const CHARGER: u8 = b'%';
     pub const DOOR: u8 = b'+';
     pub const FLOOR: u8 = b'.';
     pub const VOID: u8 = b' ';
@@ -342,3 +414,41 @@ impl TileKind {
     pub const WALL_H: u8 = b'-';
     pub const WALL_V: u8 = b'|';
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn nearest_floor() {
+        let (map, _) = Map::parse("#####\n#.###\n#####\n###..");
+
+        assert_eq!(Some(ivec2(1, 1)), map.nearest_floor(ivec2(1, 1)));
+        assert_eq!(Some(ivec2(1, 1)), map.nearest_floor(ivec2(2, 1)));
+        assert_eq!(Some(ivec2(3, 3)), map.nearest_floor(ivec2(3, 2)));
+        assert_eq!(Some(ivec2(4, 3)), map.nearest_floor(ivec2(6, 4)));
+
+        let (map, _) = Map::parse("###\n###");
+
+        assert_eq!(None, map.nearest_floor(ivec2(1, 1)));
+    }
+
+    #[test]
+    fn scatter_chargers() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let (mut map, _) = Map::parse("#####\n#...#\n#...#\n#...#\n#..##");
+
+        map.scatter_chargers(&mut rng, 3);
+
+        assert_eq!(TileKind::CHARGER, map.get(ivec2(2, 2)).kind);
+
+        let chargers = map
+            .tiles
+            .iter()
+            .filter(|tile| tile.kind == TileKind::CHARGER);
+
+        assert_eq!(1, chargers.count());
+    }
+}
@@ -0,0 +1,434 @@
+use crate::{
+    AliveBots, BotId, Bots, Clock, Event, Map, Object, ObjectId, ObjectKind,
+    Objects, Policy, Ticks, WorldName, WorldPath, WorldRng,
+};
+use ahash::AHashMap;
+use anyhow::{anyhow, Error, Result};
+use bevy_ecs::event::EventReader;
+use bevy_ecs::system::{Commands, Res, ResMut, Resource};
+use chrono::{DateTime, Utc};
+use glam::IVec2;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::fmt;
+use std::str::FromStr;
+use tracing::debug;
+
+/// How bots earn points.
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum GameMode {
+    /// Bots score by killing other bots
+    #[default]
+    Deathmatch,
+
+    /// Each team has a base with its own flag (see [`ModePolicy::bases`]) -
+    /// a bot scores a point when it brings an enemy flag next to its team's
+    /// base; captured flags and flags carried by a bot that dies return to
+    /// their bases
+    CaptureTheFlag,
+
+    /// Bots score a point for each second they're the only one standing on
+    /// the hill, i.e. within [`ModePolicy::hill_radius`] of
+    /// [`ModePolicy::hill`]
+    KingOfTheHill,
+
+    /// Bots score a point for each gem they pick; picked gems reappear at a
+    /// random place
+    GemCollection,
+}
+
+impl FromStr for GameMode {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "deathmatch" => Ok(GameMode::Deathmatch),
+            "capture-the-flag" => Ok(GameMode::CaptureTheFlag),
+            "king-of-the-hill" => Ok(GameMode::KingOfTheHill),
+            "gem-collection" => Ok(GameMode::GemCollection),
+            s => Err(anyhow!("unknown mode: {s}")),
+        }
+    }
+}
+
+impl fmt::Display for GameMode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GameMode::Deathmatch => write!(f, "deathmatch"),
+            GameMode::CaptureTheFlag => write!(f, "capture-the-flag"),
+            GameMode::KingOfTheHill => write!(f, "king-of-the-hill"),
+            GameMode::GemCollection => write!(f, "gem-collection"),
+        }
+    }
+}
+
+/// State of the current round.
+///
+/// Stats here are tracked separately from [`crate::Lives`], since they
+/// survive bot's death - they get reset only when the round ends.
+#[derive(
+    Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, Resource,
+)]
+pub struct Round {
+    /// Number of rounds completed so far
+    pub idx: u32,
+
+    pub age: Ticks,
+    pub bots: AHashMap<BotId, RoundBot>,
+}
+
+impl Round {
+    pub fn leader(&self) -> Option<(BotId, u32)> {
+        self.bots
+            .iter()
+            .filter(|(_, bot)| bot.score > 0)
+            .max_by_key(|(id, bot)| (bot.score, **id))
+            .map(|(id, bot)| (*id, bot.score))
+    }
+
+    fn finish(
+        &mut self,
+        world: String,
+        ended_at: DateTime<Utc>,
+    ) -> RoundResult {
+        let winner = self.leader().map(|(id, _)| id);
+
+        let mut bots: Vec<_> = self
+            .bots
+            .drain()
+            .map(|(id, bot)| RoundResultBot {
+                id,
+                score: bot.score,
+                kills: bot.kills,
+                age: bot.age,
+            })
+            .collect();
+
+        bots.sort_by_key(|bot| (Reverse(bot.score), bot.id));
+
+        let result = RoundResult {
+            world,
+            idx: self.idx,
+            ended_at,
+            age: self.age,
+            winner,
+            bots,
+        };
+
+        self.idx += 1;
+        self.age = Default::default();
+
+        result
+    }
+}
+
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize,
+)]
+pub struct RoundBot {
+    pub score: u32,
+    pub kills: u32,
+
+    /// For how long the bot has been alive during this round
+    pub age: Ticks,
+}
+
+/// Summary of a finished round, as archived in `<world>.rounds`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RoundResult {
+    pub world: String,
+    pub idx: u32,
+    pub ended_at: DateTime<Utc>,
+    pub age: Ticks,
+    pub winner: Option<BotId>,
+
+    /// Bots that took part in the round, sorted by score
+    pub bots: Vec<RoundResultBot>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RoundResultBot {
+    pub id: BotId,
+    pub score: u32,
+    pub kills: u32,
+    pub age: Ticks,
+}
+
+/// Rounds that have ended, but haven't been archived yet.
+///
+/// Archiving involves I/O, so it's done by the storage system, outside of the
+/// event loop.
+#[derive(Debug, Default, Resource)]
+pub struct FinishedRounds(pub Vec<RoundResult>);
+
+/// Awards points according to the current [`GameMode`].
+///
+/// Points are awarded through [`Event::BotScored`], so that they end up
+/// counted both in the current round and in bot's lives.
+#[allow(clippy::too_many_arguments)]
+pub fn score(
+    mut cmds: Commands,
+    mut bots: ResMut<Bots>,
+    clock: Res<Clock>,
+    map: Res<Map>,
+    mut objects: ResMut<Objects>,
+    policy: Res<Policy>,
+    mut round: ResMut<Round>,
+    mut rng: ResMut<WorldRng>,
+) {
+    let ticks = clock.ticks() as u64;
+    let prev_age = round.age;
+
+    round.age = Ticks::new(prev_age.ticks() + ticks);
+
+    for bot in bots.alive.iter() {
+        let entry = round.bots.entry(bot.id).or_default();
+
+        entry.age = Ticks::new(entry.age.ticks() + ticks);
+    }
+
+    let is_new_second = round.age.seconds() > prev_age.seconds();
+
+    match policy.mode.kind {
+        GameMode::Deathmatch => {
+            // Handled by `bots::kill()`
+        }
+
+        GameMode::CaptureTheFlag => {
+            let mut captured = Vec::new();
+
+            for bot in bots.alive.iter_mut() {
+                let Some(team) = bot.team else {
+                    continue;
+                };
+
+                let Some(base) = policy.mode.bases.get(&team) else {
+                    continue;
+                };
+
+                if (bot.pos - base).abs().max_element() > 1 {
+                    continue;
+                }
+
+                while let Some((id, obj)) = bot.inventory.take_if(|obj| {
+                    obj.team().is_some_and(|flag_team| flag_team != team)
+                }) {
+                    captured.push((bot.id, bot.pos, id, obj));
+                }
+            }
+
+            for (bot, pos, id, obj) in captured {
+                cmds.send_event(Event::BotScored { id: bot });
+
+                let pos = policy.mode.base_of(obj).unwrap_or(pos);
+
+                drop_at(
+                    &mut rng.0,
+                    &map,
+                    &bots.alive,
+                    &mut objects,
+                    id,
+                    obj,
+                    pos,
+                );
+            }
+
+            raise_flags(&mut rng.0, &map, &bots.alive, &mut objects, &policy);
+        }
+
+        GameMode::KingOfTheHill => {
+            if is_new_second
+                && let Some(hill) =
+                    policy.mode.hill.or_else(|| map.nearest_floor(map.center()))
+            {
+                let radius = policy.mode.hill_radius as f32;
+
+                let mut kings = bots.alive.iter().filter(|bot| {
+                    bot.pos.as_vec2().distance(hill.as_vec2()) <= radius
+                });
+
+                if let Some(king) = kings.next()
+                    && kings.next().is_none()
+                {
+                    cmds.send_event(Event::BotScored { id: king.id });
+                }
+            }
+        }
+
+        GameMode::GemCollection => {
+            let mut picked = Vec::new();
+
+            for bot in bots.alive.iter_mut() {
+                while let Some((id, obj)) =
+                    bot.inventory.take_kind(ObjectKind::GEM)
+                {
+                    picked.push((bot.id, id, obj));
+                }
+            }
+
+            for (bot, id, obj) in picked {
+                cmds.send_event(Event::BotScored { id: bot });
+
+                place(&mut rng.0, &map, &bots.alive, &mut objects, id, obj);
+            }
+        }
+    }
+}
+
+/// Tracks scores of the current round and ends it once someone reaches
+/// [`ModePolicy::win_score`] or once the round lasts for
+/// [`ModePolicy::round_duration`].
+///
+/// Results of finished rounds are queued in [`FinishedRounds`], from where
+/// they get archived next to the world's file, so that the store can build
+/// leaderboards out of them.
+#[allow(clippy::too_many_arguments)]
+pub fn update(
+    mut cmds: Commands,
+    clock: Res<Clock>,
+    name: Res<WorldName>,
+    path: Option<Res<WorldPath>>,
+    policy: Res<Policy>,
+    mut round: ResMut<Round>,
+    mut finished: ResMut<FinishedRounds>,
+    mut events: EventReader<Event>,
+) {
+    for event in events.read() {
+        match *event {
+            Event::BotScored { id } => {
+                round.bots.entry(id).or_default().score += 1;
+            }
+
+            // If rounds never end, we'd keep discarded bots forever
+            Event::BotDiscarded { id } if !policy.mode.has_rounds() => {
+                round.bots.remove(&id);
+            }
+
+            _ => (),
+        }
+    }
+
+    let has_winner = policy.mode.win_score.is_some_and(|win_score| {
+        round.leader().is_some_and(|(_, score)| score >= win_score)
+    });
+
+    let has_timed_out = policy
+        .mode
+        .round_duration
+        .is_some_and(|duration| round.age.seconds() >= duration as u64);
+
+    if !has_winner && !has_timed_out {
+        return;
+    }
+
+    let result = round.finish(name.0.load().to_string(), clock.now());
+
+    debug!(idx = result.idx, winner = ?result.winner, "round ended");
+
+    cmds.send_event(Event::RoundEnded {
+        winner: result.winner,
+    });
+
+    if path.is_some() {
+        finished.0.push(result);
+    }
+}
+
+/// Puts object at a random free floor tile, discarding it if there's no
+/// space left.
+pub(crate) fn place(
+    rng: &mut impl RngCore,
+    map: &Map,
+    bots: &AliveBots,
+    objects: &mut Objects,
+    id: ObjectId,
+    obj: Object,
+) {
+    for _ in 0..1024 {
+        let pos = map.sample_pos(rng);
+
+        if is_free(map, bots, objects, pos) {
+            objects.add(id, obj, Some(pos));
+            return;
+        }
+    }
+}
+
+/// Drops object at given position or, if it's occupied, at a random place.
+pub(crate) fn drop_at(
+    rng: &mut impl RngCore,
+    map: &Map,
+    bots: &AliveBots,
+    objects: &mut Objects,
+    id: ObjectId,
+    obj: Object,
+    pos: IVec2,
+) {
+    if is_free(map, bots, objects, pos) {
+        objects.add(id, obj, Some(pos));
+    } else {
+        place(rng, map, bots, objects, id, obj);
+    }
+}
+
+/// Raises flags of teams that don't have one, be it lying somewhere or
+/// carried by a bot - this happens when the world starts or when a carrier
+/// gets deleted; if team's base is occupied, the flag gets raised later.
+fn raise_flags(
+    rng: &mut impl RngCore,
+    map: &Map,
+    bots: &AliveBots,
+    objects: &mut Objects,
+    policy: &Policy,
+) {
+    let mut missing: Vec<_> = policy.mode.bases.iter().collect();
+
+    if missing.is_empty() {
+        return;
+    }
+
+    let flags = objects
+        .iter()
+        .map(|entry| entry.obj)
+        .chain(bots.iter().flat_map(|bot| bot.inventory.iter()))
+        .filter_map(|obj| obj.team());
+
+    for team in flags {
+        missing.retain(|(base_team, _)| **base_team != team);
+    }
+
+    for (&team, &base) in missing {
+        if is_free(map, bots, objects, base) {
+            objects.create(rng, Object::flag(team), Some(base));
+        }
+    }
+}
+
+fn is_free(map: &Map, bots: &AliveBots, objects: &Objects, pos: IVec2) -> bool {
+    map.get(pos).is_floor()
+        && objects.lookup_at(pos).is_none()
+        && bots.lookup_at(pos).is_none()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn game_mode() {
+        for mode in [
+            GameMode::Deathmatch,
+            GameMode::CaptureTheFlag,
+            GameMode::KingOfTheHill,
+            GameMode::GemCollection,
+        ] {
+            assert_eq!(mode, mode.to_string().parse().unwrap());
+        }
+
+        assert!(GameMode::from_str("tag").is_err());
+    }
+}
@@ -17,6 +17,22 @@ impl Object {
         }
     }
 
+    /// Creates a flag that belongs to given team, as used in
+    /// [`crate::GameMode::CaptureTheFlag`].
+    pub fn flag(team: u8) -> Self {
+        Self {
+            kind: ObjectKind::FLAG,
+            meta: [1, team, 0],
+        }
+    }
+
+    /// Returns team this object belongs to - currently only flags created
+    /// through [`Self::flag()`] have one.
+    pub fn team(&self) -> Option<u8> {
+        (self.kind == ObjectKind::FLAG && self.meta[0] == 1)
+            .then_some(self.meta[1])
+    }
+
     pub fn name(&self) -> &'static str {
         match self.kind {
             ObjectKind::FLAG => "flag",
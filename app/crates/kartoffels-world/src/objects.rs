@@ -3,12 +3,15 @@ use ahash::AHashMap;
 use bevy_ecs::system::Resource;
 use glam::IVec2;
 use rand::{Rng, RngCore};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::BTreeMap;
 
 #[derive(Clone, Debug, Default, PartialEq, Eq, Resource)]
 pub struct Objects {
-    objects: AHashMap<ObjectId, Object>,
+    // Ordered, so that iterating (and serializing) objects is reproducible
+    objects: BTreeMap<ObjectId, Object>,
     pos_to_id: AHashMap<IVec2, ObjectId>,
-    id_to_pos: AHashMap<ObjectId, IVec2>,
+    id_to_pos: BTreeMap<ObjectId, IVec2>,
 }
 
 impl Objects {
@@ -78,7 +81,32 @@ impl Objects {
     }
 }
 
-#[derive(Clone, Copy, Debug)]
+impl Serialize for Objects {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+impl<'de> Deserialize<'de> for Objects {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let mut this = Self::default();
+        let objects = Vec::<ObjectEntry>::deserialize(deserializer)?;
+
+        for obj in objects {
+            this.add(obj.id, obj.obj, obj.pos);
+        }
+
+        Ok(this)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
 pub struct ObjectEntry {
     pub id: ObjectId,
     pub obj: Object,
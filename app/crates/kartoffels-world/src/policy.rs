@@ -1,7 +1,9 @@
-use crate::spec;
+use crate::{spec, GameMode, Object};
 use anyhow::{anyhow, Error, Result};
 use bevy_ecs::system::Resource;
+use glam::IVec2;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::str::FromStr;
 
 #[derive(
@@ -9,28 +11,65 @@ use std::str::FromStr;
 )]
 pub struct Policy {
     pub auto_respawn: bool,
+    pub energy: EnergyPolicy,
+
+    /// Whether bots can stab their teammates; bots without a team can always
+    /// be stabbed
+    pub friendly_fire: bool,
+
     pub max_alive_bots: usize,
     pub max_queued_bots: usize,
+    pub mode: ModePolicy,
 }
 
-impl FromStr for Policy {
-    type Err = Error;
-
-    fn from_str(spec: &str) -> Result<Self> {
-        let mut this = Self::default();
-
+impl Policy {
+    /// Overrides fields specified in given spec, leaving the rest untouched.
+    pub fn apply(&mut self, spec: &str) -> Result<()> {
         for entry in spec::entries(spec) {
             let entry = entry?;
 
             match entry.key {
                 "auto-respawn" => {
-                    this.auto_respawn = entry.value()?;
+                    self.auto_respawn = entry.value()?;
+                }
+                "energy-arm" => {
+                    self.energy.arm = entry.value()?;
+                }
+                "energy-charge" => {
+                    self.energy.charge = entry.value()?;
+                }
+                "energy-motor-move" => {
+                    self.energy.motor_move = entry.value()?;
+                }
+                "energy-motor-turn" => {
+                    self.energy.motor_turn = entry.value()?;
+                }
+                "energy-radar" => {
+                    self.energy.radar = entry.value()?;
+                }
+                "energy-radio" => {
+                    self.energy.radio = entry.value()?;
+                }
+                "friendly-fire" => {
+                    self.friendly_fire = entry.value()?;
                 }
                 "max-alive-bots" => {
-                    this.max_alive_bots = entry.value()?;
+                    self.max_alive_bots = entry.value()?;
                 }
                 "max-queued-bots" => {
-                    this.max_queued_bots = entry.value()?;
+                    self.max_queued_bots = entry.value()?;
+                }
+                "mode" => {
+                    self.mode.kind = entry.value.parse()?;
+                }
+                "mode-hill-radius" => {
+                    self.mode.hill_radius = entry.value()?;
+                }
+                "mode-round-duration" => {
+                    self.mode.round_duration = Some(entry.value()?);
+                }
+                "mode-win-score" => {
+                    self.mode.win_score = Some(entry.value()?);
                 }
                 key => {
                     return Err(anyhow!("unknown key: {key}"));
@@ -38,10 +77,89 @@ impl FromStr for Policy {
             }
         }
 
+        Ok(())
+    }
+}
+
+impl FromStr for Policy {
+    type Err = Error;
+
+    fn from_str(spec: &str) -> Result<Self> {
+        let mut this = Self::default();
+
+        this.apply(spec)?;
+
         Ok(this)
     }
 }
 
+/// How much energy bots spend on actions and how quickly they recharge.
+///
+/// All costs default to zero, i.e. batteries don't drain unless a world
+/// explicitly opts in.
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize,
+)]
+pub struct EnergyPolicy {
+    /// Cost of stabbing, picking or dropping
+    pub arm: u32,
+
+    /// Energy restored every [`BotBattery::CHARGE_EVERY`] ticks when bot is
+    /// standing next to a charger
+    ///
+    /// [`BotBattery::CHARGE_EVERY`]: crate::BotBattery::CHARGE_EVERY
+    pub charge: u32,
+
+    /// Cost of moving forward or backward
+    pub motor_move: u32,
+
+    /// Cost of turning left or right
+    pub motor_turn: u32,
+
+    /// Cost of performing a radar scan
+    pub radar: u32,
+
+    /// Cost of broadcasting a radio message
+    pub radio: u32,
+}
+
+/// How bots score and when rounds end.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModePolicy {
+    pub kind: GameMode,
+
+    /// Score at which a round ends, with the bot that's reached it declared
+    /// the winner; `None` means rounds never end
+    pub win_score: Option<u32>,
+
+    /// Center of the hill in [`GameMode::KingOfTheHill`]; `None` means the
+    /// floor tile closest to the map's center
+    pub hill: Option<IVec2>,
+
+    /// Radius of the hill in [`GameMode::KingOfTheHill`]
+    pub hill_radius: u32,
+
+    /// Bases of teams in [`GameMode::CaptureTheFlag`], keyed by team; each
+    /// base is where its team's flag gets raised and where the team brings
+    /// enemy flags to score
+    pub bases: BTreeMap<u8, IVec2>,
+
+    /// For how many seconds a round lasts, with the bot with the highest
+    /// score declared the winner; `None` means rounds aren't timed
+    pub round_duration: Option<u32>,
+}
+
+impl ModePolicy {
+    pub fn has_rounds(&self) -> bool {
+        self.win_score.is_some() || self.round_duration.is_some()
+    }
+
+    /// Returns base of the team given object belongs to.
+    pub fn base_of(&self, obj: Object) -> Option<IVec2> {
+        self.bases.get(&obj.team()?).copied()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -55,8 +173,57 @@ mod tests {
 
         let expected = Policy {
             auto_respawn: true,
+            energy: Default::default(),
+            friendly_fire: false,
             max_alive_bots: 100,
             max_queued_bots: 200,
+            mode: Default::default(),
+        };
+
+        assert_eq!(expected, actual);
+
+        // ---
+
+        let actual = Policy::from_str(
+            "energy-arm=1,energy-charge=2,energy-motor-move=3,\
+             energy-motor-turn=4,energy-radar=5,energy-radio=6,\
+             friendly-fire=true",
+        )
+        .unwrap();
+
+        let expected = Policy {
+            energy: EnergyPolicy {
+                arm: 1,
+                charge: 2,
+                motor_move: 3,
+                motor_turn: 4,
+                radar: 5,
+                radio: 6,
+            },
+            friendly_fire: true,
+            ..Default::default()
+        };
+
+        assert_eq!(expected, actual);
+
+        // ---
+
+        let actual = Policy::from_str(
+            "mode=king-of-the-hill,mode-hill-radius=3,mode-round-duration=600,\
+             mode-win-score=60",
+        )
+        .unwrap();
+
+        let expected = Policy {
+            mode: ModePolicy {
+                kind: GameMode::KingOfTheHill,
+                win_score: Some(60),
+                hill: None,
+                hill_radius: 3,
+                bases: Default::default(),
+                round_duration: Some(600),
+            },
+            ..Default::default()
         };
 
         assert_eq!(expected, actual);
@@ -0,0 +1,465 @@
+mod systems;
+
+pub use self::systems::*;
+use crate::{
+    snapshots, BotId, Bots, Clock, CreateBotRequest, Dir, Fuel, HandleRx,
+    Header, Map, Object, ObjectId, Request, SerializedWorld, Shutdown,
+    Snapshot, Snapshots, Spawn, Stats,
+};
+use anyhow::{anyhow, Context, Result};
+use bevy_ecs::system::Resource;
+use bevy_ecs::world::{Mut, World};
+use ciborium::Value;
+use glam::IVec2;
+use kartoffels_utils::Id;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use tokio::sync::{mpsc, oneshot, watch};
+use tracing::{info, warn};
+
+/// Recording's preamble, describing the world's state at the moment the
+/// recording has started.
+///
+/// In the file, it's preceded by [`Header`] and followed by a stream of
+/// [`ReplayEntry`].
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReplayHeader<'a> {
+    pub id: Id,
+    pub clock: Clock,
+    pub fuel: u32,
+    pub paused: bool,
+    pub spawn: Spawn,
+    pub world: SerializedWorld<'a>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub enum ReplayEntry {
+    Request {
+        frame: u64,
+        request: ReplayRequest,
+    },
+    /// Serialized [`Snapshot`] of the world at given frame, used to verify
+    /// that the replay doesn't diverge from the recording.
+    Checkpoint {
+        frame: u64,
+        snapshot: Value,
+    },
+}
+
+impl ReplayEntry {
+    fn frame(&self) -> u64 {
+        match self {
+            ReplayEntry::Request { frame, .. } => *frame,
+            ReplayEntry::Checkpoint { frame, .. } => *frame,
+        }
+    }
+}
+
+/// Recorded counterpart of [`Request`].
+#[derive(Debug, Serialize, Deserialize)]
+pub enum ReplayRequest {
+    Tick {
+        fuel: u32,
+    },
+    Pause,
+    Resume,
+    Shutdown,
+    Rename {
+        name: String,
+    },
+    CreateBot {
+        req: CreateBotRequest,
+    },
+    KillBot {
+        id: BotId,
+        reason: String,
+    },
+    DeleteBot {
+        id: BotId,
+    },
+    SendToBot {
+        id: BotId,
+        msg: String,
+    },
+    PauseBot {
+        id: BotId,
+    },
+    ResumeBot {
+        id: BotId,
+    },
+    StepBot {
+        id: BotId,
+    },
+    AddBreakpoint {
+        id: BotId,
+        addr: u32,
+    },
+    RemoveBreakpoint {
+        id: BotId,
+        addr: u32,
+    },
+    SetMap {
+        map: Map,
+    },
+    SetSpawn {
+        pos: Option<IVec2>,
+        dir: Option<Dir>,
+    },
+    CreateObject {
+        obj: Object,
+        pos: Option<IVec2>,
+    },
+    DeleteObject {
+        id: ObjectId,
+    },
+    Overclock {
+        clock: Clock,
+    },
+}
+
+impl ReplayRequest {
+    pub fn new(request: &Request) -> Option<Self> {
+        let this = match request {
+            Request::Tick { fuel, .. } => Self::Tick { fuel: *fuel },
+            Request::Pause { .. } => Self::Pause,
+            Request::Resume { .. } => Self::Resume,
+            Request::Shutdown { .. } => Self::Shutdown,
+
+            Request::Rename { name, .. } => Self::Rename { name: name.clone() },
+
+            Request::CreateBot { req, .. } => {
+                Self::CreateBot { req: req.clone() }
+            }
+
+            Request::KillBot { id, reason, .. } => Self::KillBot {
+                id: *id,
+                reason: reason.clone(),
+            },
+
+            Request::DeleteBot { id, .. } => Self::DeleteBot { id: *id },
+
+            Request::SendToBot { id, msg, .. } => Self::SendToBot {
+                id: *id,
+                msg: msg.clone(),
+            },
+
+            Request::PauseBot { id, .. } => Self::PauseBot { id: *id },
+            Request::ResumeBot { id, .. } => Self::ResumeBot { id: *id },
+            Request::StepBot { id, .. } => Self::StepBot { id: *id },
+
+            Request::AddBreakpoint { id, addr, .. } => Self::AddBreakpoint {
+                id: *id,
+                addr: *addr,
+            },
+
+            Request::RemoveBreakpoint { id, addr, .. } => {
+                Self::RemoveBreakpoint {
+                    id: *id,
+                    addr: *addr,
+                }
+            }
+
+            Request::SetMap { map, .. } => Self::SetMap { map: map.clone() },
+
+            Request::SetSpawn { pos, dir, .. } => Self::SetSpawn {
+                pos: *pos,
+                dir: *dir,
+            },
+
+            Request::CreateObject { obj, pos, .. } => Self::CreateObject {
+                obj: *obj,
+                pos: *pos,
+            },
+
+            Request::DeleteObject { id, .. } => Self::DeleteObject { id: *id },
+
+            Request::Overclock { clock, .. } => Self::Overclock {
+                clock: clock.clone(),
+            },
+
+            // Profiling doesn't affect the simulation, so there's no need to
+            // replay it
+            Request::StartProfilingBot { .. }
+            | Request::ProfileBot { .. }
+            | Request::StopProfilingBot { .. }
+            | Request::Record { .. } => {
+                return None;
+            }
+        };
+
+        Some(this)
+    }
+
+    fn into_request(self) -> Request {
+        // Responses are not interesting during replay, so we just pass
+        // senders with already-dropped receivers
+        fn tx<T>() -> oneshot::Sender<T> {
+            oneshot::channel().0
+        }
+
+        match self {
+            Self::Tick { fuel } => Request::Tick { fuel, tx: tx() },
+            Self::Pause => Request::Pause { tx: tx() },
+            Self::Resume => Request::Resume { tx: tx() },
+            Self::Shutdown => Request::Shutdown { tx: tx() },
+            Self::Rename { name } => Request::Rename { name, tx: tx() },
+            Self::CreateBot { req } => Request::CreateBot { req, tx: tx() },
+
+            Self::KillBot { id, reason } => Request::KillBot {
+                id,
+                reason,
+                tx: tx(),
+            },
+
+            Self::DeleteBot { id } => Request::DeleteBot { id, tx: tx() },
+
+            Self::SendToBot { id, msg } => {
+                Request::SendToBot { id, msg, tx: tx() }
+            }
+
+            Self::PauseBot { id } => Request::PauseBot { id, tx: tx() },
+            Self::ResumeBot { id } => Request::ResumeBot { id, tx: tx() },
+            Self::StepBot { id } => Request::StepBot { id, tx: tx() },
+
+            Self::AddBreakpoint { id, addr } => {
+                Request::AddBreakpoint { id, addr, tx: tx() }
+            }
+
+            Self::RemoveBreakpoint { id, addr } => {
+                Request::RemoveBreakpoint { id, addr, tx: tx() }
+            }
+
+            Self::SetMap { map } => Request::SetMap { map, tx: tx() },
+
+            Self::SetSpawn { pos, dir } => {
+                Request::SetSpawn { pos, dir, tx: tx() }
+            }
+
+            Self::CreateObject { obj, pos } => {
+                Request::CreateObject { obj, pos, tx: tx() }
+            }
+
+            Self::DeleteObject { id } => Request::DeleteObject { id, tx: tx() },
+
+            Self::Overclock { clock } => Request::Overclock {
+                clock: replayed_clock(clock),
+                tx: tx(),
+            },
+        }
+    }
+}
+
+#[derive(Debug, Resource)]
+pub struct Recorder {
+    frame: u64,
+    file: BufWriter<File>,
+}
+
+impl Recorder {
+    const CHECKPOINT_EVERY: u64 = 1024;
+
+    pub fn record(&mut self, request: ReplayRequest) {
+        self.write(ReplayEntry::Request {
+            frame: self.frame,
+            request,
+        });
+    }
+
+    fn checkpoint(&mut self, snapshot: &Snapshot) {
+        let snapshot = match Value::serialized(snapshot) {
+            Ok(snapshot) => snapshot,
+            Err(err) => {
+                warn!(?err, "couldn't serialize checkpoint");
+                return;
+            }
+        };
+
+        self.write(ReplayEntry::Checkpoint {
+            frame: self.frame,
+            snapshot,
+        });
+
+        // Flushing on checkpoints makes the recording usable even if the
+        // world crashes later
+        if let Err(err) = self.file.flush() {
+            warn!(?err, "couldn't flush recording");
+        }
+    }
+
+    fn write(&mut self, entry: ReplayEntry) {
+        if let Err(err) = ciborium::into_writer(&entry, &mut self.file) {
+            warn!(?err, "couldn't write recording");
+        }
+    }
+}
+
+#[derive(Debug, Resource)]
+pub struct StartRecording {
+    pub path: PathBuf,
+    pub tx: Option<oneshot::Sender<Result<()>>>,
+}
+
+pub fn load(path: &Path) -> Result<(ReplayHeader<'static>, Vec<ReplayEntry>)> {
+    let file = File::open(path)
+        .with_context(|| format!("couldn't open: {}", path.display()))?;
+
+    let mut file = BufReader::new(file);
+
+    let header = Header::read(&mut file)
+        .context("couldn't read header")?
+        .validated()
+        .context("couldn't validate header")?;
+
+    if header.version() != Header::default().version() {
+        return Err(anyhow!(
+            "unsupported version: got {}, expected {}",
+            header.version(),
+            Header::default().version(),
+        ));
+    }
+
+    let header =
+        ciborium::from_reader(&mut file).context("couldn't read preamble")?;
+
+    let mut entries = Vec::new();
+
+    while !file.fill_buf()?.is_empty() {
+        entries.push(ciborium::from_reader(&mut file).with_context(|| {
+            format!("couldn't read entry #{}", entries.len())
+        })?);
+    }
+
+    Ok((header, entries))
+}
+
+/// Runs the recorded requests against given world, frame by frame, verifying
+/// the world's state at each checkpoint.
+pub fn run(mut world: World, entries: Vec<ReplayEntry>) -> Result<()> {
+    let Some(last_frame) = entries.iter().map(|entry| entry.frame()).max()
+    else {
+        return Ok(());
+    };
+
+    let mut requests = BTreeMap::<_, Vec<_>>::new();
+    let mut checkpoints = BTreeMap::new();
+
+    for entry in entries {
+        match entry {
+            ReplayEntry::Request { frame, request } => {
+                requests.entry(frame).or_default().push(request);
+            }
+
+            ReplayEntry::Checkpoint { frame, snapshot } => {
+                checkpoints.insert(frame, snapshot);
+            }
+        }
+    }
+
+    let capacity = requests.values().map(Vec::len).max().unwrap_or(1);
+    let (tx, rx) = mpsc::channel(capacity);
+
+    world.insert_resource(HandleRx(rx));
+
+    world.insert_resource(Snapshots {
+        tx: watch::Sender::default(),
+    });
+
+    let mut schedule = crate::main_schedule();
+
+    info!(frames = last_frame + 1, "replaying");
+
+    for frame in 0..=last_frame {
+        for request in requests.remove(&frame).into_iter().flatten() {
+            tx.try_send(request.into_request()).map_err(|_| {
+                anyhow!("replay diverged at frame {frame}: request got stuck")
+            })?;
+        }
+
+        // With manual clock, the world blocks until it receives a request -
+        // if there's none, the replay must've diverged
+        let clock = world.resource::<Clock>();
+
+        if let Clock::Manual { .. } = clock
+            && world.resource::<Fuel>().remaining() <= clock.ticks()
+            && tx.capacity() == tx.max_capacity()
+        {
+            return Err(anyhow!(
+                "replay diverged at frame {frame}: world ran out of fuel"
+            ));
+        }
+
+        schedule.run(&mut world);
+
+        if let Some(expected) = checkpoints.remove(&frame) {
+            let actual = Value::serialized(&checkpoint(&mut world))
+                .context("couldn't serialize checkpoint")?;
+
+            if let Some(diff) = diff(&expected, &actual) {
+                return Err(anyhow!(
+                    "replay diverged at frame {frame}: {diff} differ"
+                ));
+            }
+        }
+
+        if world.remove_resource::<Shutdown>().is_some() {
+            break;
+        }
+    }
+
+    info!("replay completed");
+
+    Ok(())
+}
+
+/// Maps recorded clock into the one used for replay - since all non-manual
+/// clocks simulate the same number of ticks per frame, we can replay them as
+/// fast as possible.
+pub fn replayed_clock(clock: Clock) -> Clock {
+    match clock {
+        clock @ Clock::Manual { .. } => clock,
+        _ => Clock::Unlimited,
+    }
+}
+
+/// Prepares snapshot of given world for the purposes of a checkpoint.
+pub fn checkpoint(world: &mut World) -> Snapshot {
+    world.resource_scope(|world, mut bots: Mut<Bots>| {
+        // Clock legitimately differs between the recording and the replay,
+        // while stats are refreshed based on wall time - so we normalize both
+        let clock = replayed_clock(world.resource::<Clock>().clone());
+        let stats = Stats::new(&bots, world.resource());
+
+        snapshots::build(
+            &mut bots,
+            &clock,
+            world.resource(),
+            world.resource(),
+            world.resource(),
+            world.resource(),
+            &stats,
+        )
+    })
+}
+
+/// Returns the name of the first top-level field that differs between given
+/// snapshots.
+fn diff(expected: &Value, actual: &Value) -> Option<String> {
+    if expected == actual {
+        return None;
+    }
+
+    if let (Value::Map(expected), Value::Map(actual)) = (expected, actual) {
+        for ((key, lhs), (_, rhs)) in expected.iter().zip(actual) {
+            if lhs != rhs
+                && let Some(key) = key.as_text()
+            {
+                return Some(key.into());
+            }
+        }
+    }
+
+    Some("snapshots".into())
+}
@@ -0,0 +1,5 @@
+mod record;
+mod start;
+
+pub use self::record::*;
+pub use self::start::*;
@@ -0,0 +1,23 @@
+use crate::{replay, Recorder, Shutdown};
+use bevy_ecs::world::World;
+
+pub fn record(world: &mut World) {
+    if !world.contains_resource::<Recorder>() {
+        return;
+    }
+
+    let shutdown = world.contains_resource::<Shutdown>();
+    let frame = world.resource::<Recorder>().frame;
+
+    if shutdown || frame % Recorder::CHECKPOINT_EVERY == 0 {
+        let snapshot = replay::checkpoint(world);
+
+        world.resource_mut::<Recorder>().checkpoint(&snapshot);
+    }
+
+    if shutdown {
+        world.remove_resource::<Recorder>();
+    } else {
+        world.resource_mut::<Recorder>().frame += 1;
+    }
+}
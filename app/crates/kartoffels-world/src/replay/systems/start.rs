@@ -0,0 +1,102 @@
+use crate::{
+    Bots, Clock, Fuel, Header, Lives, Map, Objects, Paused, Policy, Recorder,
+    ReplayHeader, Round, SerializedWorld, Spawn, StartRecording, Theme,
+    WorldId, WorldName, WorldRng,
+};
+use anyhow::{anyhow, Context};
+use bevy_ecs::system::{Commands, Res, ResMut};
+use maybe_owned::MaybeOwned;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use tracing::info;
+
+#[allow(clippy::too_many_arguments)]
+pub fn start(
+    mut cmds: Commands,
+    bots: Res<Bots>,
+    clock: Res<Clock>,
+    fuel: Res<Fuel>,
+    id: Res<WorldId>,
+    lives: Res<Lives>,
+    map: Res<Map>,
+    name: Res<WorldName>,
+    objects: Res<Objects>,
+    paused: Res<Paused>,
+    policy: Res<Policy>,
+    recorder: Option<Res<Recorder>>,
+    // Bevy supports up to 16 parameters per system
+    (rng, round): (Res<WorldRng>, Res<Round>),
+    spawn: Res<Spawn>,
+    start: Option<ResMut<StartRecording>>,
+    theme: Option<Res<Theme>>,
+) {
+    let Some(mut start) = start else {
+        return;
+    };
+
+    let Some(tx) = start.tx.take() else {
+        return;
+    };
+
+    cmds.remove_resource::<StartRecording>();
+
+    let result = (|| {
+        if recorder.is_some() {
+            return Err(anyhow!("world is already being recorded"));
+        }
+
+        let header = ReplayHeader {
+            id: id.0,
+            clock: clock.clone(),
+            fuel: fuel.remaining(),
+            paused: paused.get(),
+            spawn: *spawn,
+            world: SerializedWorld {
+                bots: MaybeOwned::Borrowed(&bots),
+                lives: MaybeOwned::Borrowed(&lives),
+                map: MaybeOwned::Borrowed(&map),
+                name: MaybeOwned::Owned(name.0.load().to_string()),
+                objects: MaybeOwned::Borrowed(&objects),
+                policy: MaybeOwned::Borrowed(&policy),
+                rng: MaybeOwned::Borrowed(&rng.0),
+                round: MaybeOwned::Borrowed(&round),
+                theme: theme
+                    .as_ref()
+                    .map(|theme| MaybeOwned::Borrowed(&**theme)),
+            },
+        };
+
+        let mut buffer = Vec::new();
+
+        ciborium::into_writer(&header, &mut buffer)
+            .context("couldn't serialize preamble")?;
+
+        let mut file =
+            File::create(&start.path).map(BufWriter::new).with_context(
+                || format!("couldn't create: {}", start.path.display()),
+            )?;
+
+        Header::default()
+            .write(&mut file)
+            .context("couldn't write header")?;
+
+        file.write_all(&buffer)
+            .and_then(|_| file.flush())
+            .context("couldn't write preamble")?;
+
+        Ok(Recorder { frame: 0, file })
+    })();
+
+    match result {
+        Ok(recorder) => {
+            info!(path=?start.path, "recording started");
+
+            cmds.insert_resource(recorder);
+            _ = tx.send(Ok(()));
+        }
+
+        Err(err) => {
+            _ = tx.send(Err(err));
+        }
+    }
+}
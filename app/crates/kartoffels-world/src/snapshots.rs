@@ -1,33 +1,38 @@
+mod packet;
 mod stream;
 mod systems;
 
+pub use self::packet::*;
 pub use self::stream::*;
 pub use self::systems::*;
 use crate::{
     BotEvent, BotId, BotLife, BotLives, BotStats, Clock, Dir, Map, Object,
-    ObjectId, Ticks,
+    ObjectId, Round, TeamStats, Ticks, Tile,
 };
 use ahash::AHashMap;
 use bevy_ecs::system::Resource;
-use glam::IVec2;
+use glam::{IVec2, UVec2};
 use itertools::Itertools;
+use kartoffels_cpu::Cpu;
 use prettytable::{row, Table};
-use serde::Serialize;
+use serde::{Serialize, Serializer};
 use std::cmp::Reverse;
-use std::collections::VecDeque;
+use std::collections::{BTreeSet, VecDeque};
 use std::fmt;
 use std::sync::Arc;
 use tokio::sync::watch;
 
-#[derive(Debug, Default, Serialize)]
+#[derive(Clone, Debug, Default, Serialize)]
 pub struct Snapshot {
-    pub bots: BotsSnapshot,
+    pub bots: Arc<BotsSnapshot>,
     pub clock: Clock,
-    pub lives: LivesSnapshot,
-    pub map: Map,
-    pub objects: ObjectsSnapshot,
-    pub stats: StatsSnapshot,
-    pub tiles: Map,
+    pub lives: Arc<LivesSnapshot>,
+    pub map: MapSnapshot,
+    pub objects: Arc<ObjectsSnapshot>,
+    #[serde(skip)]
+    pub round: Arc<Round>,
+    pub stats: Arc<StatsSnapshot>,
+    pub tiles: MapSnapshot,
     pub version: u64,
 }
 
@@ -50,6 +55,74 @@ impl fmt::Display for Snapshot {
     }
 }
 
+/// Map with bots and objects drawn on top of it.
+///
+/// Instead of carrying a full copy, this consists of a keyframe shared by all
+/// viewers and tiles that have changed since then - so that each viewer has to
+/// copy only the changes, see [`SnapshotStream`].
+#[derive(Clone, Debug, Default)]
+pub struct MapSnapshot {
+    keyframe: Arc<Map>,
+    changes: Arc<AHashMap<IVec2, Tile>>,
+}
+
+impl MapSnapshot {
+    pub(crate) fn new(keyframe: Arc<Map>) -> Self {
+        Self {
+            keyframe,
+            changes: Default::default(),
+        }
+    }
+
+    pub fn get(&self, pos: IVec2) -> Tile {
+        self.changes
+            .get(&pos)
+            .copied()
+            .unwrap_or_else(|| self.keyframe.get(pos))
+    }
+
+    pub fn center(&self) -> IVec2 {
+        self.keyframe.center()
+    }
+
+    pub fn size(&self) -> UVec2 {
+        self.keyframe.size()
+    }
+
+    pub fn to_map(&self) -> Map {
+        let mut map = (*self.keyframe).clone();
+
+        for (pos, tile) in self.changes.iter() {
+            map.set(*pos, *tile);
+        }
+
+        map
+    }
+}
+
+impl PartialEq for MapSnapshot {
+    fn eq(&self, other: &Self) -> bool {
+        self.to_map() == other.to_map()
+    }
+}
+
+impl Eq for MapSnapshot {}
+
+impl Serialize for MapSnapshot {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.to_map().serialize(serializer)
+    }
+}
+
+impl fmt::Display for MapSnapshot {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_map())
+    }
+}
+
 #[derive(Debug, Default, PartialEq, Eq, Serialize)]
 pub struct BotsSnapshot {
     pub alive: AliveBotsSnapshot,
@@ -102,6 +175,17 @@ pub enum BotSnapshot<'a> {
     Queued(&'a QueuedBotSnapshot),
 }
 
+impl<'a> BotSnapshot<'a> {
+    /// Returns who uploaded this bot, see [`crate::CreateBotRequest::owner()`].
+    pub fn owner(&self) -> Option<&'a str> {
+        match *self {
+            BotSnapshot::Alive(bot) => bot.owner.as_deref(),
+            BotSnapshot::Dead(bot) => bot.owner.as_deref(),
+            BotSnapshot::Queued(bot) => bot.owner.as_deref(),
+        }
+    }
+}
+
 #[derive(Debug, Default, PartialEq, Eq, Serialize)]
 pub struct AliveBotsSnapshot {
     entries: Vec<AliveBotSnapshot>,
@@ -179,14 +263,43 @@ impl fmt::Display for AliveBotsSnapshot {
 #[derive(Debug, PartialEq, Eq, Serialize)]
 pub struct AliveBotSnapshot {
     pub age: Ticks,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub debugger: Option<Arc<BotDebuggerSnapshot>>,
     pub dir: Dir,
     pub events: Arc<VecDeque<Arc<BotEvent>>>,
     pub id: BotId,
+    #[serde(skip)]
+    pub owner: Option<Arc<str>>,
     pub pos: IVec2,
     pub score: u32,
     pub serial: Arc<VecDeque<u32>>,
 }
 
+/// State of a bot that's being debugged, see [`crate::Handle::pause_bot()`].
+#[derive(Debug, PartialEq, Eq, Serialize)]
+pub struct BotDebuggerSnapshot {
+    pub breakpoints: BTreeSet<u32>,
+    pub paused: bool,
+    pub pc: u32,
+
+    /// Bot's memory, present only when the bot is paused
+    #[serde(skip)]
+    pub ram: Box<[u8]>,
+    pub regs: [i32; 32],
+}
+
+impl BotDebuggerSnapshot {
+    pub fn load(&self, addr: u32) -> Option<u8> {
+        let rel_addr = addr.checked_sub(Cpu::RAM_BASE)?;
+
+        self.ram.get(rel_addr as usize).copied()
+    }
+
+    pub fn disassemble(&self, addr: u32) -> Option<(u32, String)> {
+        Cpu::disassemble(&self.ram, addr)
+    }
+}
+
 #[derive(Debug, Default, PartialEq, Eq, Serialize)]
 pub struct DeadBotsSnapshot {
     #[serde(with = "kartoffels_utils::serde::sorted_map")]
@@ -218,6 +331,8 @@ impl DeadBotsSnapshot {
 #[derive(Debug, PartialEq, Eq, Serialize)]
 pub struct DeadBotSnapshot {
     pub events: Arc<VecDeque<Arc<BotEvent>>>,
+    #[serde(skip)]
+    pub owner: Option<Arc<str>>,
     pub serial: Arc<VecDeque<u32>>,
 }
 
@@ -240,6 +355,8 @@ impl QueuedBotsSnapshot {
 #[derive(Debug, PartialEq, Eq, Serialize)]
 pub struct QueuedBotSnapshot {
     pub events: Arc<VecDeque<Arc<BotEvent>>>,
+    #[serde(skip)]
+    pub owner: Option<Arc<str>>,
     pub place: u8,
     pub reincarnated: bool,
     pub serial: Arc<VecDeque<u32>>,
@@ -299,17 +416,26 @@ pub type BotLifeSnapshot = BotLife;
 pub struct StatsSnapshot {
     #[serde(with = "kartoffels_utils::serde::sorted_map")]
     entries: Arc<AHashMap<BotId, BotStats>>,
+
+    #[serde(with = "kartoffels_utils::serde::sorted_map")]
+    teams: Arc<AHashMap<u8, TeamStatsSnapshot>>,
 }
 
 impl StatsSnapshot {
     pub fn get(&self, id: BotId) -> Option<&BotStatsSnapshot> {
         self.entries.get(&id)
     }
+
+    pub fn team(&self, team: u8) -> Option<&TeamStatsSnapshot> {
+        self.teams.get(&team)
+    }
 }
 
 pub type BotStatsSnapshot = BotStats;
 
+pub type TeamStatsSnapshot = TeamStats;
+
 #[derive(Debug, Resource)]
 pub struct Snapshots {
-    pub tx: watch::Sender<Arc<Snapshot>>,
+    pub tx: watch::Sender<Arc<SnapshotPacket>>,
 }
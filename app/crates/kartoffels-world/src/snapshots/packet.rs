@@ -0,0 +1,23 @@
+use super::Snapshot;
+use crate::Tile;
+use glam::IVec2;
+use std::sync::Arc;
+
+/// Snapshot as sent by the world.
+///
+/// Instead of carrying a fresh copy of tiles, `snapshot.map` and
+/// `snapshot.tiles` contain just the latest keyframe, while `diffs` contain
+/// changes introduced by each version since then - see
+/// [`super::SnapshotStream`] for the other side.
+#[derive(Debug, Default)]
+pub struct SnapshotPacket {
+    pub snapshot: Snapshot,
+    pub diffs: Vec<Arc<SnapshotDiff>>,
+}
+
+/// Tiles changed between given version and the version before it.
+#[derive(Debug)]
+pub struct SnapshotDiff {
+    pub version: u64,
+    pub changes: Vec<(IVec2, Tile)>,
+}
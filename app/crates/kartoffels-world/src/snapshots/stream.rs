@@ -1,6 +1,8 @@
-use super::Snapshot;
-use crate::Handle;
+use super::{MapSnapshot, Snapshot, SnapshotPacket};
+use crate::{Handle, Tile};
 use anyhow::{Context, Result};
+use glam::IVec2;
+use std::mem;
 use std::sync::Arc;
 use tokio::sync::watch;
 use tokio_stream::wrappers::WatchStream;
@@ -8,17 +10,82 @@ use tokio_stream::StreamExt;
 
 #[derive(Debug)]
 pub struct SnapshotStream {
-    stream: WatchStream<Arc<Snapshot>>,
+    stream: WatchStream<Arc<SnapshotPacket>>,
+
+    /// Keyframe with all diffs up to `version` applied
+    tiles: MapSnapshot,
+
+    /// Version of the last diff applied to `tiles`
+    version: u64,
+
+    /// See [`Self::received_bytes()`]
+    received_bytes: u64,
 }
 
 impl SnapshotStream {
-    pub(crate) fn new(tx: &watch::Sender<Arc<Snapshot>>) -> Self {
+    pub(crate) fn new(tx: &watch::Sender<Arc<SnapshotPacket>>) -> Self {
         Self {
             stream: WatchStream::new(tx.subscribe()),
+            tiles: Default::default(),
+            version: 0,
+            received_bytes: 0,
         }
     }
 
     pub async fn next(&mut self) -> Result<Arc<Snapshot>> {
-        self.stream.next().await.context(Handle::ERR)
+        let packet = self.stream.next().await.context(Handle::ERR)?;
+
+        Ok(Arc::new(self.apply(&packet)))
+    }
+
+    /// Returns how much tile data (keyframes and diffs) this stream has
+    /// received so far, in bytes.
+    pub fn received_bytes(&self) -> u64 {
+        self.received_bytes
+    }
+
+    fn apply(&mut self, packet: &SnapshotPacket) -> Snapshot {
+        let keyframe = &packet.snapshot.tiles.keyframe;
+
+        if !Arc::ptr_eq(&self.tiles.keyframe, keyframe) {
+            self.tiles = MapSnapshot::new(keyframe.clone());
+            self.version = 0;
+
+            self.received_bytes += (keyframe.size().element_product() as u64)
+                * (mem::size_of::<Tile>() as u64);
+        }
+
+        // Since the underlying channel is a `watch`, we might've missed some
+        // packets - that's alright, because each packet carries all of the
+        // diffs since the keyframe, so we can just catch up
+        for diff in &packet.diffs {
+            if diff.version <= self.version {
+                continue;
+            }
+
+            // Changes are usually shared with the previously returned snapshot,
+            // so this copies them - but that's proportional to the number of
+            // bots and objects, not to the map size
+            let changes = Arc::make_mut(&mut self.tiles.changes);
+
+            for (pos, tile) in &diff.changes {
+                if self.tiles.keyframe.get(*pos) == *tile {
+                    changes.remove(pos);
+                } else {
+                    changes.insert(*pos, *tile);
+                }
+            }
+
+            self.version = diff.version;
+
+            self.received_bytes += (diff.changes.len() as u64)
+                * (mem::size_of::<(IVec2, Tile)>() as u64);
+        }
+
+        Snapshot {
+            map: self.tiles.clone(),
+            tiles: self.tiles.clone(),
+            ..packet.snapshot.clone()
+        }
     }
 }
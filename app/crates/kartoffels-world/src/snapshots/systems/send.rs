@@ -1,19 +1,43 @@
 use crate::{
-    AliveBotSnapshot, AliveBots, AliveBotsSnapshot, Bots, BotsSnapshot, Clock,
-    DeadBotSnapshot, DeadBots, DeadBotsSnapshot, Events, Lives, LivesSnapshot,
-    Map, ObjectSnapshot, Objects, ObjectsSnapshot, QueuedBotSnapshot,
-    QueuedBots, QueuedBotsSnapshot, Snapshot, Snapshots, Stats, StatsSnapshot,
+    AliveBotSnapshot, AliveBots, AliveBotsSnapshot, BotDebuggerSnapshot, Bots,
+    BotsSnapshot, Clock, DeadBotSnapshot, DeadBots, DeadBotsSnapshot, Events,
+    Lives, LivesSnapshot, Map, MapSnapshot, ObjectSnapshot, Objects,
+    ObjectsSnapshot, QueuedBotSnapshot, QueuedBots, QueuedBotsSnapshot, Round,
+    Snapshot, SnapshotDiff, SnapshotPacket, Snapshots, Stats, StatsSnapshot,
     Tile, TileKind,
 };
 use ahash::AHashMap;
+use bevy_ecs::change_detection::DetectChanges;
 use bevy_ecs::system::{Local, Res, ResMut};
+use glam::IVec2;
 use std::cmp::Reverse;
+use std::mem;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
 pub struct State {
     next_run_at: Instant,
     version: u64,
+
+    /// Map with bots and objects drawn on top of it
+    tiles: Map,
+
+    /// Positions of bots and objects drawn on `tiles`, used to restore the
+    /// underlying map on the next run
+    overlay: Vec<IVec2>,
+
+    /// Whether the underlying map has changed since the last run - if so, we
+    /// have to rebuild the tiles from scratch
+    map_changed: bool,
+
+    keyframe: Arc<Map>,
+    diffs: Vec<Arc<SnapshotDiff>>,
+}
+
+impl State {
+    /// After how many diffs we create a new keyframe - keeps the packets (and
+    /// time needed to catch up for new subscribers) small.
+    const KEYFRAME_INTERVAL: usize = 64;
 }
 
 impl Default for State {
@@ -21,6 +45,11 @@ impl Default for State {
         Self {
             next_run_at: Instant::now(),
             version: 0,
+            tiles: Default::default(),
+            overlay: Default::default(),
+            map_changed: true,
+            keyframe: Default::default(),
+            diffs: Default::default(),
         }
     }
 }
@@ -34,16 +63,21 @@ pub fn send(
     lives: Res<Lives>,
     map: Res<Map>,
     objects: Res<Objects>,
+    round: Res<Round>,
     snapshots: Res<Snapshots>,
     stats: Res<Stats>,
 ) {
+    // Note that we have to check this before bailing out, since the change
+    // detection is relative to the last time this system was *called*
+    state.map_changed |= map.is_changed();
+
     if Instant::now() < state.next_run_at {
         return;
     }
 
     state.version += 1;
 
-    let snapshot = {
+    let packet = {
         let bots = BotsSnapshot {
             alive: prepare_alive_bots(&mut bots.alive, &lives),
             dead: prepare_dead_bots(&mut bots.dead),
@@ -52,29 +86,36 @@ pub fn send(
 
         let stats = StatsSnapshot {
             entries: stats.entries.clone(),
+            teams: stats.teams.clone(),
         };
 
         let lives = LivesSnapshot {
             entries: lives.entries.clone(),
         };
 
-        let map = prepare_map(&bots, &map, &objects);
+        prepare_tiles(&mut state, &bots, &map, &objects);
+
         let objects = prepare_objects(&objects);
-        let tiles = map.clone();
 
-        Arc::new(Snapshot {
-            bots,
+        let snapshot = Snapshot {
+            bots: Arc::new(bots),
             clock: clock.clone(),
-            lives,
-            map,
-            objects,
-            stats,
-            tiles,
+            lives: Arc::new(lives),
+            map: MapSnapshot::new(state.keyframe.clone()),
+            objects: Arc::new(objects),
+            round: Arc::new(round.clone()),
+            stats: Arc::new(stats),
+            tiles: MapSnapshot::new(state.keyframe.clone()),
             version: state.version,
+        };
+
+        Arc::new(SnapshotPacket {
+            snapshot,
+            diffs: state.diffs.clone(),
         })
     };
 
-    snapshots.tx.send_replace(snapshot);
+    snapshots.tx.send_replace(packet);
 
     if let Some(events) = &mut events {
         events.send(state.version);
@@ -86,6 +127,48 @@ pub fn send(
     };
 }
 
+/// Prepares a standalone snapshot of the world, bypassing keyframes and diffs
+/// - used to verify recordings, see [`crate::replay::run()`].
+pub fn build(
+    bots: &mut Bots,
+    clock: &Clock,
+    lives: &Lives,
+    map: &Map,
+    objects: &Objects,
+    round: &Round,
+    stats: &Stats,
+) -> Snapshot {
+    let bots = BotsSnapshot {
+        alive: prepare_alive_bots(&mut bots.alive, lives),
+        dead: prepare_dead_bots(&mut bots.dead),
+        queued: prepare_queued_bots(&mut bots.queued),
+    };
+
+    let tiles = {
+        let mut tiles = map.clone();
+
+        draw_overlay(&mut tiles, &mut Vec::new(), &bots, objects);
+        MapSnapshot::new(Arc::new(tiles))
+    };
+
+    Snapshot {
+        bots: Arc::new(bots),
+        clock: clock.clone(),
+        lives: Arc::new(LivesSnapshot {
+            entries: lives.entries.clone(),
+        }),
+        map: tiles.clone(),
+        objects: Arc::new(prepare_objects(objects)),
+        round: Arc::new(round.clone()),
+        stats: Arc::new(StatsSnapshot {
+            entries: stats.entries.clone(),
+            teams: stats.teams.clone(),
+        }),
+        tiles,
+        version: 0,
+    }
+}
+
 fn prepare_alive_bots(
     bots: &mut AliveBots,
     lives: &Lives,
@@ -94,9 +177,25 @@ fn prepare_alive_bots(
         .iter_mut()
         .map(|bot| AliveBotSnapshot {
             age: bot.age(),
+            debugger: bot.debugger.as_ref().map(|debugger| {
+                Arc::new(BotDebuggerSnapshot {
+                    breakpoints: debugger.breakpoints().clone(),
+                    paused: debugger.is_paused(),
+                    pc: bot.cpu.pc(),
+                    // Memory is only inspected while the bot is paused, so
+                    // there's no point in copying it all the time
+                    ram: if debugger.is_paused() {
+                        bot.cpu.ram().into()
+                    } else {
+                        Default::default()
+                    },
+                    regs: *bot.cpu.regs(),
+                })
+            }),
             dir: bot.dir,
             events: bot.events.snapshot(),
             id: bot.id,
+            owner: bot.owner.clone(),
             pos: bot.pos,
             score: lives.curr_score(bot.id),
             serial: bot.serial.snapshot(),
@@ -134,6 +233,7 @@ fn prepare_dead_bots(bots: &mut DeadBots) -> DeadBotsSnapshot {
         .map(|entry| {
             let bot = DeadBotSnapshot {
                 events: entry.events.clone(),
+                owner: entry.owner.clone(),
                 serial: entry.serial.clone(),
             };
 
@@ -150,6 +250,7 @@ fn prepare_queued_bots(bots: &mut QueuedBots) -> QueuedBotsSnapshot {
         .map(|entry| {
             let bot = QueuedBotSnapshot {
                 events: entry.bot.events.snapshot(),
+                owner: entry.bot.owner.clone(),
                 place: entry.place + 1,
                 reincarnated: entry.bot.requeued,
                 serial: entry.bot.serial.snapshot(),
@@ -162,8 +263,84 @@ fn prepare_queued_bots(bots: &mut QueuedBots) -> QueuedBotsSnapshot {
     QueuedBotsSnapshot { entries }
 }
 
-fn prepare_map(bots: &BotsSnapshot, map: &Map, objects: &Objects) -> Map {
-    let mut map = map.clone();
+/// Updates tiles and either creates a new keyframe or records a diff against
+/// the previous version.
+///
+/// Instead of building the tiles from scratch, we only restore positions that
+/// were covered by bots and objects during the previous run - this keeps the
+/// cost proportional to the number of bots and objects, not the map size.
+fn prepare_tiles(
+    state: &mut State,
+    bots: &BotsSnapshot,
+    map: &Map,
+    objects: &Objects,
+) {
+    if mem::take(&mut state.map_changed) {
+        state.tiles = map.clone();
+        state.overlay.clear();
+
+        draw_overlay(&mut state.tiles, &mut state.overlay, bots, objects);
+
+        state.keyframe = Arc::new(state.tiles.clone());
+        state.diffs.clear();
+
+        return;
+    }
+
+    let prev: Vec<_> = state
+        .overlay
+        .drain(..)
+        .map(|pos| (pos, state.tiles.get(pos)))
+        .collect();
+
+    for (pos, _) in &prev {
+        state.tiles.set(*pos, map.get(*pos));
+    }
+
+    draw_overlay(&mut state.tiles, &mut state.overlay, bots, objects);
+
+    // Note that the same position might get reported more than once (e.g. when
+    // a bot stays in place) - that's fine, since applying a change is
+    // idempotent and it's cheaper than deduplicating here
+    let mut changes = Vec::new();
+
+    for (pos, old) in &prev {
+        let new = state.tiles.get(*pos);
+        if *old != new {
+            changes.push((*pos, new));
+        }
+    }
+
+    for pos in &state.overlay {
+        let new = state.tiles.get(*pos);
+        if map.get(*pos) != new {
+            changes.push((*pos, new));
+        }
+    }
+
+    if state.diffs.len() >= State::KEYFRAME_INTERVAL {
+        state.keyframe = Arc::new(state.tiles.clone());
+        state.diffs.clear();
+    } else if !changes.is_empty() {
+        state.diffs.push(Arc::new(SnapshotDiff {
+            version: state.version,
+            changes,
+        }));
+    }
+}
+
+fn draw_overlay(
+    tiles: &mut Map,
+    overlay: &mut Vec<IVec2>,
+    bots: &BotsSnapshot,
+    objects: &Objects,
+) {
+    let mut draw = |tiles: &mut Map, pos: IVec2, tile: Tile| {
+        if tiles.contains(pos) {
+            tiles.set(pos, tile);
+            overlay.push(pos);
+        }
+    };
 
     for (idx, bot) in bots.alive.iter().enumerate() {
         let tile = Tile {
@@ -178,20 +355,18 @@ fn prepare_map(bots: &BotsSnapshot, map: &Map, objects: &Objects) -> Map {
             meta: [idx as u8, u8::from(bot.dir), 0],
         };
 
-        map.set(bot.pos, tile);
+        draw(tiles, bot.pos, tile);
 
-        if !map.get(chevron_pos).is_bot() {
-            map.set(chevron_pos, chevron_tile);
+        if !tiles.get(chevron_pos).is_bot() {
+            draw(tiles, chevron_pos, chevron_tile);
         }
     }
 
     for obj in objects.iter() {
         if let Some(pos) = obj.pos {
-            map.set(pos, obj.obj.kind);
+            draw(tiles, pos, Tile::new(obj.obj.kind));
         }
     }
-
-    map
 }
 
 fn prepare_objects(objects: &Objects) -> ObjectsSnapshot {
@@ -206,3 +381,161 @@ fn prepare_objects(objects: &Objects) -> ObjectsSnapshot {
 
     ObjectsSnapshot { objects }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        AliveBot, BotId, Object, ObjectId, ObjectKind, SnapshotStream,
+    };
+    use bevy_ecs::change_detection::Mut;
+    use bevy_ecs::schedule::Schedule;
+    use bevy_ecs::world::World;
+    use futures_util::FutureExt;
+    use glam::{ivec2, uvec2};
+    use rand::{Rng, RngCore, SeedableRng};
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn diffs() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let map = {
+            let mut map = Map::new(uvec2(32, 32));
+
+            map.fill(TileKind::FLOOR);
+            map
+        };
+
+        let (mut world, mut schedule) = setup(&mut rng, map, 16);
+        let mut stream = SnapshotStream::new(&world.resource::<Snapshots>().tx);
+
+        for iter in 0..256 {
+            mutate(&mut rng, &mut world);
+
+            if iter == 100 {
+                world.resource_mut::<Map>().set(ivec2(0, 0), TileKind::WALL);
+            }
+
+            if iter % 50 == 0 {
+                let pos = ivec2(rng.gen_range(0..32), rng.gen_range(0..32));
+
+                world.resource_mut::<Objects>().add(
+                    ObjectId(rng.gen()),
+                    Object::new(ObjectKind::GEM),
+                    Some(pos),
+                );
+            }
+
+            schedule.run(&mut world);
+
+            // Skip some snapshots to make sure the stream is able to catch up
+            if iter % 3 == 0 {
+                continue;
+            }
+
+            let actual = stream.next().now_or_never().unwrap().unwrap();
+
+            let expected = {
+                let mut tiles = world.resource::<Map>().clone();
+
+                draw_overlay(
+                    &mut tiles,
+                    &mut Vec::new(),
+                    &actual.bots,
+                    world.resource::<Objects>(),
+                );
+
+                tiles
+            };
+
+            assert_eq!(
+                expected,
+                actual.tiles.to_map(),
+                "mismatch at iter={iter}"
+            );
+            assert_eq!(
+                expected,
+                actual.map.to_map(),
+                "mismatch at iter={iter}"
+            );
+        }
+    }
+
+    fn setup(
+        rng: &mut impl RngCore,
+        map: Map,
+        bots: usize,
+    ) -> (World, Schedule) {
+        let mut world = World::new();
+        let mut alive = AliveBots::default();
+
+        while alive.count() < bots {
+            let pos = map.sample_pos(rng);
+
+            if map.get(pos).is_floor() && alive.lookup_at(pos).is_none() {
+                alive.add(AliveBot {
+                    dir: rng.gen(),
+                    id: BotId::new(rng.next_u64()),
+                    pos,
+                    ..Default::default()
+                });
+            }
+        }
+
+        world.insert_resource(Bots {
+            alive,
+            ..Default::default()
+        });
+
+        world.insert_resource(Clock::manual());
+        world.insert_resource(Lives::default());
+        world.insert_resource(map);
+        world.insert_resource(Objects::default());
+        world.insert_resource(Round::default());
+        world.insert_resource(Stats::default());
+
+        world.insert_resource(Snapshots {
+            tx: Default::default(),
+        });
+
+        (world, crate::schedule(send))
+    }
+
+    /// Turns and moves some of the bots, mimicking what usually happens during
+    /// a single frame.
+    fn mutate(rng: &mut impl RngCore, world: &mut World) {
+        world.resource_scope(|world, mut bots: Mut<Bots>| {
+            let map = world.resource::<Map>();
+
+            for idx in 0..bots.alive.len() {
+                let Some(mut bot) = bots.alive.take(idx) else {
+                    continue;
+                };
+
+                let id = bot.id;
+                let pos = bot.pos;
+
+                match rng.gen_range(0..4) {
+                    0 => {
+                        bot.dir = bot.dir.turned_left();
+                    }
+
+                    1 => {
+                        let at = bot.pos + bot.dir;
+
+                        if map.get(at).is_floor()
+                            && bots.alive.lookup_at(at).is_none()
+                        {
+                            bot.pos = at;
+                        }
+                    }
+
+                    _ => (),
+                }
+
+                bots.alive.insert(idx, id, pos, Some(bot));
+            }
+        });
+    }
+}
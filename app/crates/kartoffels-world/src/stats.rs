@@ -8,6 +8,30 @@ use std::time::Instant;
 #[derive(Clone, Debug, Default, Resource)]
 pub struct Stats {
     pub entries: Arc<AHashMap<BotId, BotStats>>,
+    pub teams: Arc<AHashMap<u8, TeamStats>>,
+}
+
+impl Stats {
+    pub fn new(bots: &Bots, lives: &Lives) -> Self {
+        let entries: AHashMap<_, _> = lives
+            .entries
+            .iter()
+            .map(|(id, lives)| (*id, BotStats::new(bots, lives, *id)))
+            .collect();
+
+        let mut teams = AHashMap::<_, TeamStats>::new();
+
+        for (id, lives) in &lives.entries {
+            if let Some(team) = lives.team {
+                teams.entry(team).or_default().add(&entries[id]);
+            }
+        }
+
+        Self {
+            entries: Arc::new(entries),
+            teams: Arc::new(teams),
+        }
+    }
 }
 
 pub fn update(
@@ -20,14 +44,7 @@ pub fn update(
         return;
     }
 
-    let entries = Arc::make_mut(&mut stats.entries);
-
-    *entries = lives
-        .entries
-        .iter()
-        .map(|(id, lives)| (*id, BotStats::new(&bots, lives, *id)))
-        .collect();
-
+    *stats = Stats::new(&bots, &lives);
     *prev_run_at = Some(Instant::now());
 }
 
@@ -66,6 +83,19 @@ impl BotStats {
     }
 }
 
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct TeamStats {
+    pub bots: u32,
+    pub score: u32,
+}
+
+impl TeamStats {
+    fn add(&mut self, bot: &BotStats) {
+        self.bots += 1;
+        self.score += bot.scores.sum;
+    }
+}
+
 #[derive(Clone, Debug, Default, PartialEq, Serialize)]
 pub struct BotStatsPart {
     pub sum: u32,
@@ -2,9 +2,9 @@ mod header;
 mod migrations;
 mod systems;
 
-use self::header::*;
+pub use self::header::*;
 pub use self::systems::*;
-use crate::{Bots, Lives, Map, Policy, Theme};
+use crate::{Bots, Lives, Map, Objects, Policy, Round, Theme};
 use maybe_owned::MaybeOwned;
 use rand_chacha::ChaCha8Rng;
 use serde::{Deserialize, Serialize};
@@ -15,7 +15,9 @@ pub struct SerializedWorld<'a> {
     pub lives: MaybeOwned<'a, Lives>,
     pub map: MaybeOwned<'a, Map>,
     pub name: MaybeOwned<'a, String>,
+    pub objects: MaybeOwned<'a, Objects>,
     pub policy: MaybeOwned<'a, Policy>,
     pub rng: MaybeOwned<'a, ChaCha8Rng>,
+    pub round: MaybeOwned<'a, Round>,
     pub theme: Option<MaybeOwned<'a, Theme>>,
 }
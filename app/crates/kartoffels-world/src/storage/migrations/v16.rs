@@ -0,0 +1,57 @@
+use ciborium::Value;
+use kartoffels_utils::{CborMapExt, CborValueExt};
+
+pub fn run(world: &mut Value) {
+    let energy = Vec::default()
+        .with_entry("arm", Value::Integer(0.into()))
+        .with_entry("charge", Value::Integer(0.into()))
+        .with_entry("motor_move", Value::Integer(0.into()))
+        .with_entry("motor_turn", Value::Integer(0.into()))
+        .with_entry("radar", Value::Integer(0.into()));
+
+    world
+        .query_mut("/policy")
+        .next()
+        .unwrap()
+        .as_map_mut()
+        .unwrap()
+        .add_entry("energy", Value::Map(energy));
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::storage::migrations;
+    use indoc::indoc;
+
+    #[test]
+    fn test() {
+        let given = indoc! {r#"
+          {
+            "policy": {
+              "auto_respawn": true,
+              "max_alive_bots": 16,
+              "max_queued_bots": 32
+            }
+          }
+        "#};
+
+        let expected = indoc! {r#"
+          {
+            "policy": {
+              "auto_respawn": true,
+              "energy": {
+                "arm": 0,
+                "charge": 0,
+                "motor_move": 0,
+                "motor_turn": 0,
+                "radar": 0
+              },
+              "max_alive_bots": 16,
+              "max_queued_bots": 32
+            }
+          }
+        "#};
+
+        migrations::tests::run(16, given, expected);
+    }
+}
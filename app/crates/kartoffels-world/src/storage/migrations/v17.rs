@@ -0,0 +1,41 @@
+use ciborium::Value;
+use kartoffels_utils::CborMapExt;
+
+pub fn run(world: &mut Value) {
+    world
+        .as_map_mut()
+        .unwrap()
+        .add_entry("objects", Value::Array(Default::default()));
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::storage::migrations;
+    use indoc::indoc;
+
+    #[test]
+    fn test() {
+        let given = indoc! {r#"
+          {
+            "map": {
+              "size": [0, 0],
+              "tiles": []
+            },
+            "name": "world"
+          }
+        "#};
+
+        let expected = indoc! {r#"
+          {
+            "map": {
+              "size": [0, 0],
+              "tiles": []
+            },
+            "name": "world",
+            "objects": []
+          }
+        "#};
+
+        migrations::tests::run(17, given, expected);
+    }
+}
@@ -0,0 +1,58 @@
+use ciborium::Value;
+use kartoffels_utils::{CborMapExt, CborValueExt};
+
+pub fn run(world: &mut Value) {
+    for bot in world.query_mut("/bots/alive/*") {
+        let outbox = vec![Value::Integer(0.into()); 8];
+
+        bot.as_map_mut().unwrap().add_entry(
+            "radio",
+            Value::Map(
+                Vec::default()
+                    .with_entry("cooldown", Value::Integer(0.into()))
+                    .with_entry("inbox", Value::Array(vec![]))
+                    .with_entry("outbox", Value::Array(outbox)),
+            ),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::storage::migrations;
+    use indoc::indoc;
+
+    #[test]
+    fn test() {
+        let given = indoc! {r#"
+          {
+            "bots": {
+              "alive": [
+                {
+                  "id": "1234-1234-1234-1234"
+                }
+              ]
+            }
+          }
+        "#};
+
+        let expected = indoc! {r#"
+          {
+            "bots": {
+              "alive": [
+                {
+                  "id": "1234-1234-1234-1234",
+                  "radio": {
+                    "cooldown": 0,
+                    "inbox": [],
+                    "outbox": [0, 0, 0, 0, 0, 0, 0, 0]
+                  }
+                }
+              ]
+            }
+          }
+        "#};
+
+        migrations::tests::run(18, given, expected);
+    }
+}
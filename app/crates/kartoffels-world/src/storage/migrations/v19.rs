@@ -0,0 +1,95 @@
+use ciborium::Value;
+use kartoffels_utils::{CborMapExt, CborValueExt};
+
+pub fn run(world: &mut Value) {
+    world
+        .query_mut("/policy")
+        .next()
+        .unwrap()
+        .as_map_mut()
+        .unwrap()
+        .add_entry("friendly_fire", Value::Bool(false));
+
+    for bot in world.query_mut("/bots/{alive,queued}/*") {
+        bot.as_map_mut().unwrap().add_entry("team", Value::Null);
+    }
+
+    for lives in world.query_mut("/lives/*") {
+        lives.as_map_mut().unwrap().add_entry("team", Value::Null);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::storage::migrations;
+    use indoc::indoc;
+
+    #[test]
+    fn test() {
+        let given = indoc! {r#"
+          {
+            "bots": {
+              "alive": [
+                {
+                  "id": "1234-1234-1234-1234"
+                }
+              ],
+              "dead": [
+                {
+                  "id": "2345-2345-2345-2345"
+                }
+              ],
+              "queued": [
+                {
+                  "id": "3456-3456-3456-3456"
+                }
+              ]
+            },
+            "lives": {
+              "1234-1234-1234-1234": {
+                "len": 0
+              }
+            },
+            "policy": {
+              "auto_respawn": true
+            }
+          }
+        "#};
+
+        let expected = indoc! {r#"
+          {
+            "bots": {
+              "alive": [
+                {
+                  "id": "1234-1234-1234-1234",
+                  "team": null
+                }
+              ],
+              "dead": [
+                {
+                  "id": "2345-2345-2345-2345"
+                }
+              ],
+              "queued": [
+                {
+                  "id": "3456-3456-3456-3456",
+                  "team": null
+                }
+              ]
+            },
+            "lives": {
+              "1234-1234-1234-1234": {
+                "len": 0,
+                "team": null
+              }
+            },
+            "policy": {
+              "auto_respawn": true,
+              "friendly_fire": false
+            }
+          }
+        "#};
+
+        migrations::tests::run(19, given, expected);
+    }
+}
@@ -0,0 +1,67 @@
+use ciborium::Value;
+use kartoffels_utils::{CborMapExt, CborValueExt};
+
+pub fn run(world: &mut Value) {
+    for fw in world.query_mut("/bots/{alive,queued}/*/fw") {
+        fw.as_map_mut().unwrap().add_entry("symbols", Value::Null);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::storage::migrations;
+    use indoc::indoc;
+
+    #[test]
+    fn test() {
+        let given = indoc! {r#"
+          {
+            "bots": {
+              "alive": [
+                {
+                  "fw": {
+                    "segments": [],
+                    "entry_pc": 1024
+                  }
+                }
+              ],
+              "queued": [
+                {
+                  "fw": {
+                    "segments": [],
+                    "entry_pc": 2048
+                  }
+                }
+              ]
+            }
+          }
+        "#};
+
+        let expected = indoc! {r#"
+          {
+            "bots": {
+              "alive": [
+                {
+                  "fw": {
+                    "segments": [],
+                    "entry_pc": 1024,
+                    "symbols": null
+                  }
+                }
+              ],
+              "queued": [
+                {
+                  "fw": {
+                    "segments": [],
+                    "entry_pc": 2048,
+                    "symbols": null
+                  }
+                }
+              ]
+            }
+          }
+        "#};
+
+        migrations::tests::run(21, given, expected);
+    }
+}
@@ -0,0 +1,66 @@
+use ciborium::Value;
+use kartoffels_utils::{CborMapExt, CborValueExt};
+
+pub fn run(world: &mut Value) {
+    for bot in world.query_mut("/bots/{alive,dead,queued}/*") {
+        bot.as_map_mut().unwrap().add_entry("owner", Value::Null);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::storage::migrations;
+    use indoc::indoc;
+
+    #[test]
+    fn test() {
+        let given = indoc! {r#"
+          {
+            "bots": {
+              "alive": [
+                {
+                  "id": "1234-1234-1234-1234"
+                }
+              ],
+              "dead": [
+                {
+                  "id": "2345-2345-2345-2345"
+                }
+              ],
+              "queued": [
+                {
+                  "id": "3456-3456-3456-3456"
+                }
+              ]
+            }
+          }
+        "#};
+
+        let expected = indoc! {r#"
+          {
+            "bots": {
+              "alive": [
+                {
+                  "id": "1234-1234-1234-1234",
+                  "owner": null
+                }
+              ],
+              "dead": [
+                {
+                  "id": "2345-2345-2345-2345",
+                  "owner": null
+                }
+              ],
+              "queued": [
+                {
+                  "id": "3456-3456-3456-3456",
+                  "owner": null
+                }
+              ]
+            }
+          }
+        "#};
+
+        migrations::tests::run(22, given, expected);
+    }
+}
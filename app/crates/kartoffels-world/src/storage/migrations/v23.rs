@@ -0,0 +1,64 @@
+use ciborium::Value;
+use kartoffels_utils::{CborMapExt, CborValueExt};
+
+pub fn run(world: &mut Value) {
+    let mode = Vec::default()
+        .with_entry("kind", Value::Text("deathmatch".into()))
+        .with_entry("win_score", Value::Null)
+        .with_entry("hill_radius", Value::Integer(0.into()));
+
+    world
+        .query_mut("/policy")
+        .next()
+        .unwrap()
+        .as_map_mut()
+        .unwrap()
+        .add_entry("mode", Value::Map(mode));
+
+    let round = Vec::default()
+        .with_entry("idx", Value::Integer(0.into()))
+        .with_entry("age", Value::Integer(0.into()))
+        .with_entry("scores", Value::Map(Default::default()));
+
+    world
+        .as_map_mut()
+        .unwrap()
+        .add_entry("round", Value::Map(round));
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::storage::migrations;
+    use indoc::indoc;
+
+    #[test]
+    fn test() {
+        let given = indoc! {r#"
+          {
+            "policy": {
+              "auto_respawn": true
+            }
+          }
+        "#};
+
+        let expected = indoc! {r#"
+          {
+            "policy": {
+              "auto_respawn": true,
+              "mode": {
+                "kind": "deathmatch",
+                "win_score": null,
+                "hill_radius": 0
+              }
+            },
+            "round": {
+              "idx": 0,
+              "age": 0,
+              "scores": {}
+            }
+          }
+        "#};
+
+        migrations::tests::run(23, given, expected);
+    }
+}
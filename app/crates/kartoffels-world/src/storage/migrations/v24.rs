@@ -0,0 +1,91 @@
+use ciborium::Value;
+use kartoffels_utils::{CborMapExt, CborValueExt};
+
+pub fn run(world: &mut Value) {
+    world
+        .query_mut("/policy/mode")
+        .next()
+        .unwrap()
+        .as_map_mut()
+        .unwrap()
+        .add_entry("round_duration", Value::Null);
+
+    world
+        .query_mut("/round")
+        .next()
+        .unwrap()
+        .as_map_mut()
+        .unwrap()
+        .rename_entry("scores", "bots");
+
+    for bot in world.query_mut("/round/bots/*") {
+        let score = bot.clone();
+
+        *bot = Value::Map(
+            Vec::default()
+                .with_entry("score", score)
+                .with_entry("kills", Value::Integer(0.into()))
+                .with_entry("age", Value::Integer(0.into())),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::storage::migrations;
+    use indoc::indoc;
+
+    #[test]
+    fn test() {
+        let given = indoc! {r#"
+          {
+            "policy": {
+              "mode": {
+                "kind": "deathmatch",
+                "win_score": null,
+                "hill_radius": 0
+              }
+            },
+            "round": {
+              "idx": 3,
+              "age": 1234,
+              "scores": {
+                "0000-0000-0000-0001": 5,
+                "0000-0000-0000-0002": 7
+              }
+            }
+          }
+        "#};
+
+        let expected = indoc! {r#"
+          {
+            "policy": {
+              "mode": {
+                "kind": "deathmatch",
+                "win_score": null,
+                "hill_radius": 0,
+                "round_duration": null
+              }
+            },
+            "round": {
+              "idx": 3,
+              "age": 1234,
+              "bots": {
+                "0000-0000-0000-0001": {
+                  "score": 5,
+                  "kills": 0,
+                  "age": 0
+                },
+                "0000-0000-0000-0002": {
+                  "score": 7,
+                  "kills": 0,
+                  "age": 0
+                }
+              }
+            }
+          }
+        "#};
+
+        migrations::tests::run(24, given, expected);
+    }
+}
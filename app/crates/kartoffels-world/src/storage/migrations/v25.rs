@@ -0,0 +1,90 @@
+use ciborium::Value;
+use kartoffels_utils::{CborMapExt, CborValueExt};
+
+pub fn run(world: &mut Value) {
+    for serial in world.query_mut("/bots/{alive,queued}/*/serial") {
+        serial
+            .as_map_mut()
+            .unwrap()
+            .add_entry("input", Value::Array(vec![]));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::storage::migrations;
+    use indoc::indoc;
+
+    #[test]
+    fn test() {
+        let given = indoc! {r#"
+          {
+            "bots": {
+              "alive": [
+                {
+                  "id": "0000-0000-0000-0001",
+                  "serial": {
+                    "curr": [104, 105],
+                    "next": [],
+                    "buffering": false
+                  }
+                }
+              ],
+              "dead": [
+                {
+                  "id": "0000-0000-0000-0002",
+                  "serial": [104, 105]
+                }
+              ],
+              "queued": [
+                {
+                  "id": "0000-0000-0000-0003",
+                  "serial": {
+                    "curr": [],
+                    "next": [],
+                    "buffering": false
+                  }
+                }
+              ]
+            }
+          }
+        "#};
+
+        let expected = indoc! {r#"
+          {
+            "bots": {
+              "alive": [
+                {
+                  "id": "0000-0000-0000-0001",
+                  "serial": {
+                    "curr": [104, 105],
+                    "next": [],
+                    "buffering": false,
+                    "input": []
+                  }
+                }
+              ],
+              "dead": [
+                {
+                  "id": "0000-0000-0000-0002",
+                  "serial": [104, 105]
+                }
+              ],
+              "queued": [
+                {
+                  "id": "0000-0000-0000-0003",
+                  "serial": {
+                    "curr": [],
+                    "next": [],
+                    "buffering": false,
+                    "input": []
+                  }
+                }
+              ]
+            }
+          }
+        "#};
+
+        migrations::tests::run(25, given, expected);
+    }
+}
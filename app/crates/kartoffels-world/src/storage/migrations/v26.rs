@@ -0,0 +1,52 @@
+use ciborium::Value;
+use kartoffels_utils::{CborMapExt, CborValueExt};
+
+pub fn run(world: &mut Value) {
+    world
+        .query_mut("/policy/energy")
+        .next()
+        .unwrap()
+        .as_map_mut()
+        .unwrap()
+        .add_entry("radio", Value::Integer(0.into()));
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::storage::migrations;
+    use indoc::indoc;
+
+    #[test]
+    fn test() {
+        let given = indoc! {r#"
+          {
+            "policy": {
+              "energy": {
+                "arm": 0,
+                "charge": 0,
+                "motor_move": 0,
+                "motor_turn": 0,
+                "radar": 0
+              }
+            }
+          }
+        "#};
+
+        let expected = indoc! {r#"
+          {
+            "policy": {
+              "energy": {
+                "arm": 0,
+                "charge": 0,
+                "motor_move": 0,
+                "motor_turn": 0,
+                "radar": 0,
+                "radio": 0
+              }
+            }
+          }
+        "#};
+
+        migrations::tests::run(26, given, expected);
+    }
+}
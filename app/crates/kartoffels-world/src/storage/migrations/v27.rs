@@ -0,0 +1,73 @@
+use ciborium::Value;
+use kartoffels_utils::{CborMapExt, CborValueExt};
+
+pub fn run(world: &mut Value) {
+    let mode = world
+        .query_mut("/policy/mode")
+        .next()
+        .unwrap()
+        .as_map_mut()
+        .unwrap();
+
+    mode.add_entry("hill", Value::Null);
+    mode.add_entry("bases", Value::Map(Default::default()));
+
+    if let Some(theme) = world
+        .query_mut("/theme")
+        .next()
+        .and_then(|theme| theme.as_map_mut())
+        && theme
+            .get_entry_mut("type")
+            .is_some_and(|ty| ty.as_text() == Some("file"))
+    {
+        theme.add_entry("hill", Value::Null);
+        theme.add_entry("bases", Value::Array(Default::default()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::storage::migrations;
+    use indoc::indoc;
+
+    #[test]
+    fn test() {
+        let given = indoc! {r#"
+          {
+            "policy": {
+              "mode": {
+                "hill_radius": 0,
+                "kind": "capture_the_flag",
+                "round_duration": null,
+                "win_score": null
+              }
+            },
+            "theme": {
+              "type": "file"
+            }
+          }
+        "#};
+
+        let expected = indoc! {r#"
+          {
+            "policy": {
+              "mode": {
+                "bases": {},
+                "hill": null,
+                "hill_radius": 0,
+                "kind": "capture_the_flag",
+                "round_duration": null,
+                "win_score": null
+              }
+            },
+            "theme": {
+              "bases": [],
+              "hill": null,
+              "type": "file"
+            }
+          }
+        "#};
+
+        migrations::tests::run(27, given, expected);
+    }
+}
@@ -0,0 +1,45 @@
+use ciborium::Value;
+use kartoffels_utils::{CborMapExt, CborValueExt};
+
+pub fn run(world: &mut Value) {
+    if let Some(theme) = world
+        .query_mut("/theme")
+        .next()
+        .and_then(|theme| theme.as_map_mut())
+        && theme.get_entry_mut("type").is_some_and(|ty| {
+            matches!(ty.as_text(), Some("arena") | Some("cave"))
+        })
+    {
+        theme.add_entry("chargers", Value::Integer(0.into()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::storage::migrations;
+    use indoc::indoc;
+
+    #[test]
+    fn test() {
+        let given = indoc! {r#"
+          {
+            "theme": {
+              "radius": 16,
+              "type": "arena"
+            }
+          }
+        "#};
+
+        let expected = indoc! {r#"
+          {
+            "theme": {
+              "chargers": 0,
+              "radius": 16,
+              "type": "arena"
+            }
+          }
+        "#};
+
+        migrations::tests::run(28, given, expected);
+    }
+}
@@ -1,19 +1,24 @@
 use crate::storage::Header;
 use crate::{
-    Bots, Lives, Map, Metronome, Policy, SerializedWorld, Shutdown, Theme,
-    WorldName, WorldPath, WorldRng,
+    Bots, FinishedRounds, Lives, Map, Metronome, Objects, Policy, Round,
+    RoundResult, SerializedWorld, Shutdown, Theme, WorldName, WorldPath,
+    WorldRng,
 };
-use anyhow::Context;
-use bevy_ecs::system::{Local, Res};
+use anyhow::{Context, Result};
+use bevy_ecs::system::{Local, Res, ResMut};
 use maybe_owned::MaybeOwned;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Path;
 use std::thread::JoinHandle;
 use std::time::{Duration, Instant};
-use std::{fs, thread};
+use std::{fs, mem, thread};
 use tracing::{debug, warn, Span};
 
 pub struct State {
     next_run_at: Instant,
     ongoing_save: Option<JoinHandle<()>>,
+    ongoing_archive: Option<JoinHandle<()>>,
 }
 
 impl Default for State {
@@ -21,6 +26,7 @@ impl Default for State {
         Self {
             next_run_at: next_run_at(),
             ongoing_save: Default::default(),
+            ongoing_archive: Default::default(),
         }
     }
 }
@@ -29,12 +35,15 @@ impl Default for State {
 pub fn save(
     mut state: Local<State>,
     bots: Res<Bots>,
+    mut finished: ResMut<FinishedRounds>,
     lives: Res<Lives>,
     map: Res<Map>,
     name: Res<WorldName>,
+    objects: Res<Objects>,
     path: Option<Res<WorldPath>>,
     policy: Res<Policy>,
     rng: Res<WorldRng>,
+    round: Res<Round>,
     shutdown: Option<Res<Shutdown>>,
     theme: Option<Res<Theme>>,
 ) {
@@ -42,6 +51,14 @@ pub fn save(
         return;
     };
 
+    archive(&mut state, &path, &mut finished);
+
+    if shutdown.is_some()
+        && let Some(handle) = state.ongoing_archive.take()
+    {
+        handle.join().expect("archiving-thread crashed");
+    }
+
     if Instant::now() < state.next_run_at && shutdown.is_none() {
         return;
     }
@@ -72,8 +89,10 @@ pub fn save(
         bots: MaybeOwned::Borrowed(&bots),
         map: MaybeOwned::Borrowed(&map),
         name: MaybeOwned::Owned(name.0.load().to_string()),
+        objects: MaybeOwned::Borrowed(&objects),
         policy: MaybeOwned::Borrowed(&policy),
         rng: MaybeOwned::Borrowed(&rng.0),
+        round: MaybeOwned::Borrowed(&round),
         lives: MaybeOwned::Borrowed(&lives),
         theme: theme.as_ref().map(|theme| MaybeOwned::Borrowed(&**theme)),
     };
@@ -132,6 +151,52 @@ pub fn save(
     }
 }
 
+/// Appends finished rounds into `<world>.rounds`.
+///
+/// Rounds are appended in order, so before spawning a new thread we wait for
+/// the previous one to complete - since rounds are rare, that should be
+/// instant.
+fn archive(state: &mut State, path: &WorldPath, finished: &mut FinishedRounds) {
+    if finished.0.is_empty() {
+        return;
+    }
+
+    if let Some(handle) = state.ongoing_archive.take() {
+        handle.join().expect("archiving-thread crashed");
+    }
+
+    let path = path.0.with_extension("rounds");
+    let rounds = mem::take(&mut finished.0);
+    let span = Span::current();
+
+    state.ongoing_archive = Some(thread::spawn(move || {
+        let _span = span.entered();
+
+        if let Err(err) = append_rounds(&path, &rounds) {
+            warn!("couldn't archive rounds: {err:?}");
+        }
+    }));
+}
+
+fn append_rounds(path: &Path, rounds: &[RoundResult]) -> Result<()> {
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("couldn't open `{}`", path.display()))?;
+
+    let mut buffer = Vec::new();
+
+    for round in rounds {
+        serde_json::to_writer(&mut buffer, round)?;
+        buffer.push(b'\n');
+    }
+
+    file.write_all(&buffer)?;
+
+    Ok(())
+}
+
 fn next_run_at() -> Instant {
     Instant::now() + Duration::from_mins(15)
 }
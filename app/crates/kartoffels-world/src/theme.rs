@@ -1,8 +1,14 @@
 mod arena;
 mod cave;
+mod dungeon;
+mod file;
+mod maze;
 
 pub use self::arena::*;
 pub use self::cave::*;
+pub use self::dungeon::*;
+pub use self::file::*;
+pub use self::maze::*;
 use crate::{Map, MapBuilder};
 use anyhow::{anyhow, Error, Result};
 use bevy_ecs::system::Resource;
@@ -18,6 +24,15 @@ pub enum Theme {
 
     #[serde(rename = "cave")]
     Cave(CaveTheme),
+
+    #[serde(rename = "dungeon")]
+    Dungeon(DungeonTheme),
+
+    #[serde(rename = "file")]
+    File(FileTheme),
+
+    #[serde(rename = "maze")]
+    Maze(MazeTheme),
 }
 
 impl Theme {
@@ -29,6 +44,9 @@ impl Theme {
         match self {
             Theme::Arena(this) => this.build(rng, map).await,
             Theme::Cave(this) => this.build(rng, map).await,
+            Theme::Dungeon(this) => this.build(rng, map).await,
+            Theme::File(this) => this.build(rng, map).await,
+            Theme::Maze(this) => this.build(rng, map).await,
         }
     }
 }
@@ -45,6 +63,18 @@ impl FromStr for Theme {
             return CaveTheme::from_str(spec).map(Theme::Cave);
         }
 
+        if let Some(spec) = spec.strip_prefix("dungeon:") {
+            return DungeonTheme::from_str(spec).map(Theme::Dungeon);
+        }
+
+        if let Some(spec) = spec.strip_prefix("file:") {
+            return FileTheme::from_str(spec).map(Theme::File);
+        }
+
+        if let Some(spec) = spec.strip_prefix("maze:") {
+            return MazeTheme::from_str(spec).map(Theme::Maze);
+        }
+
         Err(anyhow!("unknown theme"))
     }
 }
@@ -53,6 +83,7 @@ impl FromStr for Theme {
 mod tests {
     use super::*;
     use glam::uvec2;
+    use std::fs;
 
     #[test]
     fn from_str() {
@@ -65,5 +96,37 @@ mod tests {
             Theme::Cave(CaveTheme::new(uvec2(12, 34))),
             Theme::from_str("cave:width=12,height=34").unwrap(),
         );
+
+        assert_eq!(
+            Theme::Arena(ArenaTheme::new(123).with_chargers(4)),
+            Theme::from_str("arena:radius=123,chargers=4").unwrap(),
+        );
+
+        assert_eq!(
+            Theme::Cave(CaveTheme::new(uvec2(12, 34)).with_chargers(4)),
+            Theme::from_str("cave:width=12,height=34,chargers=4").unwrap(),
+        );
+
+        assert_eq!(
+            Theme::Dungeon(DungeonTheme::new(uvec2(12, 34))),
+            Theme::from_str("dungeon:width=12,height=34").unwrap(),
+        );
+
+        assert_eq!(
+            Theme::Maze(MazeTheme::new(uvec2(12, 34))),
+            Theme::from_str("maze:width=12,height=34").unwrap(),
+        );
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("duel.txt");
+
+        fs::write(&path, "---\n|a.b|").unwrap();
+
+        assert_eq!(
+            Theme::File(FileTheme::load(&path).unwrap()),
+            Theme::from_str(&format!("file:path={}", path.display())).unwrap(),
+        );
+
+        assert!(Theme::from_str("file:path=/does/not/exist.txt").is_err());
     }
 }
@@ -9,11 +9,20 @@ use std::str::FromStr;
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ArenaTheme {
     radius: u32,
+    chargers: u32,
 }
 
 impl ArenaTheme {
     pub fn new(radius: u32) -> Self {
-        Self { radius }
+        Self {
+            radius,
+            chargers: 0,
+        }
+    }
+
+    pub fn with_chargers(mut self, chargers: u32) -> Self {
+        self.chargers = chargers;
+        self
     }
 
     pub async fn build(
@@ -21,20 +30,24 @@ impl ArenaTheme {
         rng: &mut impl RngCore,
         mut map: MapBuilder,
     ) -> Result<Map> {
-        map.reveal(rng, {
+        let arena = {
             let map = Map::new(uvec2(self.radius, self.radius) * 2 + 1);
             let center = map.center();
             let radius = self.radius as f32;
 
-            map.map(|pos, tile| {
+            let mut map = map.map(|pos, tile| {
                 if center.as_vec2().distance(pos.as_vec2()) < radius {
                     TileKind::FLOOR.into()
                 } else {
                     tile
                 }
-            })
-        })
-        .await;
+            });
+
+            map.scatter_chargers(rng, self.chargers);
+            map
+        };
+
+        map.reveal(rng, arena).await;
 
         Ok(map.commit())
     }
@@ -45,6 +58,7 @@ impl FromStr for ArenaTheme {
 
     fn from_str(spec: &str) -> Result<Self> {
         let mut radius = None;
+        let mut chargers = 0;
 
         for entry in spec::entries(spec) {
             let entry = entry?;
@@ -53,6 +67,9 @@ impl FromStr for ArenaTheme {
                 "radius" => {
                     radius = Some(entry.value()?);
                 }
+                "chargers" => {
+                    chargers = entry.value()?;
+                }
                 key => {
                     return Err(anyhow!("unknown key: {key}"));
                 }
@@ -61,7 +78,7 @@ impl FromStr for ArenaTheme {
 
         let radius = radius.context("missing key: radius")?;
 
-        Ok(Self::new(radius))
+        Ok(Self::new(radius).with_chargers(chargers))
     }
 }
 
@@ -87,4 +104,19 @@ mod tests {
         Asserter::new("src/theme/arena/tests")
             .assert("build.txt", map.to_string());
     }
+
+    #[test]
+    fn build_with_chargers() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let map = ArenaTheme::from_str("radius=16,chargers=4")
+            .unwrap()
+            .build(&mut rng, MapBuilder::detached())
+            .now_or_never()
+            .unwrap()
+            .unwrap();
+
+        Asserter::new("src/theme/arena/tests")
+            .assert("build-with-chargers.txt", map.to_string());
+    }
 }
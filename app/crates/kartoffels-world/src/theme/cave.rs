@@ -15,11 +15,17 @@ use std::str::FromStr;
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct CaveTheme {
     size: UVec2,
+    chargers: u32,
 }
 
 impl CaveTheme {
     pub fn new(size: UVec2) -> Self {
-        Self { size }
+        Self { size, chargers: 0 }
+    }
+
+    pub fn with_chargers(mut self, chargers: u32) -> Self {
+        self.chargers = chargers;
+        self
     }
 
     pub async fn build(
@@ -68,6 +74,7 @@ impl CaveTheme {
 
         self.fill_pass(rng, &mut map).await;
 
+        map.with(|map| map.scatter_chargers(rng, self.chargers));
         map.commit()
     }
 
@@ -208,6 +215,7 @@ impl FromStr for CaveTheme {
     fn from_str(spec: &str) -> Result<Self> {
         let mut width = None;
         let mut height = None;
+        let mut chargers = 0;
 
         for entry in spec::entries(spec) {
             let entry = entry?;
@@ -219,6 +227,9 @@ impl FromStr for CaveTheme {
                 "height" => {
                     height = Some(entry.value()?);
                 }
+                "chargers" => {
+                    chargers = entry.value()?;
+                }
                 key => {
                     return Err(anyhow!("unknown key: {key}"));
                 }
@@ -228,7 +239,7 @@ impl FromStr for CaveTheme {
         let width = width.context("missing key: width")?;
         let height = height.context("missing key: height")?;
 
-        Ok(Self::new(uvec2(width, height)))
+        Ok(Self::new(uvec2(width, height)).with_chargers(chargers))
     }
 }
 
@@ -252,4 +263,19 @@ mod tests {
         Asserter::new("src/theme/cave/tests")
             .assert("build.txt", map.to_string());
     }
+
+    #[test]
+    fn build_with_chargers() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let map = CaveTheme::from_str("width=64,height=32,chargers=8")
+            .unwrap()
+            .build(&mut rng, MapBuilder::detached())
+            .now_or_never()
+            .unwrap()
+            .unwrap();
+
+        Asserter::new("src/theme/cave/tests")
+            .assert("build-with-chargers.txt", map.to_string());
+    }
 }
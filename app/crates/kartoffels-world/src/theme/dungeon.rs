@@ -0,0 +1,219 @@
+use super::MapBuilder;
+use crate::{spec, Map, TileKind};
+use anyhow::{anyhow, Context, Error, Result};
+use glam::{ivec2, uvec2, IVec2, UVec2};
+use rand::{Rng, RngCore};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// Rectangular rooms connected with corridors.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DungeonTheme {
+    size: UVec2,
+}
+
+impl DungeonTheme {
+    /// Smallest supported size - anything below wouldn't fit a single room.
+    pub const MIN_SIZE: UVec2 = uvec2(12, 8);
+
+    const ROOM_MIN_SIZE: IVec2 = ivec2(5, 4);
+    const ROOM_MAX_SIZE: IVec2 = ivec2(12, 8);
+
+    pub fn new(size: UVec2) -> Self {
+        assert!(size.cmpge(Self::MIN_SIZE).all());
+
+        Self { size }
+    }
+
+    pub async fn build(
+        &self,
+        rng: &mut impl RngCore,
+        mut map: MapBuilder,
+    ) -> Result<Map> {
+        let generated = self.generate(rng);
+
+        map.reveal(rng, generated).await;
+
+        Ok(map.commit())
+    }
+
+    fn generate(&self, rng: &mut impl RngCore) -> Map {
+        let mut map = Map::new(self.size);
+        let mut rooms = self.place_rooms(rng);
+
+        for &(min, max) in &rooms {
+            map.rect(min, max, TileKind::WALL_H);
+            map.rect(min + 1, max - 1, TileKind::FLOOR);
+            map.line(
+                ivec2(min.x, min.y + 1),
+                ivec2(min.x, max.y - 1),
+                TileKind::WALL_V,
+            );
+            map.line(
+                ivec2(max.x, min.y + 1),
+                ivec2(max.x, max.y - 1),
+                TileKind::WALL_V,
+            );
+
+            for corner in [min, ivec2(max.x, min.y), ivec2(min.x, max.y), max] {
+                map.set(corner, TileKind::DOOR);
+            }
+        }
+
+        // Connect neighbouring rooms, going left-to-right, which guarantees
+        // that every room is reachable from every other one
+        rooms.sort_by_key(|(min, max)| min.x + max.x);
+
+        for pair in rooms.windows(2) {
+            let src = (pair[0].0 + pair[0].1) / 2;
+            let dst = (pair[1].0 + pair[1].1) / 2;
+
+            let mid = if rng.gen_bool(0.5) {
+                ivec2(dst.x, src.y)
+            } else {
+                ivec2(src.x, dst.y)
+            };
+
+            for (p1, p2) in [(src, mid), (mid, dst)] {
+                Self::dig(&mut map, p1, p2);
+            }
+        }
+
+        // Surround corridors with walls
+        let mut floors = Vec::new();
+
+        map.for_each(|pos, tile| {
+            if tile.is_floor() {
+                floors.push(pos);
+            }
+        });
+
+        for pos in floors {
+            for dy in -1..=1 {
+                for dx in -1..=1 {
+                    map.set_if_void(pos + ivec2(dx, dy), TileKind::WALL);
+                }
+            }
+        }
+
+        map
+    }
+
+    fn place_rooms(&self, rng: &mut impl RngCore) -> Vec<(IVec2, IVec2)> {
+        let mut rooms: Vec<(IVec2, IVec2)> = Vec::new();
+        let area = self.size.as_ivec2();
+        let max_size = Self::ROOM_MAX_SIZE.min(area);
+
+        for _ in 0..(self.size.x * self.size.y / 16) {
+            let size = ivec2(
+                rng.gen_range(Self::ROOM_MIN_SIZE.x..=max_size.x),
+                rng.gen_range(Self::ROOM_MIN_SIZE.y..=max_size.y),
+            );
+
+            let min = ivec2(
+                rng.gen_range(0..=area.x - size.x),
+                rng.gen_range(0..=area.y - size.y),
+            );
+
+            let max = min + size - 1;
+
+            // Keep a gap between rooms, so that there's space for corridors
+            let overlaps = rooms.iter().any(|&(min2, max2)| {
+                min.x <= max2.x + 2
+                    && max.x + 2 >= min2.x
+                    && min.y <= max2.y + 2
+                    && max.y + 2 >= min2.y
+            });
+
+            if !overlaps {
+                rooms.push((min, max));
+            }
+        }
+
+        rooms
+    }
+
+    fn dig(map: &mut Map, p1: IVec2, p2: IVec2) {
+        let [x1, x2] = [p1.x.min(p2.x), p1.x.max(p2.x)];
+        let [y1, y2] = [p1.y.min(p2.y), p1.y.max(p2.y)];
+
+        for y in y1..=y2 {
+            for x in x1..=x2 {
+                map.set(ivec2(x, y), TileKind::FLOOR);
+            }
+        }
+    }
+}
+
+impl FromStr for DungeonTheme {
+    type Err = Error;
+
+    fn from_str(spec: &str) -> Result<Self> {
+        let mut width = None;
+        let mut height = None;
+
+        for entry in spec::entries(spec) {
+            let entry = entry?;
+
+            match entry.key {
+                "width" => {
+                    width = Some(entry.value()?);
+                }
+                "height" => {
+                    height = Some(entry.value()?);
+                }
+                key => {
+                    return Err(anyhow!("unknown key: {key}"));
+                }
+            }
+        }
+
+        let width = width.context("missing key: width")?;
+        let height = height.context("missing key: height")?;
+        let size = uvec2(width, height);
+
+        if size.cmplt(Self::MIN_SIZE).any() {
+            return Err(anyhow!(
+                "dungeon must be at least {}x{}",
+                Self::MIN_SIZE.x,
+                Self::MIN_SIZE.y,
+            ));
+        }
+
+        Ok(Self::new(size))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::FutureExt;
+    use kartoffels_utils::Asserter;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn build() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let map = DungeonTheme::from_str("width=64,height=32")
+            .unwrap()
+            .build(&mut rng, MapBuilder::detached())
+            .now_or_never()
+            .unwrap()
+            .unwrap();
+
+        Asserter::new("src/theme/dungeon/tests")
+            .assert("build.txt", map.to_string());
+    }
+
+    #[test]
+    fn from_str_err() {
+        assert_eq!(
+            "dungeon must be at least 12x8",
+            DungeonTheme::from_str("width=32,height=4")
+                .unwrap_err()
+                .to_string(),
+        );
+    }
+}
@@ -0,0 +1,369 @@
+use super::MapBuilder;
+use crate::{spec, Dir, Map, Object, ObjectKind, Objects, Policy, TileKind};
+use anyhow::{anyhow, Context, Error, Result};
+use glam::IVec2;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// Theme that loads a hand-authored map from a text file.
+///
+/// The file consists of a header, a `---` separator and the map itself, e.g.:
+///
+/// ```text
+/// name = duel
+/// policy = max-alive-bots=2,max-queued-bots=2
+/// spawn = a:e
+/// spawn = b:w
+/// object = c:gem
+/// ---
+/// +-------+
+/// |a..c..b|
+/// +-------+
+/// ```
+///
+/// Letters in the map are anchors - they are turned into floor and can be
+/// referred to from the header:
+///
+/// - `spawn = anchor[:dir]` marks a spawn point (optionally facing given
+///   direction, one of `n`, `e`, `s` or `w`); when there are no spawn points,
+///   bots get spawned at random,
+/// - `object = anchor:kind` places an object (`flag` or `gem`) there,
+/// - `hill = anchor` marks the hill's center for king-of-the-hill,
+/// - `base = anchor:team` marks team's base for capture-the-flag.
+///
+/// `policy` uses the same syntax as `--policy` and gets applied on top of the
+/// world's policy.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileTheme {
+    path: PathBuf,
+    name: Option<String>,
+    policy: Option<String>,
+    spawns: Vec<(IVec2, Option<Dir>)>,
+    objects: Vec<(IVec2, Object)>,
+    hill: Option<IVec2>,
+    bases: Vec<(IVec2, u8)>,
+    map: String,
+}
+
+impl FileTheme {
+    pub fn load(path: &Path) -> Result<Self> {
+        let src = fs::read_to_string(path)
+            .with_context(|| format!("couldn't read `{}`", path.display()))?;
+
+        Self::parse(path, &src)
+            .with_context(|| format!("couldn't parse `{}`", path.display()))
+    }
+
+    pub fn parse(path: &Path, src: &str) -> Result<Self> {
+        let (header, map) = if let Some(map) = src.strip_prefix("---\n") {
+            ("", map)
+        } else {
+            src.split_once("\n---\n")
+                .context("missing `---` separator between header and map")?
+        };
+
+        let map = map.trim_end_matches('\n');
+
+        for tile in map.bytes() {
+            if !(tile.is_ascii_alphabetic() || Self::TILES.contains(&tile)) {
+                return Err(anyhow!("unknown tile: `{}`", tile as char));
+            }
+        }
+
+        let (_, anchors) = Map::parse(map);
+
+        let mut this = Self {
+            path: path.to_owned(),
+            name: None,
+            policy: None,
+            spawns: Default::default(),
+            objects: Default::default(),
+            hill: None,
+            bases: Default::default(),
+            map: map.to_owned(),
+        };
+
+        for (idx, line) in header.lines().enumerate() {
+            let line = line.trim();
+
+            if line.is_empty() {
+                continue;
+            }
+
+            let (key, value) = line
+                .split_once('=')
+                .map(|(key, value)| (key.trim(), value.trim()))
+                .with_context(|| format!("missing `=` in line {}", idx + 1))?;
+
+            let anchor = |anchor: &str| {
+                let mut chars = anchor.chars();
+
+                chars
+                    .next()
+                    .filter(|_| chars.next().is_none())
+                    .and_then(|anchor| anchors.find(anchor))
+                    .with_context(|| format!("unknown anchor: `{anchor}`"))
+            };
+
+            match key {
+                "name" => {
+                    this.name = Some(value.to_owned());
+                }
+
+                "policy" => {
+                    // Make sure the policy is correct upfront, so that we don't
+                    // fail only once someone tries to create the world
+                    Policy::default().apply(value)?;
+
+                    this.policy = Some(value.to_owned());
+                }
+
+                "spawn" => {
+                    let (pos, dir) = match value.split_once(':') {
+                        Some((pos, dir)) => (pos, Some(dir.parse()?)),
+                        None => (value, None),
+                    };
+
+                    this.spawns.push((anchor(pos)?, dir));
+                }
+
+                "object" => {
+                    let (pos, kind) = value
+                        .split_once(':')
+                        .context("missing object kind, e.g. `a:gem`")?;
+
+                    let kind = match kind {
+                        "flag" => ObjectKind::FLAG,
+                        "gem" => ObjectKind::GEM,
+                        kind => {
+                            return Err(anyhow!("unknown object: `{kind}`"));
+                        }
+                    };
+
+                    this.objects.push((anchor(pos)?, Object::new(kind)));
+                }
+
+                "hill" => {
+                    this.hill = Some(anchor(value)?);
+                }
+
+                "base" => {
+                    let (pos, team) = value
+                        .split_once(':')
+                        .context("missing base's team, e.g. `a:0`")?;
+
+                    let team = team
+                        .parse()
+                        .with_context(|| format!("invalid team: `{team}`"))?;
+
+                    this.bases.push((anchor(pos)?, team));
+                }
+
+                key => {
+                    return Err(anyhow!("unknown key: `{key}`"));
+                }
+            }
+        }
+
+        Ok(this)
+    }
+
+    /// Returns map's name, falling back to the file name if the header doesn't
+    /// specify one.
+    pub fn name(&self) -> String {
+        if let Some(name) = &self.name {
+            return name.clone();
+        }
+
+        self.path
+            .file_stem()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default()
+    }
+
+    pub fn spawns(&self) -> &[(IVec2, Option<Dir>)] {
+        &self.spawns
+    }
+
+    pub async fn build(
+        &self,
+        rng: &mut impl RngCore,
+        mut map: MapBuilder,
+    ) -> Result<Map> {
+        map.reveal(rng, self.map()).await;
+
+        Ok(map.commit())
+    }
+
+    /// Applies map's policy overrides, hill, bases and objects - called once,
+    /// when the world gets created.
+    pub(crate) fn setup(
+        &self,
+        rng: &mut impl RngCore,
+        policy: &mut Policy,
+        objects: &mut Objects,
+    ) -> Result<()> {
+        if let Some(spec) = &self.policy {
+            policy.apply(spec)?;
+        }
+
+        if let Some(hill) = self.hill {
+            policy.mode.hill = Some(hill);
+        }
+
+        for (pos, team) in &self.bases {
+            policy.mode.bases.insert(*team, *pos);
+        }
+
+        for (pos, obj) in &self.objects {
+            objects.create(rng, *obj, Some(*pos));
+        }
+
+        Ok(())
+    }
+
+    fn map(&self) -> Map {
+        let (mut map, anchors) = Map::parse(&self.map);
+
+        anchors.fill(&mut map, TileKind::FLOOR);
+        map
+    }
+
+    const TILES: &[u8] = &[
+        TileKind::CHARGER,
+        TileKind::DOOR,
+        TileKind::FLOOR,
+        TileKind::VOID,
+        TileKind::WALL,
+        TileKind::WALL_H,
+        TileKind::WALL_V,
+        b'\n',
+    ];
+}
+
+impl FromStr for FileTheme {
+    type Err = Error;
+
+    fn from_str(spec: &str) -> Result<Self> {
+        let mut path = None;
+
+        for entry in spec::entries(spec) {
+            let entry = entry?;
+
+            match entry.key {
+                "path" => {
+                    path = Some(PathBuf::from(entry.value));
+                }
+                key => {
+                    return Err(anyhow!("unknown key: {key}"));
+                }
+            }
+        }
+
+        let path = path.context("missing key: path")?;
+
+        Self::load(&path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::FutureExt;
+    use glam::ivec2;
+    use indoc::indoc;
+    use kartoffels_utils::Asserter;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+    use std::collections::BTreeMap;
+
+    const MAP: &str = indoc! {"
+        name = duel
+        policy = max-alive-bots=2,friendly-fire=true
+        spawn = a:e
+        spawn = b
+        object = c:gem
+        hill = d
+        base = e:0
+        base = f:1
+
+        ---
+        +-------+
+        |a..c..b|
+        |e%.d.%f|
+        +-------+
+    "};
+
+    #[test]
+    fn parse() {
+        let target = FileTheme::parse(Path::new("maps/duel.txt"), MAP).unwrap();
+
+        assert_eq!("duel", target.name());
+
+        assert_eq!(
+            &[(ivec2(1, 1), Some(Dir::E)), (ivec2(7, 1), None)],
+            target.spawns(),
+        );
+
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut policy = Policy::default();
+        let mut objects = Objects::default();
+
+        target.setup(&mut rng, &mut policy, &mut objects).unwrap();
+
+        assert_eq!(2, policy.max_alive_bots);
+        assert!(policy.friendly_fire);
+        assert_eq!(Some(ivec2(4, 2)), policy.mode.hill);
+
+        assert_eq!(
+            BTreeMap::from([(0, ivec2(1, 2)), (1, ivec2(7, 2))]),
+            policy.mode.bases,
+        );
+
+        assert_eq!(
+            Some(Object::new(ObjectKind::GEM)),
+            objects.get_at(ivec2(4, 1)),
+        );
+
+        let map = target
+            .build(&mut rng, MapBuilder::detached())
+            .now_or_never()
+            .unwrap()
+            .unwrap();
+
+        Asserter::new("src/theme/file/tests")
+            .assert("parse.txt", map.to_string());
+    }
+
+    #[test]
+    fn parse_err() {
+        let cases = [
+            ("|.|", "missing `---` separator between header and map"),
+            ("---\n|?|", "unknown tile: `?`"),
+            ("foo = bar\n---\n|.|", "unknown key: `foo`"),
+            ("spawn = a\n---\n|.|", "unknown anchor: `a`"),
+            ("object = a\n---\n|a|", "missing object kind, e.g. `a:gem`"),
+            ("object = a:cat\n---\n|a|", "unknown object: `cat`"),
+            ("base = a\n---\n|a|", "missing base's team, e.g. `a:0`"),
+        ];
+
+        for (src, expected) in cases {
+            let actual = FileTheme::parse(Path::new("map.txt"), src)
+                .unwrap_err()
+                .to_string();
+
+            assert_eq!(expected, actual, "src: {src:?}");
+        }
+    }
+
+    #[test]
+    fn name() {
+        let target =
+            FileTheme::parse(Path::new("maps/arena.txt"), "---\n|.|").unwrap();
+
+        assert_eq!("arena", target.name());
+    }
+}
@@ -0,0 +1,137 @@
+use super::MapBuilder;
+use crate::{spec, Dir, Map, TileKind};
+use anyhow::{anyhow, Context, Error, Result};
+use glam::{uvec2, IVec2, UVec2};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// Acyclic maze, generated using the recursive backtracking algorithm.
+///
+/// Size is expressed in cells - since cells are separated by walls, the
+/// underlying map is `size * 2 + 1` tiles large.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MazeTheme {
+    size: UVec2,
+}
+
+impl MazeTheme {
+    pub fn new(size: UVec2) -> Self {
+        Self { size }
+    }
+
+    pub async fn build(
+        &self,
+        rng: &mut impl RngCore,
+        mut map: MapBuilder,
+    ) -> Result<Map> {
+        let generated = self.generate(rng);
+
+        map.reveal(rng, generated).await;
+
+        Ok(map.commit())
+    }
+
+    fn generate(&self, rng: &mut impl RngCore) -> Map {
+        let mut map = Map::new(self.size * 2 + 1);
+
+        map.fill(TileKind::WALL);
+
+        if self.size.x == 0 || self.size.y == 0 {
+            return map;
+        }
+
+        let cell = |pos: IVec2| pos * 2 + 1;
+        let mut visited = vec![false; (self.size.x * self.size.y) as usize];
+        let mut stack = vec![IVec2::ZERO];
+
+        visited[0] = true;
+        map.set(cell(IVec2::ZERO), TileKind::FLOOR);
+
+        while let Some(&pos) = stack.last() {
+            let next = Dir::shuffled(rng).into_iter().find_map(|dir| {
+                let next = pos + dir;
+
+                if next.x < 0
+                    || next.y < 0
+                    || next.x >= self.size.x as i32
+                    || next.y >= self.size.y as i32
+                {
+                    return None;
+                }
+
+                let idx =
+                    (next.y as u32 * self.size.x + next.x as u32) as usize;
+
+                (!visited[idx]).then_some((next, idx))
+            });
+
+            if let Some((next, idx)) = next {
+                visited[idx] = true;
+
+                map.set(cell(pos) + (next - pos), TileKind::FLOOR);
+                map.set(cell(next), TileKind::FLOOR);
+
+                stack.push(next);
+            } else {
+                stack.pop();
+            }
+        }
+
+        map
+    }
+}
+
+impl FromStr for MazeTheme {
+    type Err = Error;
+
+    fn from_str(spec: &str) -> Result<Self> {
+        let mut width = None;
+        let mut height = None;
+
+        for entry in spec::entries(spec) {
+            let entry = entry?;
+
+            match entry.key {
+                "width" => {
+                    width = Some(entry.value()?);
+                }
+                "height" => {
+                    height = Some(entry.value()?);
+                }
+                key => {
+                    return Err(anyhow!("unknown key: {key}"));
+                }
+            }
+        }
+
+        let width = width.context("missing key: width")?;
+        let height = height.context("missing key: height")?;
+
+        Ok(Self::new(uvec2(width, height)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::FutureExt;
+    use kartoffels_utils::Asserter;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn build() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let map = MazeTheme::from_str("width=16,height=8")
+            .unwrap()
+            .build(&mut rng, MapBuilder::detached())
+            .now_or_never()
+            .unwrap()
+            .unwrap();
+
+        Asserter::new("src/theme/maze/tests")
+            .assert("build.txt", map.to_string());
+    }
+}
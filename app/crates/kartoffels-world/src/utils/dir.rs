@@ -1,8 +1,10 @@
+use anyhow::{anyhow, Error, Result};
 use glam::{ivec2, IVec2};
 use rand::distributions::{Distribution, Standard};
 use rand::seq::SliceRandom;
 use rand::{Rng, RngCore};
 use serde::{Deserialize, Serialize};
+use std::str::FromStr;
 use std::{fmt, ops};
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
@@ -86,6 +88,20 @@ impl fmt::Display for Dir {
     }
 }
 
+impl FromStr for Dir {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "n" => Ok(Dir::N),
+            "e" => Ok(Dir::E),
+            "s" => Ok(Dir::S),
+            "w" => Ok(Dir::W),
+            s => Err(anyhow!("unknown direction: `{s}`")),
+        }
+    }
+}
+
 impl Distribution<Dir> for Standard {
     fn sample<R>(&self, rng: &mut R) -> Dir
     where
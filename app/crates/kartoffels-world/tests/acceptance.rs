@@ -1,16 +1,17 @@
 use glam::{ivec2, uvec2};
 use indoc::indoc;
-use kartoffels_prefabs::{DUMMY, ROBERTO};
+use kartoffels_prefabs::{CHL_PERSONAL_ROOMBA, DUMMY, RADIO, ROBERTO};
 use kartoffels_utils::{Asserter, ErrorExt};
 use kartoffels_world::prelude::*;
 use std::future::Future;
 use std::path::Path;
 use std::sync::Arc;
 use tempfile::NamedTempFile;
+use test_case::test_case;
 
 #[tokio::test]
 async fn smoke() {
-    let world = kartoffels_world::create(config());
+    let world = kartoffels_world::create(config()).unwrap();
     let mut asserter = asserter("smoke");
 
     for _ in 0..16 {
@@ -31,7 +32,7 @@ async fn smoke() {
 
 #[tokio::test]
 async fn pause_and_resume() {
-    let world = kartoffels_world::create(config());
+    let world = kartoffels_world::create(config()).unwrap();
 
     for _ in 0..16 {
         world
@@ -70,9 +71,85 @@ async fn pause_and_resume() {
     assert_ne!(snap3.bots, snap4.bots);
 }
 
+#[tokio::test]
+async fn tick_with_unlimited_clock() {
+    let world = kartoffels_world::create(Config {
+        clock: Clock::Unlimited,
+        ..config()
+    })
+    .unwrap();
+
+    world.pause().await.unwrap();
+
+    let bot = world
+        .create_bot(CreateBotRequest::new(ROBERTO))
+        .await
+        .unwrap();
+
+    world.tick(10_000).await.unwrap();
+
+    // World should've been paused right after the last tick, so from now on
+    // all snapshots should show the same thing
+    let mut snapshots = world.snapshots();
+    let version = world.version();
+
+    let snap1 = loop {
+        let snapshot = snapshots.next().await.unwrap();
+
+        if snapshot.version > version {
+            break snapshot;
+        }
+    };
+
+    let snap2 = snapshots.next().await.unwrap();
+
+    assert_ne!(snap1.version, snap2.version);
+    assert_eq!(snap1.bots, snap2.bots);
+
+    let age = snap1.bots.alive.get(bot).unwrap().age;
+
+    assert!((10_000..10_000 + 32).contains(&age.ticks()), "age: {age:?}");
+}
+
+#[tokio::test]
+async fn parallel_tick() {
+    let mut snaps = Vec::new();
+
+    for sequential in [false, true] {
+        // Spawn more bots than a single thread ticks, so that the work gets
+        // actually split
+        let world = kartoffels_world::create(Config {
+            policy: Policy {
+                max_alive_bots: 64,
+                max_queued_bots: 64,
+                ..config().policy
+            },
+            sequential,
+            ..config()
+        })
+        .unwrap();
+
+        for _ in 0..64 {
+            world
+                .create_bot(CreateBotRequest::new(ROBERTO))
+                .await
+                .unwrap();
+        }
+
+        world.tick(5_000).await.unwrap();
+
+        snaps.push(world.snapshot().await);
+    }
+
+    assert_eq!(64, snaps[0].bots.alive.len());
+    assert_eq!(snaps[0].bots, snaps[1].bots);
+    assert_eq!(snaps[0].round, snaps[1].round);
+    assert_eq!(snaps[0].map, snaps[1].map);
+}
+
 #[tokio::test]
 async fn kill_bot() {
-    let world = kartoffels_world::create(config());
+    let world = kartoffels_world::create(config()).unwrap();
 
     let bot1 = world
         .create_bot(CreateBotRequest::new(DUMMY))
@@ -108,7 +185,7 @@ async fn kill_bot() {
 
 #[tokio::test]
 async fn delete_bot() {
-    let world = kartoffels_world::create(config());
+    let world = kartoffels_world::create(config()).unwrap();
 
     let bot1 = world
         .create_bot(CreateBotRequest::new(DUMMY))
@@ -147,9 +224,248 @@ async fn delete_bot() {
     assert!(snap2.bots.alive.get(bot3).is_some());
 }
 
+#[tokio::test]
+async fn debug_bot() {
+    let world = kartoffels_world::create(config()).unwrap();
+
+    let bot = world
+        .create_bot(CreateBotRequest::new(DUMMY))
+        .await
+        .unwrap();
+
+    // Give the bot a moment to reach its main loop
+    world.tick(1_000).await.unwrap();
+    world.pause_bot(bot).await.unwrap();
+    world.tick(1).await.unwrap();
+
+    let snap1 = world.snapshot().await;
+    let bot1 = snap1.bots.alive.get(bot).unwrap();
+    let debugger1 = bot1.debugger.as_ref().unwrap();
+
+    assert!(debugger1.paused);
+    assert!(debugger1.disassemble(debugger1.pc).is_some());
+
+    // ---
+
+    world.tick(100).await.unwrap();
+
+    let snap2 = world.snapshot().await;
+    let bot2 = snap2.bots.alive.get(bot).unwrap();
+
+    assert_eq!(bot1.age, bot2.age);
+
+    // ---
+
+    world.step_bot(bot).await.unwrap();
+    world.tick(100).await.unwrap();
+
+    let snap3 = world.snapshot().await;
+    let bot3 = snap3.bots.alive.get(bot).unwrap();
+
+    assert_eq!(bot1.age.ticks() + 1, bot3.age.ticks());
+
+    // ---
+
+    world.add_breakpoint(bot, debugger1.pc).await.unwrap();
+    world.resume_bot(bot).await.unwrap();
+    world.tick(100).await.unwrap();
+
+    let snap4 = world.snapshot().await;
+    let bot4 = snap4.bots.alive.get(bot).unwrap();
+    let debugger4 = bot4.debugger.as_ref().unwrap();
+
+    assert!(debugger4.paused);
+    assert_eq!(debugger1.pc, debugger4.pc);
+    assert_eq!(bot1.age.ticks() + 2, bot4.age.ticks());
+
+    // ---
+
+    world.remove_breakpoint(bot, debugger1.pc).await.unwrap();
+    world.resume_bot(bot).await.unwrap();
+    world.tick(100).await.unwrap();
+
+    let snap5 = world.snapshot().await;
+    let bot5 = snap5.bots.alive.get(bot).unwrap();
+
+    assert!(bot5.debugger.is_none());
+    assert!(bot5.age.ticks() > bot4.age.ticks());
+
+    // ---
+
+    // Memory is sent only for paused bots
+    world.add_breakpoint(bot, 0).await.unwrap();
+    world.tick(1).await.unwrap();
+
+    let snap6 = world.snapshot().await;
+    let bot6 = snap6.bots.alive.get(bot).unwrap();
+    let debugger6 = bot6.debugger.as_ref().unwrap();
+
+    assert!(!debugger6.paused);
+    assert!(debugger6.disassemble(debugger6.pc).is_none());
+}
+
+#[tokio::test]
+async fn profile_bot() {
+    let world = kartoffels_world::create(config()).unwrap();
+
+    let bot = world
+        .create_bot(CreateBotRequest::new(ROBERTO))
+        .await
+        .unwrap();
+
+    world.tick(1).await.unwrap();
+
+    assert_eq!(
+        "bot is not being profiled",
+        world.profile_bot(bot).await.unwrap_err().to_string(),
+    );
+
+    world.start_profiling_bot(bot).await.unwrap();
+
+    let profile1 = world.profile_bot(bot).await.unwrap();
+
+    assert_eq!(0, profile1.samples);
+    assert!(profile1.functions.is_empty());
+
+    // ---
+
+    world.tick(10_000).await.unwrap();
+
+    let profile2 = world.profile_bot(bot).await.unwrap();
+
+    assert!(profile2.samples > 0);
+
+    assert_eq!(
+        profile2.samples,
+        profile2
+            .functions
+            .iter()
+            .map(|fun| fun.samples)
+            .sum::<u32>(),
+    );
+
+    assert!(profile2
+        .functions
+        .is_sorted_by(|lhs, rhs| lhs.samples >= rhs.samples));
+
+    // ---
+
+    // Another viewer joins - samples get reset, but since the first viewer is
+    // still around, stopping once keeps the profiler running
+    world.start_profiling_bot(bot).await.unwrap();
+
+    let profile3 = world.profile_bot(bot).await.unwrap();
+
+    assert_eq!(0, profile3.samples);
+
+    world.stop_profiling_bot(bot).await.unwrap();
+    world.tick(1_000).await.unwrap();
+
+    let profile4 = world.profile_bot(bot).await.unwrap();
+
+    assert!(profile4.samples > 0);
+
+    // ---
+
+    world.stop_profiling_bot(bot).await.unwrap();
+
+    assert_eq!(
+        "bot is not being profiled",
+        world.profile_bot(bot).await.unwrap_err().to_string(),
+    );
+
+    // ---
+
+    world.delete_bot(bot).await.unwrap();
+
+    assert_eq!(
+        "bot is not alive",
+        world.profile_bot(bot).await.unwrap_err().to_string(),
+    );
+}
+
+#[tokio::test]
+async fn send_to_bot() {
+    let world = kartoffels_world::create(config()).unwrap();
+
+    let bot = world
+        .create_bot(CreateBotRequest::new(DUMMY))
+        .await
+        .unwrap();
+
+    world.tick(1).await.unwrap();
+    world.send_to_bot(bot, "hello!\n".into()).await.unwrap();
+
+    assert_eq!(
+        "bot's serial input is full - it can hold up to 256 characters",
+        world
+            .send_to_bot(bot, "x".repeat(250))
+            .await
+            .unwrap_err()
+            .to_string(),
+    );
+
+    world.delete_bot(bot).await.unwrap();
+
+    assert_eq!(
+        "bot is not alive",
+        world
+            .send_to_bot(bot, "hello!".into())
+            .await
+            .unwrap_err()
+            .to_string(),
+    );
+}
+
+#[tokio::test]
+async fn radio() {
+    let world = kartoffels_world::create(config()).unwrap();
+
+    // Range is 4 tiles, so `a` and `b` can hear each other, but `c` is too far
+    // away from both
+    let [a, b, c] = [ivec2(8, 12), ivec2(12, 12), ivec2(17, 12)];
+    let mut ids = Vec::new();
+
+    for pos in [a, b, c] {
+        let id = world
+            .create_bot(CreateBotRequest::new(RADIO).at(pos).instant())
+            .await
+            .unwrap();
+
+        ids.push(id);
+    }
+
+    world.tick(100_000).await.unwrap();
+
+    let snapshot = world.snapshot().await;
+
+    let actual: Vec<_> = ids
+        .iter()
+        .map(|id| {
+            snapshot
+                .bots
+                .alive
+                .get(*id)
+                .unwrap()
+                .serial
+                .iter()
+                .filter_map(|ch| char::from_u32(*ch))
+                .collect::<String>()
+        })
+        .collect();
+
+    let expected = vec![
+        format!("{}\n", ids[1].to_string().replace('-', "")),
+        format!("{}\n", ids[0].to_string().replace('-', "")),
+        String::new(),
+    ];
+
+    assert_eq!(expected, actual);
+}
+
 #[tokio::test]
 async fn set_map() {
-    let world = kartoffels_world::create(config());
+    let world = kartoffels_world::create(config()).unwrap();
 
     assert_eq!(uvec2(0, 0), world.snapshot().await.map.size());
     assert_eq!(uvec2(0, 0), world.snapshot().await.tiles.size());
@@ -180,7 +496,7 @@ async fn set_map() {
 
 #[tokio::test]
 async fn set_spawn() {
-    let world = kartoffels_world::create(config());
+    let world = kartoffels_world::create(config()).unwrap();
 
     // First bot gets born at a random place
     world
@@ -234,6 +550,258 @@ async fn set_spawn() {
     assert_eq!(expected, actual);
 }
 
+#[tokio::test]
+async fn file_theme() {
+    let file = NamedTempFile::new().unwrap();
+
+    std::fs::write(
+        file.path(),
+        indoc! {"
+            policy = max-alive-bots=2
+            spawn = a:e
+            spawn = b:w
+            object = c:gem
+            ---
+            +-----+
+            |a.c.b|
+            +-----+
+        "},
+    )
+    .unwrap();
+
+    let world = kartoffels_world::create(Config {
+        theme: Some(Theme::File(FileTheme::load(file.path()).unwrap())),
+        ..config()
+    })
+    .unwrap();
+
+    // First two bots get born at the spawn points, while the third one waits
+    // in the queue, because the map's policy allows for two alive bots
+    for _ in 0..3 {
+        world
+            .create_bot(CreateBotRequest::new(DUMMY))
+            .await
+            .unwrap();
+    }
+
+    world.tick(1).await.unwrap();
+
+    let snapshot = world.snapshot().await;
+
+    let mut actual: Vec<_> = snapshot
+        .bots
+        .alive
+        .iter()
+        .map(|bot| (bot.pos, bot.dir))
+        .collect();
+
+    actual.sort_by_key(|(pos, _)| pos.x);
+
+    assert_eq!(vec![(ivec2(1, 1), Dir::E), (ivec2(5, 1), Dir::W)], actual);
+    assert!(!snapshot.bots.queued.is_empty());
+
+    let objects: Vec<_> = snapshot
+        .objects
+        .iter()
+        .map(|obj| (obj.pos, obj.obj.kind))
+        .collect();
+
+    assert_eq!(vec![(Some(ivec2(3, 1)), ObjectKind::GEM)], objects);
+}
+
+#[tokio::test]
+async fn king_of_the_hill() {
+    let world = kartoffels_world::create(Config {
+        events: true,
+        policy: Policy {
+            mode: ModePolicy {
+                kind: GameMode::KingOfTheHill,
+                win_score: Some(2),
+                hill: None,
+                hill_radius: 2,
+                bases: Default::default(),
+                round_duration: None,
+            },
+            ..config().policy
+        },
+        ..config()
+    })
+    .unwrap();
+
+    let mut events = world.events().unwrap();
+
+    // First bot sits on the hill, second one doesn't
+    let king = world
+        .create_bot(CreateBotRequest::new(DUMMY).at(ivec2(12, 13)))
+        .await
+        .unwrap();
+
+    world
+        .create_bot(CreateBotRequest::new(DUMMY).at(ivec2(4, 12)))
+        .await
+        .unwrap();
+
+    world.tick(2 * 64_000).await.unwrap();
+
+    let mut scored = Vec::new();
+
+    let winner = loop {
+        match events.next().await.unwrap().event {
+            Event::BotScored { id } => {
+                scored.push(id);
+            }
+            Event::RoundEnded { winner } => {
+                break winner;
+            }
+            _ => (),
+        }
+    };
+
+    assert_eq!(Some(king), winner);
+    assert_eq!(vec![king, king], scored);
+    assert_eq!(
+        2,
+        world.snapshot().await.lives.get(king).unwrap().curr.score
+    );
+}
+
+#[tokio::test]
+async fn capture_the_flag() {
+    let world = kartoffels_world::create(Config {
+        policy: Policy {
+            mode: ModePolicy {
+                kind: GameMode::CaptureTheFlag,
+                bases: [(0, ivec2(4, 12)), (1, ivec2(12, 10))].into(),
+                ..Default::default()
+            },
+            ..config().policy
+        },
+        ..config()
+    })
+    .unwrap();
+
+    // Bot starts two tiles away from its base, with an enemy flag in between -
+    // it should pick the flag, step forward and score
+    let flag = world
+        .create_object(Object::flag(0), ivec2(12, 11))
+        .await
+        .unwrap();
+
+    let bot = world
+        .create_bot(
+            CreateBotRequest::new(CHL_PERSONAL_ROOMBA)
+                .at(ivec2(12, 12))
+                .facing(Dir::N)
+                .team(1),
+        )
+        .await
+        .unwrap();
+
+    // Wait for the bot to pick the flag and score
+    for _ in 0..100 {
+        world.tick(1000).await.unwrap();
+
+        if world
+            .snapshot()
+            .await
+            .lives
+            .get(bot)
+            .is_some_and(|life| life.curr.score > 0)
+        {
+            break;
+        }
+    }
+
+    assert_eq!(1, world.snapshot().await.lives.get(bot).unwrap().curr.score);
+
+    // Captured flag gets back to its base
+    let mut actual: Vec<_> = world
+        .snapshot()
+        .await
+        .objects
+        .iter()
+        .map(|obj| (obj.obj.team(), obj.pos))
+        .collect();
+
+    actual.sort_by_key(|(team, _)| *team);
+
+    let expected = vec![
+        (Some(0), Some(ivec2(4, 12))),
+        (Some(1), Some(ivec2(12, 10))),
+    ];
+
+    assert_eq!(expected, actual);
+
+    let actual = world
+        .snapshot()
+        .await
+        .objects
+        .iter()
+        .find(|obj| obj.obj.team() == Some(0))
+        .map(|obj| obj.id);
+
+    assert_eq!(Some(flag), actual);
+}
+
+#[tokio::test]
+async fn timed_round() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("arena.world");
+
+    let world = kartoffels_world::create(Config {
+        events: true,
+        name: "arena".into(),
+        path: Some(path.clone()),
+        policy: Policy {
+            mode: ModePolicy {
+                round_duration: Some(1),
+                ..config().policy.mode
+            },
+            ..config().policy
+        },
+        ..config()
+    })
+    .unwrap();
+
+    let mut events = world.events().unwrap();
+
+    let bot = world
+        .create_bot(CreateBotRequest::new(DUMMY))
+        .await
+        .unwrap();
+
+    world.tick(64_000).await.unwrap();
+
+    let winner = loop {
+        if let Event::RoundEnded { winner } = events.next().await.unwrap().event
+        {
+            break winner;
+        }
+    };
+
+    // Nobody scored, so there's no winner
+    assert_eq!(None, winner);
+
+    // Rounds are archived in the background - shutting down waits for that
+    world.shutdown().await.unwrap();
+
+    let rounds =
+        std::fs::read_to_string(path.with_extension("rounds")).unwrap();
+    let rounds: Vec<RoundResult> = rounds
+        .lines()
+        .map(|round| serde_json::from_str(round).unwrap())
+        .collect();
+
+    assert_eq!(1, rounds.len());
+    assert_eq!("arena", rounds[0].world);
+    assert_eq!(0, rounds[0].idx);
+    assert_eq!(None, rounds[0].winner);
+    assert_eq!(1, rounds[0].bots.len());
+    assert_eq!(bot, rounds[0].bots[0].id);
+    assert_eq!(0, rounds[0].bots[0].score);
+    assert!(rounds[0].bots[0].age.ticks() > 0);
+}
+
 #[tokio::test]
 async fn with_auto_respawn() {
     let world = kartoffels_world::create(Config {
@@ -242,7 +810,8 @@ async fn with_auto_respawn() {
             ..config().policy
         },
         ..config()
-    });
+    })
+    .unwrap();
 
     let bot = world
         .create_bot(CreateBotRequest::new(DUMMY))
@@ -287,7 +856,8 @@ async fn without_auto_respawn() {
             ..config().policy
         },
         ..config()
-    });
+    })
+    .unwrap();
 
     let bot = world
         .create_bot(CreateBotRequest::new(DUMMY))
@@ -318,6 +888,98 @@ async fn without_auto_respawn() {
     assert_eq!(expected, actual);
 }
 
+#[tokio::test]
+async fn battery_depleted() {
+    let world = kartoffels_world::create(Config {
+        policy: Policy {
+            auto_respawn: false,
+            energy: EnergyPolicy {
+                radar: 4096,
+                ..Default::default()
+            },
+            ..config().policy
+        },
+        ..config()
+    })
+    .unwrap();
+
+    let bot = world
+        .create_bot(CreateBotRequest::new(ROBERTO))
+        .await
+        .unwrap();
+
+    world.tick(100_000).await.unwrap();
+
+    let snapshot = world.snapshot().await;
+
+    assert!(snapshot.bots.alive.get(bot).is_none());
+
+    let actual = snapshot
+        .bots
+        .dead
+        .get(bot)
+        .unwrap()
+        .events
+        .front()
+        .unwrap()
+        .msg
+        .clone();
+
+    assert_eq!("battery depleted", actual);
+}
+
+#[test_case(false ; "without friendly fire")]
+#[test_case(true ; "with friendly fire")]
+#[tokio::test]
+async fn teams(friendly_fire: bool) {
+    let world = kartoffels_world::create(Config {
+        policy: Policy {
+            auto_respawn: false,
+            friendly_fire,
+            ..config().policy
+        },
+        ..config()
+    })
+    .unwrap();
+
+    let bot1 = world
+        .create_bot(
+            CreateBotRequest::new(ROBERTO)
+                .at(ivec2(12, 12))
+                .facing(Dir::E)
+                .team(1)
+                .instant(),
+        )
+        .await
+        .unwrap();
+
+    let bot2 = world
+        .create_bot(
+            CreateBotRequest::new(ROBERTO)
+                .at(ivec2(13, 12))
+                .facing(Dir::W)
+                .team(1)
+                .instant(),
+        )
+        .await
+        .unwrap();
+
+    world.tick(100_000).await.unwrap();
+
+    let snapshot = world.snapshot().await;
+
+    let alive = [bot1, bot2]
+        .into_iter()
+        .filter(|bot| snapshot.bots.alive.get(*bot).is_some())
+        .count();
+
+    if friendly_fire {
+        assert_eq!(1, alive);
+    } else {
+        assert_eq!(2, alive);
+    }
+}
+
 #[tokio::test]
 async fn resume() {
     let file = NamedTempFile::new().unwrap();
@@ -325,13 +987,24 @@ async fn resume() {
     let world = kartoffels_world::create(Config {
         path: Some(file.path().to_owned()),
         ..config()
-    });
+    })
+    .unwrap();
 
     let bot = world
         .create_bot(CreateBotRequest::new(DUMMY))
         .await
         .unwrap();
 
+    let gem = world
+        .create_object(Object::new(ObjectKind::GEM), ivec2(1, 1))
+        .await
+        .unwrap();
+
+    let flag = world
+        .create_object(Object::new(ObjectKind::FLAG), None)
+        .await
+        .unwrap();
+
     // ---
 
     world.shutdown().await.unwrap();
@@ -359,6 +1032,171 @@ async fn resume() {
     let expected = vec![bot];
 
     assert_eq!(expected, actual);
+
+    // ---
+
+    let mut actual: Vec<_> = world
+        .snapshot()
+        .await
+        .objects
+        .iter()
+        .map(|obj| (obj.id, obj.obj.kind, obj.pos))
+        .collect();
+
+    actual.sort_by_key(|(id, _, _)| *id);
+
+    let mut expected = vec![
+        (gem, ObjectKind::GEM, Some(ivec2(1, 1))),
+        (flag, ObjectKind::FLAG, None),
+    ];
+
+    expected.sort_by_key(|(id, _, _)| *id);
+
+    assert_eq!(expected, actual);
+}
+
+#[tokio::test]
+async fn resume_with_inventory() {
+    let file = NamedTempFile::new().unwrap();
+
+    // In capture-the-flag mode bots drop their flags when they die, which
+    // allows us to peek into the inventory
+    let world = kartoffels_world::create(Config {
+        path: Some(file.path().to_owned()),
+        policy: Policy {
+            mode: ModePolicy {
+                kind: GameMode::CaptureTheFlag,
+                ..Default::default()
+            },
+            ..config().policy
+        },
+        ..config()
+    })
+    .unwrap();
+
+    let flag = world
+        .create_object(Object::new(ObjectKind::FLAG), ivec2(12, 10))
+        .await
+        .unwrap();
+
+    let bot = world
+        .create_bot(
+            CreateBotRequest::new(CHL_PERSONAL_ROOMBA)
+                .at(ivec2(12, 12))
+                .facing(Dir::N),
+        )
+        .await
+        .unwrap();
+
+    // Wait for the bot to pick the flag
+    for _ in 0..100 {
+        if world.snapshot().await.objects.iter().next().is_none() {
+            break;
+        }
+
+        world.tick(1000).await.unwrap();
+    }
+
+    assert!(world.snapshot().await.objects.iter().next().is_none());
+
+    // ---
+
+    world.shutdown().await.unwrap();
+
+    let world = kartoffels_world::resume(world.id(), file.path()).unwrap();
+
+    world.overclock(Clock::manual()).await.unwrap();
+    world.tick(1).await.unwrap();
+
+    let snapshot = world.snapshot().await;
+
+    assert!(snapshot.bots.alive.get(bot).is_some());
+    assert!(snapshot.objects.iter().next().is_none());
+
+    // ---
+
+    world.kill_bot(bot, "because").await.unwrap();
+    world.tick(1).await.unwrap();
+
+    let actual: Vec<_> = world
+        .snapshot()
+        .await
+        .objects
+        .iter()
+        .map(|obj| (obj.id, obj.obj.kind))
+        .collect();
+
+    assert_eq!(vec![(flag, ObjectKind::FLAG)], actual);
+}
+
+#[tokio::test]
+async fn record_and_replay() {
+    let file = NamedTempFile::new().unwrap();
+    let world = kartoffels_world::create(config()).unwrap();
+    let mut bots = Vec::new();
+
+    for _ in 0..4 {
+        bots.push(
+            world
+                .create_bot(CreateBotRequest::new(ROBERTO))
+                .await
+                .unwrap(),
+        );
+    }
+
+    world.tick(1_000).await.unwrap();
+
+    // Leave a hole between alive bots, so that the replay has to preserve
+    // the order in which bots are ticked
+    world.delete_bot(bots[1]).await.unwrap();
+    world.tick(1).await.unwrap();
+
+    let ids = async || {
+        world
+            .snapshot()
+            .await
+            .bots
+            .alive
+            .iter()
+            .map(|bot| bot.id)
+            .collect::<Vec<_>>()
+    };
+
+    let before = ids().await;
+
+    world.record(file.path()).await.unwrap();
+    world.tick(1).await.unwrap();
+
+    // Starting the recording shouldn't affect the world
+    assert_eq!(before, ids().await);
+
+    for _ in 0..4 {
+        world
+            .create_bot(CreateBotRequest::new(ROBERTO))
+            .await
+            .unwrap();
+    }
+
+    world
+        .create_object(Object::new(ObjectKind::GEM), ivec2(1, 1))
+        .await
+        .unwrap();
+
+    world.tick(10_000).await.unwrap();
+    world.pause().await.unwrap();
+    world.tick(1_000).await.unwrap();
+    world.resume().await.unwrap();
+    world.tick(10_000).await.unwrap();
+    world.shutdown().await.unwrap();
+
+    // ---
+
+    let path = file.path().to_owned();
+
+    tokio::task::spawn_blocking(move || kartoffels_world::replay(&path))
+        .await
+        .unwrap()
+        .unwrap();
 }
 
 #[tokio::test]
@@ -368,9 +1206,11 @@ async fn err_too_many_bots_queued() {
             auto_respawn: true,
             max_alive_bots: 10,
             max_queued_bots: 20,
+            ..Default::default()
         },
         ..config()
-    });
+    })
+    .unwrap();
 
     for _ in 0..30 {
         world
@@ -391,7 +1231,8 @@ async fn err_too_many_bots_queued() {
 #[tokio::test]
 async fn err_couldnt_parse_firmware_1() {
     let actual = kartoffels_world::create(config())
-        .create_bot(CreateBotRequest::new(&[0x00]))
+        .unwrap()
+        .create_bot(CreateBotRequest::new([0x00]))
         .await
         .unwrap_err()
         .to_fmt_string();
@@ -412,6 +1253,7 @@ async fn err_couldnt_parse_firmware_2() {
         include_bytes!("./acceptance/err-couldnt-parse-firmware-2/bot.elf");
 
     let actual = kartoffels_world::create(config())
+        .unwrap()
         .create_bot(CreateBotRequest::new(BOT))
         .await
         .unwrap_err()
@@ -433,6 +1275,19 @@ async fn err_couldnt_parse_firmware_2() {
     assert_eq!(expected.trim_end(), actual);
 }
 
+#[test]
+fn err_invalid_config() {
+    let mut config = config();
+
+    config.policy.max_alive_bots = 300;
+
+    let actual = kartoffels_world::create(config)
+        .unwrap_err()
+        .to_fmt_string();
+
+    assert_eq!("max-alive-bots must be at most 256", actual);
+}
+
 fn config() -> Config {
     Config {
         clock: Clock::manual(),
@@ -444,8 +1299,10 @@ fn config() -> Config {
             auto_respawn: true,
             max_alive_bots: 16,
             max_queued_bots: 16,
+            ..Default::default()
         },
         seed: Some(Default::default()),
+        sequential: false,
         theme: Some(Theme::Arena(ArenaTheme::new(12))),
     }
 }
@@ -1,6 +1,8 @@
 #![feature(async_fn_track_caller)]
 
 mod acceptance {
+    mod admin;
+    mod api;
     mod challenges;
     mod console;
     mod game;
@@ -14,7 +16,9 @@ use base64::Engine;
 use flate2::read::GzDecoder;
 use futures_util::{Sink, SinkExt, Stream, StreamExt};
 use kartoffels_store::{SessionId, Store};
+use kartoffels_utils::Id;
 use kartoffels_world::prelude::Handle as WorldHandle;
+use reqwest::{Method, RequestBuilder};
 use std::env;
 use std::io::{Cursor, Read};
 use std::net::SocketAddr;
@@ -265,6 +269,42 @@ impl TestContext {
             .unwrap();
     }
 
+    pub async fn get_json(&self, path: &str) -> serde_json::Value {
+        let url = format!("http://{}{path}", self.addr);
+
+        let body = reqwest::get(url)
+            .await
+            .unwrap()
+            .error_for_status()
+            .unwrap()
+            .text()
+            .await
+            .unwrap();
+
+        serde_json::from_str(&body).unwrap()
+    }
+
+    pub fn admin(&self, method: Method, path: &str) -> RequestBuilder {
+        let url = format!("http://{}{path}", self.addr);
+
+        reqwest::Client::new()
+            .request(method, url)
+            .bearer_auth(self.store.secret().unwrap().as_str())
+    }
+
+    pub async fn spectate(
+        &self,
+        world: Id,
+    ) -> impl Stream<Item = serde_json::Value> + Unpin {
+        let url = format!("ws://{}/worlds/{world}/spectate", self.addr);
+
+        let (client, _) = tokio_tungstenite::connect_async(url).await.unwrap();
+
+        client.map(|msg| {
+            serde_json::from_str(&msg.unwrap().into_text().unwrap()).unwrap()
+        })
+    }
+
     pub fn store(&self) -> &Store {
         &self.store
     }
@@ -0,0 +1,259 @@
+use crate::TestContext;
+use kartoffels_prefabs::DUMMY;
+use kartoffels_store::WorldType;
+use kartoffels_world::prelude::{
+    ArenaTheme, Clock, Config, CreateBotRequest, Theme,
+};
+use reqwest::Method;
+use serde_json::json;
+
+#[tokio::test]
+async fn unauthorized() {
+    let ctxt = TestContext::new([]).await;
+    let url = format!("http://{}/admin/worlds", ctxt.addr);
+
+    let status = reqwest::get(&url).await.unwrap().status();
+
+    assert_eq!(401, status.as_u16());
+
+    let status = reqwest::Client::new()
+        .get(&url)
+        .bearer_auth("invalid")
+        .send()
+        .await
+        .unwrap()
+        .status();
+
+    assert_eq!(401, status.as_u16());
+}
+
+#[tokio::test]
+async fn manage_worlds() {
+    let ctxt = TestContext::new([]).await;
+
+    let resp = ctxt
+        .admin(Method::POST, "/admin/worlds")
+        .json(&json!({
+            "name": "first-world",
+            "theme": "arena:radius=10",
+        }))
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(201, resp.status().as_u16());
+
+    let id = resp.json::<serde_json::Value>().await.unwrap()["id"]
+        .as_str()
+        .unwrap()
+        .to_owned();
+
+    let resp = ctxt
+        .admin(Method::POST, "/admin/worlds")
+        .json(&json!({
+            "name": "second-world",
+            "theme": "arena:radius=10",
+            "private": true,
+        }))
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(201, resp.status().as_u16());
+
+    let worlds = ctxt.store().worlds(None);
+
+    assert_eq!(2, worlds.len());
+    assert_eq!(WorldType::Public, worlds[0].0);
+    assert_eq!("first-world", worlds[0].1.name().as_str());
+    assert_eq!(WorldType::Private, worlds[1].0);
+    assert_eq!("second-world", worlds[1].1.name().as_str());
+
+    // ---
+
+    let resp = ctxt
+        .admin(Method::POST, &format!("/admin/worlds/{id}/rename"))
+        .json(&json!({ "name": "renamed-world" }))
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(204, resp.status().as_u16());
+
+    let worlds = ctxt
+        .admin(Method::GET, "/admin/worlds")
+        .send()
+        .await
+        .unwrap()
+        .json::<serde_json::Value>()
+        .await
+        .unwrap();
+
+    assert_eq!(id, worlds[0]["id"]);
+    assert_eq!("renamed-world", worlds[0]["name"]);
+    assert_eq!("public", worlds[0]["type"]);
+    assert_eq!("second-world", worlds[1]["name"]);
+    assert_eq!("private", worlds[1]["type"]);
+
+    // ---
+
+    let resp = ctxt
+        .admin(Method::DELETE, &format!("/admin/worlds/{id}"))
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(204, resp.status().as_u16());
+    assert_eq!(1, ctxt.store().worlds(None).len());
+
+    let resp = ctxt
+        .admin(Method::DELETE, &format!("/admin/worlds/{id}"))
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(404, resp.status().as_u16());
+}
+
+#[tokio::test]
+async fn create_world_with_invalid_theme() {
+    let ctxt = TestContext::new([]).await;
+
+    let resp = ctxt
+        .admin(Method::POST, "/admin/worlds")
+        .json(&json!({
+            "name": "world",
+            "theme": "invalid",
+        }))
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(400, resp.status().as_u16());
+
+    let body = resp.json::<serde_json::Value>().await.unwrap();
+
+    assert!(body["error"]
+        .as_str()
+        .unwrap()
+        .starts_with("couldn't parse theme: invalid"));
+}
+
+#[tokio::test]
+async fn control_world() {
+    let ctxt = {
+        let world = kartoffels_world::create(Config {
+            name: "world".into(),
+            ..Default::default()
+        })
+        .unwrap();
+
+        TestContext::new([world]).await
+    };
+
+    let world = ctxt.store().public_worlds()[0].clone();
+    let mut snapshots = world.snapshots();
+
+    for path in ["pause", "resume"] {
+        let resp = ctxt
+            .admin(
+                Method::POST,
+                &format!("/admin/worlds/{}/{path}", world.id()),
+            )
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(204, resp.status().as_u16());
+    }
+
+    let resp = ctxt
+        .admin(
+            Method::POST,
+            &format!("/admin/worlds/{}/overclock", world.id()),
+        )
+        .json(&json!({ "clock": "Faster" }))
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(204, resp.status().as_u16());
+
+    while snapshots.next().await.unwrap().clock != Clock::Faster {
+        //
+    }
+
+    let resp = ctxt
+        .admin(
+            Method::POST,
+            &format!("/admin/worlds/{}/overclock", world.id()),
+        )
+        .json(&json!({ "clock": "Unlimited" }))
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(400, resp.status().as_u16());
+}
+
+#[tokio::test]
+async fn manage_bots() {
+    let ctxt = {
+        let world = kartoffels_world::create(Config {
+            clock: Clock::manual(),
+            events: true,
+            name: "world".into(),
+            seed: Some(Default::default()),
+            theme: Some(Theme::Arena(ArenaTheme::new(4))),
+            ..Default::default()
+        })
+        .unwrap();
+
+        TestContext::new([world]).await
+    };
+
+    let world = ctxt.store().public_worlds()[0].clone();
+    let mut snapshots = world.snapshots();
+
+    let id = world
+        .create_bot(CreateBotRequest::new(DUMMY).instant())
+        .await
+        .unwrap();
+
+    let resp = ctxt
+        .admin(
+            Method::POST,
+            &format!("/admin/worlds/{}/bots/{id}/kill", world.id()),
+        )
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(204, resp.status().as_u16());
+
+    world.tick(1).await.unwrap();
+
+    let snapshot = snapshots.next().await.unwrap();
+
+    assert!(!snapshot.bots.alive.has(id));
+    assert!(snapshot.bots.has(id));
+
+    // ---
+
+    let resp = ctxt
+        .admin(
+            Method::DELETE,
+            &format!("/admin/worlds/{}/bots/{id}", world.id()),
+        )
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(204, resp.status().as_u16());
+
+    world.tick(1).await.unwrap();
+
+    let snapshot = snapshots.next().await.unwrap();
+
+    assert!(!snapshot.bots.has(id));
+}
@@ -0,0 +1,122 @@
+use crate::TestContext;
+use futures_util::StreamExt;
+use kartoffels_prefabs::DUMMY;
+use kartoffels_world::prelude::{
+    ArenaTheme, Clock, Config, CreateBotRequest, Theme,
+};
+use serde_json::json;
+
+#[tokio::test]
+async fn list_worlds() {
+    let ctxt = {
+        let first = kartoffels_world::create(Config {
+            name: "first-world".into(),
+            ..Default::default()
+        })
+        .unwrap();
+
+        let second = kartoffels_world::create(Config {
+            name: "second-world".into(),
+            ..Default::default()
+        })
+        .unwrap();
+
+        TestContext::new([first, second]).await
+    };
+
+    let worlds = ctxt.get_json("/worlds").await;
+
+    let expected = json!(ctxt
+        .store()
+        .public_worlds()
+        .iter()
+        .map(|world| json!({
+            "id": world.id().to_string(),
+            "name": world.name().as_str(),
+        }))
+        .collect::<Vec<_>>());
+
+    assert_eq!(expected, worlds);
+    assert_eq!("first-world", worlds[0]["name"]);
+    assert_eq!("second-world", worlds[1]["name"]);
+}
+
+#[tokio::test]
+async fn spectate_world() {
+    let ctxt = {
+        let world = kartoffels_world::create(Config {
+            clock: Clock::manual(),
+            events: true,
+            name: "world".into(),
+            seed: Some(Default::default()),
+            theme: Some(Theme::Arena(ArenaTheme::new(4))),
+            ..Default::default()
+        })
+        .unwrap();
+
+        TestContext::new([world]).await
+    };
+
+    let world = ctxt.store().public_worlds()[0].clone();
+    let mut stream = ctxt.spectate(world.id()).await;
+
+    let msg = stream.next().await.unwrap();
+
+    assert_eq!("snapshot", msg["type"]);
+    assert_eq!(json!([]), msg["bots"]["alive"]["entries"]);
+
+    // ---
+
+    let id = world
+        .create_bot(CreateBotRequest::new(DUMMY).instant())
+        .await
+        .unwrap();
+
+    world.tick(1).await.unwrap();
+
+    let mut seen_event = false;
+    let mut seen_snapshot = false;
+
+    while !seen_event || !seen_snapshot {
+        let msg = stream.next().await.unwrap();
+
+        match msg["type"].as_str().unwrap() {
+            "event" => {
+                assert_eq!("bot_born", msg["event"]["type"]);
+                assert_eq!(id.to_string(), msg["event"]["id"]);
+
+                seen_event = true;
+            }
+
+            "snapshot" => {
+                if msg["bots"]["alive"]["entries"][0]["id"] == id.to_string() {
+                    seen_snapshot = true;
+                }
+            }
+
+            ty => {
+                panic!("unexpected message: {ty}");
+            }
+        }
+    }
+}
+
+#[tokio::test]
+async fn spectate_unknown_world() {
+    let ctxt = TestContext::new([]).await;
+    let url =
+        format!("http://{}/worlds/1234-1234-1234-1234/spectate", ctxt.addr);
+
+    let status = reqwest::Client::new()
+        .get(url)
+        .header("connection", "upgrade")
+        .header("upgrade", "websocket")
+        .header("sec-websocket-version", "13")
+        .header("sec-websocket-key", "dGhlIHNhbXBsZSBub25jZQ==")
+        .send()
+        .await
+        .unwrap()
+        .status();
+
+    assert_eq!(404, status.as_u16());
+}
@@ -1,4 +1,5 @@
 use crate::TestContext;
+use kartoffels_store::PlayerId;
 use termwiz::input::{KeyCode, Modifiers};
 
 async fn logged_ctxt() -> TestContext {
@@ -109,3 +110,51 @@ async fn cmd_world_mgmt() {
     ctxt.wait_for_window("play").await;
     ctxt.see_frame("console/cmd-world-mgmt/2.txt").await;
 }
+
+#[tokio::test]
+async fn cmd_player_mgmt() {
+    let mut ctxt = logged_ctxt().await;
+    let player: PlayerId = "SHA256:q0u8kXzY".parse().unwrap();
+
+    drop(ctxt.store().create_session(Some(player.clone()), None));
+
+    ctxt.write("list-players").await;
+    ctxt.press(KeyCode::Enter).await;
+    ctxt.wait_for("SHA256:q0u8kXzY | user | 1 sessions").await;
+
+    // ---
+
+    ctxt.write("grant-admin SHA256:q0u8kXzY").await;
+    ctxt.press(KeyCode::Enter).await;
+    ctxt.wait_for("; grant-admin").await;
+
+    assert!(ctxt.store().player(&player).unwrap().admin);
+
+    ctxt.write("create-world zoo --theme arena:radius=8 --private --owner SHA256:q0u8kXzY").await;
+    ctxt.press(KeyCode::Enter).await;
+    ctxt.wait_for("id: 0000-0000-0000-0001").await;
+
+    ctxt.write("list-players").await;
+    ctxt.press(KeyCode::Enter).await;
+    ctxt.wait_for("SHA256:q0u8kXzY | admin | 1 sessions | 0 bots | 1 worlds")
+        .await;
+
+    assert!(ctxt
+        .store()
+        .create_session(Some(player.clone()), None)
+        .unwrap()
+        .with(|sess| sess.is_admin()));
+    assert_eq!(1, ctxt.store().player_worlds(&player).len());
+
+    // ---
+
+    ctxt.write("revoke-admin SHA256:q0u8kXzY").await;
+    ctxt.press(KeyCode::Enter).await;
+    ctxt.wait_for("; revoke-admin").await;
+
+    assert!(!ctxt.store().player(&player).unwrap().admin);
+
+    ctxt.write("grant-admin SHA256:unknown").await;
+    ctxt.press(KeyCode::Enter).await;
+    ctxt.wait_for("couldn't find player `sha256:unknown`").await;
+}
@@ -12,7 +12,8 @@ async fn smoke() {
             name: "first-world".into(),
             theme: Some(Theme::Arena(ArenaTheme::new(4))),
             ..Default::default()
-        });
+        })
+        .unwrap();
 
         let second = kartoffels_world::create(Config {
             name: "second-world".into(),
@@ -24,13 +25,15 @@ async fn smoke() {
             seed: Some(Default::default()),
             theme: Some(Theme::Arena(ArenaTheme::new(4))),
             ..Default::default()
-        });
+        })
+        .unwrap();
 
         let third = kartoffels_world::create(Config {
             name: "third-world".into(),
             theme: Some(Theme::Arena(ArenaTheme::new(4))),
             ..Default::default()
-        });
+        })
+        .unwrap();
 
         TestContext::new([first, second, third]).await
     };
@@ -52,6 +55,19 @@ async fn smoke() {
 
     // ---
 
+    ctxt.press(KeyCode::Char('m')).await;
+    ctxt.wait_for("[enter] send").await;
+    ctxt.write("hi").await;
+    ctxt.wait_for("> hi").await;
+
+    ctxt.press(KeyCode::Enter).await;
+    ctxt.wait_while("> hi").await;
+    ctxt.press(KeyCode::Escape).await;
+    ctxt.wait_for("[m] message-bot").await;
+    ctxt.see_frame("game/smoke/message.txt").await;
+
+    // ---
+
     ctxt.press(KeyCode::Char(' ')).await;
     ctxt.wait_for("paused").await;
     ctxt.see_frame("game/smoke/pause-1.txt").await;
@@ -76,9 +92,13 @@ async fn smoke() {
     ctxt.wait_for_window("bots").await;
     ctxt.see_frame("game/smoke/bots-1.txt").await;
 
+    ctxt.press(KeyCode::Char('m')).await;
+    ctxt.wait_for("[m] show-all").await;
+    ctxt.see_frame("game/smoke/bots-2.txt").await;
+
     ctxt.press(KeyCode::Escape).await;
     ctxt.wait_while_modal("bots").await;
-    ctxt.see_frame("game/smoke/bots-2.txt").await;
+    ctxt.see_frame("game/smoke/bots-3.txt").await;
 
     // ---
 
@@ -125,7 +145,8 @@ async fn http_upload_ok() {
             },
             seed: Some(Default::default()),
             ..Default::default()
-        });
+        })
+        .unwrap();
 
         TestContext::new([world]).await
     };
@@ -155,7 +176,8 @@ async fn http_upload_err() {
         let world = kartoffels_world::create(Config {
             name: "world".into(),
             ..Default::default()
-        });
+        })
+        .unwrap();
 
         TestContext::new([world]).await
     };
@@ -190,7 +212,8 @@ async fn cli_upload_err() {
         let world = kartoffels_world::create(Config {
             name: "world".into(),
             ..Default::default()
-        });
+        })
+        .unwrap();
 
         TestContext::new([world]).await
     };
@@ -1,4 +1,5 @@
 use crate::TestContext;
+use termwiz::input::KeyCode;
 
 #[tokio::test]
 async fn smoke() {
@@ -15,3 +16,15 @@ async fn too_small_screen() {
     ctxt.wait_for("ouch").await;
     ctxt.see_frame("index/too-small-screen/1.txt").await;
 }
+
+#[tokio::test]
+async fn leaderboard() {
+    let mut ctxt = TestContext::new([]).await;
+
+    ctxt.press(KeyCode::Char('l')).await;
+    ctxt.wait_for_window("leaderboard").await;
+    ctxt.see_frame("index/leaderboard/1.txt").await;
+
+    ctxt.press(KeyCode::Escape).await;
+    ctxt.wait_for("[l] leaderboard").await;
+}